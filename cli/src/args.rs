@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use kaswallet_client::model::InvoiceState;
+use proto::kaswallet_proto::{CoinSelectionStrategy, FeePriority, KeychainFilter};
 
 pub const DEFAULT_DAEMON_ADDRESS: &str = "http://127.0.0.1:8082";
 
@@ -26,12 +28,160 @@ pub enum Commands {
     ShowAddresses {
         #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
         daemon_address: String,
+
+        /// Which keychain to list
+        #[arg(long = "keychain", value_enum, default_value = "external")]
+        keychain: KeychainArg,
+
+        /// Only list addresses that have ever held funds
+        #[arg(long = "used", conflicts_with = "unused")]
+        used: bool,
+
+        /// Only list addresses that have never held funds
+        #[arg(long = "unused")]
+        unused: bool,
+
+        /// Show the keychain, index, derivation path, balance and UTXO count of each address
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
     },
 
     /// Generates a new public address of the current wallet
     NewAddress {
         #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
         daemon_address: String,
+
+        /// Number of consecutive addresses to generate (at most 10000)
+        #[arg(short = 'n', long = "count", default_value_t = 1)]
+        count: u32,
+
+        /// Also print a kaspa: payment URI for the address
+        #[arg(long = "uri", conflicts_with = "count")]
+        uri: bool,
+
+        /// Also print the payment URI as a QR code
+        #[arg(long = "qr", conflicts_with = "count")]
+        qr: bool,
+
+        /// Amount to request in the payment URI (in KAS)
+        #[arg(short = 'a', long = "amount", conflicts_with = "count")]
+        amount: Option<String>,
+
+        /// Label for the payment URI, e.g. the receiver's name
+        #[arg(long = "label", conflicts_with = "count")]
+        label: Option<String>,
+
+        /// Message for the payment URI, e.g. what the payment is for
+        #[arg(short = 'm', long = "message", conflicts_with = "count")]
+        message: Option<String>,
+    },
+
+    /// Check an address and whether it belongs to the current wallet
+    ValidateAddress {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The address to check
+        address: String,
+    },
+
+    /// Label one of the wallet's addresses
+    SetLabel {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The wallet address to label
+        address: String,
+
+        /// The label; an empty label removes it
+        label: String,
+    },
+
+    /// List the labels of the wallet's addresses
+    ListLabels {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+    },
+
+    /// Save an external address under an alias usable as --to
+    AddContact {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// ASCII letters, digits, '-', '_' and '.', at most 64 characters
+        alias: String,
+
+        /// The contact's address
+        address: String,
+
+        /// A note stored with the contact
+        #[arg(short = 'n', long = "note", default_value = "")]
+        note: String,
+    },
+
+    /// Remove a contact from the address book
+    RemoveContact {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The contact's alias
+        alias: String,
+    },
+
+    /// List the contacts in the address book
+    ListContacts {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+    },
+
+    /// Request a payment to a fresh address and print its payment URI
+    CreateInvoice {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The amount to request (in KAS)
+        amount: String,
+
+        /// A note for the payer, included in the payment URI
+        #[arg(short = 'm', long = "memo", default_value = "")]
+        memo: String,
+
+        /// Seconds until an unpaid invoice expires; never if omitted
+        #[arg(long = "expiry-seconds")]
+        expiry_seconds: Option<u64>,
+    },
+
+    /// Show an invoice and how much has been paid towards it
+    GetInvoice {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The invoice id
+        invoice_id: u64,
+    },
+
+    /// List invoices
+    ListInvoices {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Only invoices in this state (can be specified multiple times)
+        #[arg(long = "state", value_enum)]
+        states: Vec<InvoiceStateArg>,
+    },
+
+    /// List invoice state changes
+    InvoiceEvents {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Only events after this sequence number
+        #[arg(long = "after", default_value = "0")]
+        after_sequence: u64,
+
+        /// At most this many events; all if omitted
+        #[arg(long = "limit")]
+        limit: Option<u32>,
     },
 
     /// Get UTXOs for the wallet
@@ -52,6 +202,36 @@ pub enum Commands {
         include_dust: bool,
     },
 
+    /// Exclude UTXOs from automatic coin selection until they are unfrozen
+    FreezeUtxos {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Outpoints to freeze as <transaction id>:<index>
+        #[arg(required = true)]
+        outpoints: Vec<String>,
+
+        /// Why the UTXOs are frozen, stored with the freeze
+        #[arg(short = 'r', long = "reason", default_value = "")]
+        reason: String,
+    },
+
+    /// Make frozen UTXOs available to coin selection again
+    UnfreezeUtxos {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Outpoints to unfreeze as <transaction id>:<index>
+        #[arg(required = true)]
+        outpoints: Vec<String>,
+    },
+
+    /// List frozen UTXOs
+    ListFrozenUtxos {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+    },
+
     /// Sends a Kaspa transaction to a public address
     Send {
         #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
@@ -61,9 +241,13 @@ pub enum Commands {
         #[arg(short = 'f', long = "from")]
         from_addresses: Vec<String>,
 
-        /// The public address to send Kaspa to
-        #[arg(short = 't', long = "to")]
-        to_address: String,
+        /// The public address or contact alias to send Kaspa to
+        #[arg(short = 't', long = "to", required_unless_present = "uri")]
+        to_address: Option<String>,
+
+        /// A kaspa: payment URI to pay, instead of --to; its amount, if any, replaces --amount
+        #[arg(long = "uri", conflicts_with = "to_address")]
+        uri: Option<String>,
 
         /// An amount to send in Kaspa (e.g. 1234.12345678)
         #[arg(short = 'a', long = "amount", conflicts_with = "is_send_all")]
@@ -73,6 +257,18 @@ pub enum Commands {
         #[arg(long = "send-all", conflicts_with = "send_amount")]
         is_send_all: bool,
 
+        /// Deduct the fee from the amount, so the recipient pays it
+        #[arg(long = "subtract-fee", conflicts_with = "is_send_all")]
+        subtract_fee_from_amount: bool,
+
+        /// How to pick inputs from the wallet's UTXOs (defaults to the daemon's strategy)
+        #[arg(long = "coin-selection", value_enum)]
+        coin_selection: Option<CoinSelection>,
+
+        /// Subnetwork (lane) namespace to build on, e.g. 97b10000 (defaults to the daemon's)
+        #[arg(long = "subnetwork-id")]
+        subnetwork_id: Option<String>,
+
         /// Transaction payload (hex-encoded)
         #[arg(long = "payload")]
         payload: Option<String>,
@@ -93,6 +289,14 @@ pub enum Commands {
         #[arg(long = "fee-max", conflicts_with_all = ["max_fee_rate", "exact_fee_rate"])]
         max_fee: Option<u64>,
 
+        /// Use kaspad's current fee estimate for this priority
+        #[arg(long = "fee-priority", value_enum, conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee"])]
+        fee_priority: Option<Priority>,
+
+        /// Use the cheapest fee rate kaspad expects to be accepted within this many seconds
+        #[arg(long = "fee-target-seconds", conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee", "fee_priority"])]
+        fee_target_seconds: Option<u32>,
+
         /// Wallet password
         #[arg(short = 'p', long = "password")]
         password: Option<String>,
@@ -100,6 +304,11 @@ pub enum Commands {
         /// Show serialized transactions
         #[arg(short = 's', long = "show-serialized")]
         show_transactions: bool,
+
+        /// Key making retries safe: repeating it returns the original transactions instead of
+        /// paying again
+        #[arg(long = "idempotency-key")]
+        idempotency_key: Option<String>,
     },
 
     /// Create an unsigned Kaspa transaction
@@ -107,9 +316,13 @@ pub enum Commands {
         #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
         daemon_address: String,
 
-        /// The public address to send Kaspa to
-        #[arg(short = 't', long = "to")]
-        to_address: String,
+        /// The public address or contact alias to send Kaspa to
+        #[arg(short = 't', long = "to", required_unless_present = "uri")]
+        to_address: Option<String>,
+
+        /// A kaspa: payment URI to pay, instead of --to; its amount, if any, replaces --amount
+        #[arg(long = "uri", conflicts_with = "to_address")]
+        uri: Option<String>,
 
         /// An amount to send in Kaspa (e.g. 1234.12345678)
         #[arg(short = 'a', long = "amount", conflicts_with = "is_send_all")]
@@ -119,6 +332,18 @@ pub enum Commands {
         #[arg(long = "send-all", conflicts_with = "send_amount")]
         is_send_all: bool,
 
+        /// Deduct the fee from the amount, so the recipient pays it
+        #[arg(long = "subtract-fee", conflicts_with = "is_send_all")]
+        subtract_fee_from_amount: bool,
+
+        /// How to pick inputs from the wallet's UTXOs (defaults to the daemon's strategy)
+        #[arg(long = "coin-selection", value_enum)]
+        coin_selection: Option<CoinSelection>,
+
+        /// Subnetwork (lane) namespace to build on, e.g. 97b10000 (defaults to the daemon's)
+        #[arg(long = "subnetwork-id")]
+        subnetwork_id: Option<String>,
+
         /// Specific public address to send Kaspa from (can be specified multiple times)
         #[arg(short = 'f', long = "from")]
         from_addresses: Vec<String>,
@@ -142,6 +367,158 @@ pub enum Commands {
         /// Maximum fee in Sompi
         #[arg(long = "fee-max", conflicts_with_all = ["max_fee_rate", "exact_fee_rate"])]
         max_fee: Option<u64>,
+
+        /// Use kaspad's current fee estimate for this priority
+        #[arg(long = "fee-priority", value_enum, conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee"])]
+        fee_priority: Option<Priority>,
+
+        /// Use the cheapest fee rate kaspad expects to be accepted within this many seconds
+        #[arg(long = "fee-target-seconds", conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee", "fee_priority"])]
+        fee_target_seconds: Option<u32>,
+    },
+
+    /// Show how much can be sent to an address once fees are paid
+    MaxSendable {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The public address or contact alias to send Kaspa to
+        #[arg(short = 't', long = "to")]
+        to_address: String,
+
+        /// Specific public address to send Kaspa from (can be specified multiple times)
+        #[arg(short = 'f', long = "from", conflicts_with = "utxos")]
+        from_addresses: Vec<String>,
+
+        /// Specific UTXO to spend as <transaction id>:<index> (can be specified multiple times)
+        #[arg(long = "utxo", conflicts_with = "from_addresses")]
+        utxos: Vec<String>,
+
+        /// Maximum fee rate in Sompi/gram
+        #[arg(long = "fee-rate-max", conflicts_with_all = ["exact_fee_rate", "max_fee"])]
+        max_fee_rate: Option<f64>,
+
+        /// Exact fee rate in Sompi/gram
+        #[arg(long = "fee-rate-exact", conflicts_with_all = ["max_fee_rate", "max_fee"])]
+        exact_fee_rate: Option<f64>,
+
+        /// Maximum fee in Sompi
+        #[arg(long = "fee-max", conflicts_with_all = ["max_fee_rate", "exact_fee_rate"])]
+        max_fee: Option<u64>,
+
+        /// Use kaspad's current fee estimate for this priority
+        #[arg(long = "fee-priority", value_enum, conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee"])]
+        fee_priority: Option<Priority>,
+
+        /// Use the cheapest fee rate kaspad expects to be accepted within this many seconds
+        #[arg(long = "fee-target-seconds", conflicts_with_all = ["max_fee_rate", "exact_fee_rate", "max_fee", "fee_priority"])]
+        fee_target_seconds: Option<u32>,
+    },
+
+    /// Merge small UTXOs into one to reduce the fees of future sends
+    Consolidate {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Number of matching UTXOs to leave after consolidation
+        #[arg(long = "target-utxo-count", default_value = "1")]
+        target_utxo_count: u32,
+
+        /// Leave UTXOs smaller than this amount in Kaspa alone (e.g. 0.5)
+        #[arg(long = "min-input")]
+        min_input: Option<String>,
+
+        /// Leave UTXOs larger than this amount in Kaspa alone (e.g. 100)
+        #[arg(long = "max-input")]
+        max_input: Option<String>,
+
+        /// Only consolidate UTXOs of this address (can be specified multiple times)
+        #[arg(short = 'f', long = "from")]
+        from_addresses: Vec<String>,
+
+        /// Maximum fee rate in Sompi/gram
+        #[arg(long = "fee-rate-max")]
+        max_fee_rate: Option<f64>,
+
+        /// Sign and broadcast the transactions instead of printing them unsigned
+        #[arg(long = "broadcast")]
+        broadcast: bool,
+
+        /// Wallet password (with --broadcast)
+        #[arg(short = 'p', long = "password", requires = "broadcast")]
+        password: Option<String>,
+    },
+
+    /// Merge dust UTXOs into change when the fee rate makes it economic
+    SweepDust {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Only sweep dust of this address (can be specified multiple times)
+        #[arg(short = 'f', long = "from")]
+        from_addresses: Vec<String>,
+
+        /// Maximum fee rate in Sompi/gram
+        #[arg(long = "fee-rate-max", conflicts_with_all = ["fee_priority", "fee_target_seconds"])]
+        max_fee_rate: Option<f64>,
+
+        /// Use kaspad's current fee estimate for this priority
+        #[arg(
+            long = "fee-priority",
+            value_enum,
+            conflicts_with = "fee_target_seconds"
+        )]
+        fee_priority: Option<Priority>,
+
+        /// Use the cheapest fee rate kaspad expects to be accepted within this many seconds
+        #[arg(long = "fee-target-seconds")]
+        fee_target_seconds: Option<u32>,
+
+        /// Only report what the sweep would cost
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Wallet password
+        #[arg(short = 'p', long = "password", conflicts_with = "dry_run")]
+        password: Option<String>,
+    },
+
+    /// Move the funds of an external private key or mnemonic to a new wallet address
+    SweepExternal {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// Prompt for a mnemonic instead of a private key (WIF or hex)
+        #[arg(long = "mnemonic")]
+        mnemonic: bool,
+
+        /// Account derivation path of the mnemonic (default m/44'/111111'/0')
+        #[arg(long = "derivation-path", requires = "mnemonic")]
+        derivation_path: Option<String>,
+
+        /// Unused addresses to scan per keychain of the mnemonic (default 20, at most 1000)
+        #[arg(long = "gap-limit", requires = "mnemonic")]
+        gap_limit: Option<u32>,
+
+        /// Maximum fee rate in Sompi/gram
+        #[arg(long = "fee-rate-max", conflicts_with_all = ["fee_priority", "fee_target_seconds"])]
+        max_fee_rate: Option<f64>,
+
+        /// Use kaspad's current fee estimate for this priority
+        #[arg(
+            long = "fee-priority",
+            value_enum,
+            conflicts_with = "fee_target_seconds"
+        )]
+        fee_priority: Option<Priority>,
+
+        /// Use the cheapest fee rate kaspad expects to be accepted within this many seconds
+        #[arg(long = "fee-target-seconds")]
+        fee_target_seconds: Option<u32>,
+
+        /// Only report what would be swept
+        #[arg(long = "dry-run")]
+        dry_run: bool,
     },
 
     /// Sign the given unsigned transaction(s)
@@ -162,6 +539,37 @@ pub enum Commands {
         password: Option<String>,
     },
 
+    /// Sign a message with the key of a wallet address to prove control of it
+    SignMessage {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The wallet address whose key signs
+        address: String,
+
+        /// The message to sign
+        message: String,
+
+        /// Wallet password
+        #[arg(short = 'p', long = "password")]
+        password: Option<String>,
+    },
+
+    /// Verify a message signature against an address
+    VerifyMessage {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The address that signed
+        address: String,
+
+        /// The signed message
+        message: String,
+
+        /// The signature (encoded in hex)
+        signature: String,
+    },
+
     /// Broadcast the given signed transaction(s)
     Broadcast {
         #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
@@ -174,6 +582,25 @@ pub enum Commands {
         /// File containing the signed transaction(s) to broadcast (encoded in hex)
         #[arg(short = 'F', long = "transaction-file", conflicts_with = "transaction")]
         transaction_file: Option<String>,
+
+        /// Key making retries safe: repeating it returns the original transaction ids instead
+        /// of broadcasting again
+        #[arg(long = "idempotency-key")]
+        idempotency_key: Option<String>,
+    },
+
+    /// Release the UTXOs reserved for the given unsigned transaction(s) that will not be broadcast
+    ReleaseReservation {
+        #[arg(short = 'd', long = "daemonaddress", default_value = DEFAULT_DAEMON_ADDRESS)]
+        daemon_address: String,
+
+        /// The unsigned transaction(s) to release (encoded in hex)
+        #[arg(short = 't', long = "transaction", conflicts_with = "transaction_file")]
+        transaction: Option<String>,
+
+        /// File containing the unsigned transaction(s) to release (encoded in hex)
+        #[arg(short = 'F', long = "transaction-file", conflicts_with = "transaction")]
+        transaction_file: Option<String>,
     },
 
     /// Get the wallet daemon version
@@ -188,3 +615,83 @@ pub enum Commands {
         daemon_address: String,
     },
 }
+
+/// Values accepted by `--coin-selection`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CoinSelection {
+    SmallestFirst,
+    LargestFirst,
+    BranchAndBound,
+    OldestFirst,
+    AvoidAddressMixing,
+}
+
+impl From<CoinSelection> for CoinSelectionStrategy {
+    fn from(value: CoinSelection) -> Self {
+        match value {
+            CoinSelection::SmallestFirst => CoinSelectionStrategy::SmallestFirst,
+            CoinSelection::LargestFirst => CoinSelectionStrategy::LargestFirst,
+            CoinSelection::BranchAndBound => CoinSelectionStrategy::BranchAndBound,
+            CoinSelection::OldestFirst => CoinSelectionStrategy::OldestFirst,
+            CoinSelection::AvoidAddressMixing => CoinSelectionStrategy::AvoidAddressMixing,
+        }
+    }
+}
+
+/// Values accepted by `--keychain`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum KeychainArg {
+    External,
+    Internal,
+    All,
+}
+
+impl From<KeychainArg> for KeychainFilter {
+    fn from(value: KeychainArg) -> Self {
+        match value {
+            KeychainArg::External => KeychainFilter::External,
+            KeychainArg::Internal => KeychainFilter::Internal,
+            KeychainArg::All => KeychainFilter::All,
+        }
+    }
+}
+
+/// Values accepted by `--state`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum InvoiceStateArg {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+    Overpaid,
+    Expired,
+}
+
+impl From<InvoiceStateArg> for InvoiceState {
+    fn from(value: InvoiceStateArg) -> Self {
+        match value {
+            InvoiceStateArg::Unpaid => InvoiceState::Unpaid,
+            InvoiceStateArg::PartiallyPaid => InvoiceState::PartiallyPaid,
+            InvoiceStateArg::Paid => InvoiceState::Paid,
+            InvoiceStateArg::Overpaid => InvoiceState::Overpaid,
+            InvoiceStateArg::Expired => InvoiceState::Expired,
+        }
+    }
+}
+
+/// Values accepted by `--fee-priority`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Priority {
+    Low,
+    Normal,
+    High,
+}
+
+impl From<Priority> for FeePriority {
+    fn from(value: Priority) -> Self {
+        match value {
+            Priority::Low => FeePriority::Low,
+            Priority::Normal => FeePriority::Normal,
+            Priority::High => FeePriority::High,
+        }
+    }
+}
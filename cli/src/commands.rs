@@ -1,15 +1,26 @@
+use crate::args::{CoinSelection, InvoiceStateArg, KeychainArg, Priority};
 use crate::utils::{format_kas, kas_to_sompi};
 use common::error_location::ErrorLocation;
 use common::errors::{StorageError, UserInputError, WalletError, WalletResult as Result};
 use common::model::WalletSignableTransaction;
 use kaswallet_client::client::KaswalletClient;
+use kaswallet_client::model::{ExternalKeySource, InvoiceInfo};
 use prost::Message;
 use proto::kaswallet_proto::WalletSignableTransaction as ProtoWalletSignableTransaction;
-use proto::kaswallet_proto::{FeePolicy, TransactionDescription, fee_policy};
+use proto::kaswallet_proto::{
+    CoinSelectionStrategy, FeePolicy, FeePriority, Outpoint, TransactionDescription, UsageFilter,
+    fee_policy,
+};
+use qrcode::QrCode;
+use qrcode::render::unicode;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Write};
+use std::time::Duration;
+
+/// Addresses fetched per request by `show-addresses`.
+const SHOW_ADDRESSES_PAGE_SIZE: u32 = 1000;
 
 // Generic CLI argument validation failure. Reserve `InvalidAmount` for actual
 // amount-string parsing — using it for every kind of CLI error makes
@@ -51,6 +62,8 @@ struct AddressBalancesOutput {
 #[derive(Serialize)]
 struct AddressDetailOutput {
     address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
     available: u64,
     pending: u64,
     utxos: Vec<UtxoDetailOutput>,
@@ -92,10 +105,11 @@ pub async fn balance(daemon_address: &str, verbose: bool) -> Result<()> {
         );
         for addr_balance in &balance_info.address_balances {
             println!(
-                "{} {} {}",
+                "{} {} {}{}",
                 addr_balance.address,
                 format_kas(addr_balance.available),
-                format_kas(addr_balance.pending)
+                format_kas(addr_balance.pending),
+                label_suffix(&addr_balance.label)
             );
         }
         println!(
@@ -110,38 +124,182 @@ pub async fn balance(daemon_address: &str, verbose: bool) -> Result<()> {
         format_kas(balance_info.pending),
         pending_suffix
     );
+    if balance_info.frozen > 0 {
+        println!(
+            "Frozen, not included above, KAS {}",
+            format_kas(balance_info.frozen).trim()
+        );
+    }
+    if balance_info.covenant_bound > 0 {
+        println!(
+            "Covenant-bound (not spendable by this wallet), not included above, KAS {}",
+            format_kas(balance_info.covenant_bound).trim()
+        );
+    }
+    if balance_info.dust > 0 {
+        println!(
+            "Dust, not included above, KAS {}",
+            format_kas(balance_info.dust).trim()
+        );
+    }
 
     Ok(())
 }
 
 /// Show all generated addresses
-pub async fn show_addresses(daemon_address: &str) -> Result<()> {
+pub async fn show_addresses(
+    daemon_address: &str,
+    keychain: KeychainArg,
+    used: bool,
+    unused: bool,
+    verbose: bool,
+) -> Result<()> {
     let mut client = connect(daemon_address).await?;
 
-    let addresses = client.get_addresses().await?;
+    let usage = if used {
+        UsageFilter::Used
+    } else if unused {
+        UsageFilter::Unused
+    } else {
+        UsageFilter::All
+    };
+    let mut addresses = vec![];
+    let mut page_token = None;
+    loop {
+        let page = client
+            .list_addresses(keychain.into(), usage, SHOW_ADDRESSES_PAGE_SIZE, page_token)
+            .await?;
+        addresses.extend(page.addresses);
+        match page.next_page_token {
+            Some(next_page_token) => page_token = Some(next_page_token),
+            None => break,
+        }
+    }
 
     println!("Addresses ({}):", addresses.len());
     for address in &addresses {
-        println!("{}", address);
+        if verbose {
+            println!(
+                "{} {:?} {} {} {} KAS in {} UTXO(s){}",
+                address.address,
+                address.keychain,
+                address.index,
+                address.derivation_path,
+                format_kas(address.balance).trim(),
+                address.utxo_count,
+                label_suffix(&address.label)
+            );
+        } else {
+            println!("{}{}", address.address, label_suffix(&address.label));
+        }
     }
 
-    println!();
-    println!(
-        "Note: the above are only addresses that were manually created by the 'new-address' command. \
-         If you want to see a list of all addresses, including change addresses, \
-         that have a positive balance, use the command 'balance -v'"
-    );
+    if matches!(keychain, KeychainArg::External) {
+        println!();
+        println!(
+            "Note: the above are only addresses that were manually created by the 'new-address' command. \
+             To include change addresses, use '--keychain all'"
+        );
+    }
 
     Ok(())
 }
 
 /// Generate a new address
-pub async fn new_address(daemon_address: &str) -> Result<()> {
+pub async fn new_address(
+    daemon_address: &str,
+    count: u32,
+    uri: bool,
+    qr: bool,
+    amount: Option<String>,
+    label: Option<String>,
+    message: Option<String>,
+) -> Result<()> {
+    let amount = amount
+        .map(|amount| kas_to_sompi(&amount).map_err(invalid_amount))
+        .transpose()?;
+
     let mut client = connect(daemon_address).await?;
 
-    let address = client.new_address().await?;
+    if count <= 1 {
+        let address = client.new_address().await?;
+        println!("New address: {}", address);
+
+        let wants_uri = uri || amount.is_some() || label.is_some() || message.is_some();
+        if wants_uri || qr {
+            let payment_uri = client
+                .build_payment_uri(&address, amount, label, message)
+                .await?
+                .to_string();
+            if wants_uri {
+                println!("Payment URI: {}", payment_uri);
+            }
+            if qr {
+                print_qr_code(&payment_uri)?;
+            }
+        }
+        return Ok(());
+    }
 
-    println!("New address: {}", address);
+    let addresses = client.new_addresses(count).await?;
+    println!("New addresses ({}):", addresses.len());
+    for address in &addresses {
+        println!("{}", address);
+    }
+
+    Ok(())
+}
+
+fn print_qr_code(data: &str) -> Result<()> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| invalid_argument(format!("cannot encode {data} as a QR code: {e}")))?;
+    // Inverted, so the code reads as dark-on-light on a dark terminal background.
+    let rendered = code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build();
+    println!("{}", rendered);
+    Ok(())
+}
+
+/// Check an address and whether it belongs to the wallet
+pub async fn validate_address(daemon_address: &str, address: String) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    let validation = client.validate_address(address).await?;
+
+    if !validation.is_valid {
+        println!(
+            "Invalid address: {}",
+            validation.invalid_reason.unwrap_or_default()
+        );
+        return Ok(());
+    }
+    println!("Valid: yes");
+    println!(
+        "Network: {}{}",
+        validation.prefix,
+        if validation.is_network_match {
+            ""
+        } else {
+            " (does not match the daemon's network)"
+        }
+    );
+    println!("Version: {}", validation.version.as_str_name());
+    match validation.ownership {
+        Some(ownership) => {
+            println!("Mine: yes");
+            println!("Keychain: {:?}", ownership.wallet_address.keychain);
+            println!("Index: {}", ownership.wallet_address.index);
+            println!(
+                "Cosigner index: {}",
+                ownership.wallet_address.cosigner_index
+            );
+            println!("Derivation path: {}", ownership.derivation_path);
+        }
+        None => println!("Mine: no"),
+    }
 
     Ok(())
 }
@@ -167,11 +325,27 @@ pub async fn get_utxos(
     let mut client = connect(daemon_address).await?;
 
     let address_utxos = client
-        .get_utxos(addresses, include_pending, include_dust)
+        .get_utxos(addresses.clone(), include_pending, include_dust)
         .await?;
+    let frozen_utxos: Vec<_> = client
+        .list_frozen_utxos()
+        .await?
+        .into_iter()
+        .filter(|frozen_utxo| {
+            frozen_utxo
+                .address
+                .as_ref()
+                .is_some_and(|address| addresses.is_empty() || addresses.contains(address))
+        })
+        .collect();
+    let covenant_bound_utxos = client.get_covenant_bound_utxos(addresses).await?;
 
     for addr_utxos in &address_utxos {
-        println!("Address: {}", addr_utxos.address);
+        println!(
+            "Address: {}{}",
+            addr_utxos.address,
+            label_suffix(&addr_utxos.label)
+        );
         println!("  UTXOs ({}):", addr_utxos.utxos.len());
 
         for utxo in &addr_utxos.utxos {
@@ -210,6 +384,311 @@ pub async fn get_utxos(
         println!();
     }
 
+    if !frozen_utxos.is_empty() {
+        println!("Frozen UTXOs ({}):", frozen_utxos.len());
+        for frozen_utxo in &frozen_utxos {
+            print_frozen_utxo(frozen_utxo);
+        }
+    }
+
+    for addr_utxos in &covenant_bound_utxos {
+        println!(
+            "Covenant-bound UTXOs of {} ({}):",
+            addr_utxos.address,
+            addr_utxos.utxos.len()
+        );
+        for utxo in &addr_utxos.utxos {
+            println!(
+                "    {}:{} - {} KAS [covenant {}]",
+                utxo.outpoint.transaction_id,
+                utxo.outpoint.index,
+                format_kas(utxo.amount).trim(),
+                utxo.covenant_id.as_deref().unwrap_or_default()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_outpoint(outpoint: &str) -> Result<Outpoint> {
+    let (transaction_id, index) = outpoint.split_once(':').ok_or_else(|| {
+        invalid_argument(format!(
+            "outpoint {outpoint} must be <transaction id>:<index>"
+        ))
+    })?;
+    let index = index
+        .parse::<u32>()
+        .map_err(|e| invalid_argument(format!("outpoint {outpoint} has an invalid index: {e}")))?;
+    Ok(Outpoint {
+        transaction_id: transaction_id.to_string(),
+        index,
+    })
+}
+
+fn print_frozen_utxo(frozen_utxo: &kaswallet_client::model::FrozenUtxoInfo) {
+    let amount = match (&frozen_utxo.address, frozen_utxo.amount) {
+        (Some(address), Some(amount)) => {
+            format!("{} KAS at {}", format_kas(amount).trim(), address)
+        }
+        _ => "no longer in the wallet".to_string(),
+    };
+    let reason = if frozen_utxo.reason.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", frozen_utxo.reason)
+    };
+    println!(
+        "    {}:{} - {}{}",
+        frozen_utxo.outpoint.transaction_id, frozen_utxo.outpoint.index, amount, reason
+    );
+}
+
+/// ` (label)` after an address, or nothing if it is unlabelled.
+fn label_suffix(label: &Option<String>) -> String {
+    match label {
+        Some(label) => format!(" ({label})"),
+        None => String::new(),
+    }
+}
+
+/// Label a wallet address
+pub async fn set_label(daemon_address: &str, address: String, label: String) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let is_removal = label.trim().is_empty();
+    client.set_address_label(address.clone(), label).await?;
+
+    if is_removal {
+        println!("Removed the label of {}", address);
+    } else {
+        println!("Labelled {}", address);
+    }
+
+    Ok(())
+}
+
+/// List address labels
+pub async fn list_labels(daemon_address: &str) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let labels = client.list_address_labels().await?;
+
+    if labels.is_empty() {
+        println!("No labelled addresses");
+        return Ok(());
+    }
+    println!("Labels ({}):", labels.len());
+    for (address, label) in &labels {
+        println!("    {} {}", address, label);
+    }
+
+    Ok(())
+}
+
+/// Add a contact to the address book
+pub async fn add_contact(
+    daemon_address: &str,
+    alias: String,
+    address: String,
+    note: String,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let contact = client.add_contact(alias, address, note).await?;
+
+    println!("Saved contact {}: {}", contact.alias, contact.address);
+
+    Ok(())
+}
+
+/// Remove a contact from the address book
+pub async fn remove_contact(daemon_address: &str, alias: String) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    if client.remove_contact(alias.clone()).await? {
+        println!("Removed contact {}", alias);
+    } else {
+        println!("No contact named {}", alias);
+    }
+
+    Ok(())
+}
+
+/// List the contacts in the address book
+pub async fn list_contacts(daemon_address: &str) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let contacts = client.list_contacts().await?;
+
+    if contacts.is_empty() {
+        println!("No contacts");
+        return Ok(());
+    }
+    println!("Contacts ({}):", contacts.len());
+    for contact in &contacts {
+        match &contact.note {
+            Some(note) => println!("    {} {} - {}", contact.alias, contact.address, note),
+            None => println!("    {} {}", contact.alias, contact.address),
+        }
+    }
+
+    Ok(())
+}
+
+fn print_invoice(invoice: &InvoiceInfo) {
+    println!(
+        "Invoice {}: {:?}, {} of {} KAS received",
+        invoice.invoice_id,
+        invoice.state,
+        format_kas(invoice.received).trim(),
+        format_kas(invoice.amount).trim()
+    );
+    println!("    Address: {}", invoice.address);
+    if let Some(memo) = &invoice.memo {
+        println!("    Memo: {}", memo);
+    }
+    if let Some(expires_at_unix_millis) = invoice.expires_at_unix_millis {
+        println!("    Expires at (unix millis): {}", expires_at_unix_millis);
+    }
+    println!("    Payment URI: {}", invoice.payment_uri);
+}
+
+/// Create an invoice
+pub async fn create_invoice(
+    daemon_address: &str,
+    amount: String,
+    memo: String,
+    expiry_seconds: Option<u64>,
+) -> Result<()> {
+    let amount = kas_to_sompi(&amount).map_err(invalid_amount)?;
+    if amount == 0 {
+        return Err(invalid_argument("invoice amount must be positive"));
+    }
+
+    let mut client = connect(daemon_address).await?;
+    let invoice = client
+        .create_invoice(amount, memo, expiry_seconds.map(Duration::from_secs))
+        .await?;
+    print_invoice(&invoice);
+
+    Ok(())
+}
+
+/// Show an invoice
+pub async fn get_invoice(daemon_address: &str, invoice_id: u64) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let invoice = client.get_invoice(invoice_id).await?;
+    print_invoice(&invoice);
+
+    Ok(())
+}
+
+/// List invoices
+pub async fn list_invoices(daemon_address: &str, states: Vec<InvoiceStateArg>) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let invoices = client
+        .list_invoices(states.into_iter().map(Into::into).collect())
+        .await?;
+
+    if invoices.is_empty() {
+        println!("No invoices");
+        return Ok(());
+    }
+    for invoice in &invoices {
+        print_invoice(invoice);
+    }
+
+    Ok(())
+}
+
+/// List invoice state changes
+pub async fn invoice_events(
+    daemon_address: &str,
+    after_sequence: u64,
+    limit: Option<u32>,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let events = client
+        .list_invoice_events(after_sequence, limit.unwrap_or_default())
+        .await?;
+
+    if events.is_empty() {
+        println!("No invoice events");
+        return Ok(());
+    }
+    for event in &events {
+        println!(
+            "    #{} invoice {}: {:?} -> {:?}, {} KAS received (unix millis {})",
+            event.sequence,
+            event.invoice_id,
+            event.previous_state,
+            event.state,
+            format_kas(event.received).trim(),
+            event.at_unix_millis
+        );
+    }
+
+    Ok(())
+}
+
+/// Freeze UTXOs
+pub async fn freeze_utxos(
+    daemon_address: &str,
+    outpoints: Vec<String>,
+    reason: String,
+) -> Result<()> {
+    let outpoints = outpoints
+        .iter()
+        .map(|outpoint| parse_outpoint(outpoint))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut client = connect(daemon_address).await?;
+    let frozen_utxos = client.freeze_utxos(outpoints, reason).await?;
+
+    println!("Frozen UTXOs ({}):", frozen_utxos.len());
+    for frozen_utxo in &frozen_utxos {
+        print_frozen_utxo(frozen_utxo);
+    }
+
+    Ok(())
+}
+
+/// Unfreeze UTXOs
+pub async fn unfreeze_utxos(daemon_address: &str, outpoints: Vec<String>) -> Result<()> {
+    let outpoints = outpoints
+        .iter()
+        .map(|outpoint| parse_outpoint(outpoint))
+        .collect::<Result<Vec<_>>>()?;
+    let requested_count = outpoints.len();
+
+    let mut client = connect(daemon_address).await?;
+    let unfrozen_outpoints = client.unfreeze_utxos(outpoints).await?;
+
+    println!("Unfrozen UTXOs ({}):", unfrozen_outpoints.len());
+    for outpoint in &unfrozen_outpoints {
+        println!("    {}:{}", outpoint.transaction_id, outpoint.index);
+    }
+    if unfrozen_outpoints.len() < requested_count {
+        println!(
+            "{} of the given outpoints were not frozen",
+            requested_count - unfrozen_outpoints.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// List frozen UTXOs
+pub async fn list_frozen_utxos(daemon_address: &str) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+    let frozen_utxos = client.list_frozen_utxos().await?;
+
+    if frozen_utxos.is_empty() {
+        println!("No frozen UTXOs");
+        return Ok(());
+    }
+    println!("Frozen UTXOs ({}):", frozen_utxos.len());
+    for frozen_utxo in &frozen_utxos {
+        print_frozen_utxo(frozen_utxo);
+    }
+
     Ok(())
 }
 
@@ -217,6 +696,8 @@ fn build_fee_policy(
     max_fee_rate: Option<f64>,
     fee_rate: Option<f64>,
     max_fee: Option<u64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
 ) -> Option<FeePolicy> {
     if let Some(rate) = fee_rate {
         Some(FeePolicy {
@@ -226,6 +707,16 @@ fn build_fee_policy(
         Some(FeePolicy {
             fee_policy: Some(fee_policy::FeePolicy::MaxFeeRate(rate)),
         })
+    } else if let Some(priority) = fee_priority {
+        Some(FeePolicy {
+            fee_policy: Some(fee_policy::FeePolicy::Priority(
+                FeePriority::from(priority).into(),
+            )),
+        })
+    } else if let Some(target_seconds) = fee_target_seconds {
+        Some(FeePolicy {
+            fee_policy: Some(fee_policy::FeePolicy::TargetSeconds(target_seconds)),
+        })
     } else {
         max_fee.map(|fee| FeePolicy {
             fee_policy: Some(fee_policy::FeePolicy::MaxFee(fee)),
@@ -233,6 +724,13 @@ fn build_fee_policy(
     }
 }
 
+fn coin_selection_strategy(coin_selection: Option<CoinSelection>) -> i32 {
+    coin_selection
+        .map(CoinSelectionStrategy::from)
+        .unwrap_or_default()
+        .into()
+}
+
 fn get_password(prompt: &str, password: Option<String>) -> Result<String> {
     if let Some(p) = password {
         Ok(p)
@@ -255,38 +753,91 @@ fn get_password(prompt: &str, password: Option<String>) -> Result<String> {
     }
 }
 
+/// The address and amount in sompi to pay: `--to` and `--amount`, or those of a `--uri` payment
+/// request, whose address must be on the daemon's network. The amount is 0 with `--send-all`.
+async fn resolve_recipient(
+    client: &mut KaswalletClient,
+    to_address: Option<String>,
+    uri: Option<String>,
+    send_amount: Option<&str>,
+    is_send_all: bool,
+) -> Result<(String, u64)> {
+    let (to_address, requested_amount) = match uri {
+        Some(uri) => {
+            let payment_uri = client.parse_payment_uri(&uri).await?;
+            if let Some(label) = &payment_uri.label {
+                println!("Paying: {}", label);
+            }
+            if let Some(message) = &payment_uri.message {
+                println!("Message: {}", message);
+            }
+            (payment_uri.address.to_string(), payment_uri.amount)
+        }
+        None => (
+            to_address
+                .ok_or_else(|| invalid_argument("One of '--to' or '--uri' must be specified"))?,
+            None,
+        ),
+    };
+
+    let amount = match (requested_amount, send_amount) {
+        (Some(_), Some(_)) => {
+            return Err(invalid_argument(
+                "The payment URI already sets the amount; '--amount' cannot be used with it",
+            ));
+        }
+        (Some(_), None) if is_send_all => {
+            return Err(invalid_argument(
+                "The payment URI sets the amount; '--send-all' cannot be used with it",
+            ));
+        }
+        (Some(amount), None) => amount,
+        (None, Some(amount_str)) => kas_to_sompi(amount_str).map_err(invalid_amount)?,
+        (None, None) if is_send_all => 0,
+        (None, None) => {
+            return Err(invalid_argument(
+                "Exactly one of '--send-amount' or '--send-all' must be specified",
+            ));
+        }
+    };
+    Ok((to_address, amount))
+}
+
 /// Send funds to an address
 #[allow(clippy::too_many_arguments)]
 pub async fn send(
     daemon_address: &str,
-    to_address: &str,
+    to_address: Option<String>,
+    uri: Option<String>,
     send_amount: Option<&str>,
     is_send_all: bool,
+    subtract_fee_from_amount: bool,
+    coin_selection: Option<CoinSelection>,
+    subnetwork_id: Option<String>,
     from_addresses: Vec<String>,
     use_existing_change_address: bool,
     max_fee_rate: Option<f64>,
     fee_rate: Option<f64>,
     max_fee: Option<u64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
     password: Option<String>,
     show_serialized: bool,
     payload: Option<&str>,
+    idempotency_key: Option<String>,
 ) -> Result<()> {
-    // Validate that either send_amount or send_all is specified
-    if send_amount.is_none() && !is_send_all {
-        return Err(invalid_argument(
-            "Exactly one of '--send-amount' or '--send-all' must be specified",
-        ));
-    }
-
     let mut client = connect(daemon_address).await?;
 
-    let amount_sompi = if let Some(amount_str) = send_amount {
-        kas_to_sompi(amount_str).map_err(invalid_amount)?
-    } else {
-        0
-    };
+    let (to_address, amount_sompi) =
+        resolve_recipient(&mut client, to_address, uri, send_amount, is_send_all).await?;
 
-    let fee_policy = build_fee_policy(max_fee_rate, fee_rate, max_fee);
+    let fee_policy = build_fee_policy(
+        max_fee_rate,
+        fee_rate,
+        max_fee,
+        fee_priority,
+        fee_target_seconds,
+    );
 
     let payload_bytes = if let Some(payload_hex) = payload {
         hex::decode(payload_hex).map_err(|e| invalid_hex(format!("payload: {e}")))?
@@ -296,22 +847,38 @@ pub async fn send(
 
     let password = get_password("Password: ", password)?;
 
-    let result = client
-        .send(
-            TransactionDescription {
-                to_address: to_address.to_string(),
-                amount: amount_sompi,
-                is_send_all,
-                payload: payload_bytes.into(),
-                from_addresses,
-                utxos: vec![],
-                use_existing_change_address,
-                fee_policy,
-            },
-            password,
-        )
-        .await?;
+    let transaction_description = TransactionDescription {
+        to_address,
+        amount: amount_sompi,
+        is_send_all,
+        payload: payload_bytes.into(),
+        from_addresses,
+        utxos: vec![],
+        use_existing_change_address,
+        fee_policy,
+        subtract_fee_from_amount,
+        coin_selection_strategy: coin_selection_strategy(coin_selection),
+        subnetwork_id,
+        ..Default::default()
+    };
+    let result = match idempotency_key {
+        Some(idempotency_key) => {
+            client
+                .send_with_idempotency_key(transaction_description, password, idempotency_key)
+                .await?
+        }
+        None => client.send(transaction_description, password).await?,
+    };
 
+    if result.idempotent_replay {
+        println!("Idempotency key was already used; nothing new was sent");
+    }
+    if let Some(applied_fee) = result.applied_fee {
+        println!(
+            "Fee rate: {} sompi/gram (expected acceptance in ~{:.0}s)",
+            applied_fee.fee_rate, applied_fee.estimated_seconds
+        );
+    }
     println!(
         "Broadcasted {} transaction(s)",
         result.transaction_ids.len()
@@ -338,32 +905,34 @@ pub async fn send(
 #[allow(clippy::too_many_arguments)]
 pub async fn create_unsigned_transaction(
     daemon_address: &str,
-    to_address: &str,
+    to_address: Option<String>,
+    uri: Option<String>,
     send_amount: Option<&str>,
     is_send_all: bool,
+    subtract_fee_from_amount: bool,
+    coin_selection: Option<CoinSelection>,
+    subnetwork_id: Option<String>,
     from_addresses: Vec<String>,
     use_existing_change_address: bool,
     max_fee_rate: Option<f64>,
     fee_rate: Option<f64>,
     max_fee: Option<u64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
     payload: Option<&str>,
 ) -> Result<()> {
-    // Validate that either send_amount or send_all is specified
-    if send_amount.is_none() && !is_send_all {
-        return Err(invalid_argument(
-            "Exactly one of '--send-amount' or '--send-all' must be specified",
-        ));
-    }
-
     let mut client = connect(daemon_address).await?;
 
-    let amount_sompi = if let Some(amount_str) = send_amount {
-        kas_to_sompi(amount_str).map_err(invalid_amount)?
-    } else {
-        0
-    };
+    let (to_address, amount_sompi) =
+        resolve_recipient(&mut client, to_address, uri, send_amount, is_send_all).await?;
 
-    let fee_policy = build_fee_policy(max_fee_rate, fee_rate, max_fee);
+    let fee_policy = build_fee_policy(
+        max_fee_rate,
+        fee_rate,
+        max_fee,
+        fee_priority,
+        fee_target_seconds,
+    );
 
     let payload_bytes = if let Some(payload_hex) = payload {
         hex::decode(payload_hex).map_err(|e| invalid_hex(format!("payload: {e}")))?
@@ -373,7 +942,7 @@ pub async fn create_unsigned_transaction(
 
     let unsigned_transactions = client
         .create_unsigned_transactions(TransactionDescription {
-            to_address: to_address.to_string(),
+            to_address,
             amount: amount_sompi,
             is_send_all,
             payload: payload_bytes.into(),
@@ -381,6 +950,10 @@ pub async fn create_unsigned_transaction(
             utxos: vec![],
             use_existing_change_address,
             fee_policy,
+            subtract_fee_from_amount,
+            coin_selection_strategy: coin_selection_strategy(coin_selection),
+            subnetwork_id,
+            ..Default::default()
         })
         .await?;
 
@@ -398,6 +971,252 @@ pub async fn create_unsigned_transaction(
     Ok(())
 }
 
+/// Show the most that can be sent to an address
+#[allow(clippy::too_many_arguments)]
+pub async fn max_sendable(
+    daemon_address: &str,
+    to_address: &str,
+    from_addresses: Vec<String>,
+    utxos: Vec<String>,
+    max_fee_rate: Option<f64>,
+    fee_rate: Option<f64>,
+    max_fee: Option<u64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
+) -> Result<()> {
+    let utxos = utxos
+        .iter()
+        .map(|outpoint| parse_outpoint(outpoint))
+        .collect::<Result<Vec<_>>>()?;
+    let fee_policy = build_fee_policy(
+        max_fee_rate,
+        fee_rate,
+        max_fee,
+        fee_priority,
+        fee_target_seconds,
+    );
+
+    let mut client = connect(daemon_address).await?;
+    let max_sendable = client
+        .get_max_sendable(TransactionDescription {
+            to_address: to_address.to_string(),
+            from_addresses,
+            utxos,
+            fee_policy,
+            ..Default::default()
+        })
+        .await?;
+
+    println!(
+        "Max sendable: {} KAS",
+        format_kas(max_sendable.amount).trim()
+    );
+    println!(
+        "Fee: {} KAS over {} transaction(s)",
+        format_kas(max_sendable.fee).trim(),
+        max_sendable.transaction_count
+    );
+    if let Some(applied_fee) = max_sendable.applied_fee {
+        println!(
+            "Fee rate: {} sompi/gram (expected acceptance in ~{:.0}s)",
+            applied_fee.fee_rate, applied_fee.estimated_seconds
+        );
+    }
+
+    Ok(())
+}
+
+/// Consolidate small UTXOs into one
+#[allow(clippy::too_many_arguments)]
+pub async fn consolidate(
+    daemon_address: &str,
+    target_utxo_count: u32,
+    min_input: Option<&str>,
+    max_input: Option<&str>,
+    from_addresses: Vec<String>,
+    max_fee_rate: Option<f64>,
+    broadcast: bool,
+    password: Option<String>,
+) -> Result<()> {
+    let min_input_value = match min_input {
+        Some(amount_str) => kas_to_sompi(amount_str).map_err(invalid_amount)?,
+        None => 0,
+    };
+    let max_input_value = max_input
+        .map(|amount_str| kas_to_sompi(amount_str).map_err(invalid_amount))
+        .transpose()?;
+
+    let mut client = connect(daemon_address).await?;
+
+    let fee_policy = build_fee_policy(max_fee_rate, None, None, None, None);
+    let password = if broadcast {
+        Some(get_password("Password: ", password)?)
+    } else {
+        None
+    };
+
+    let result = client
+        .consolidate(
+            target_utxo_count,
+            min_input_value,
+            max_input_value,
+            from_addresses,
+            fee_policy,
+            password,
+        )
+        .await?;
+
+    if result.transactions.is_empty() {
+        println!("Nothing to consolidate");
+        return Ok(());
+    }
+
+    if broadcast {
+        println!(
+            "Broadcasted {} transaction(s)",
+            result.transaction_ids.len()
+        );
+        println!("Transaction ID(s):");
+        for tx_id in &result.transaction_ids {
+            println!("  {}", tx_id);
+        }
+    } else {
+        println!(
+            "Created {} unsigned transaction(s)",
+            result.transactions.len()
+        );
+        println!("Unsigned Transaction(s) (hex encoded):");
+        for transaction in result.transactions {
+            let serialized = serialize_transaction(transaction);
+            println!("{}", serialized);
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Sweep dust UTXOs into change
+pub async fn sweep_dust(
+    daemon_address: &str,
+    from_addresses: Vec<String>,
+    max_fee_rate: Option<f64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
+    dry_run: bool,
+    password: Option<String>,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    let fee_policy = build_fee_policy(max_fee_rate, None, None, fee_priority, fee_target_seconds);
+    let password = if dry_run {
+        None
+    } else {
+        Some(get_password("Password: ", password)?)
+    };
+
+    let result = client
+        .sweep_dust(from_addresses, fee_policy, password)
+        .await?;
+
+    if result.dust_utxo_count == 0 {
+        println!("No dust to sweep");
+        return Ok(());
+    }
+    println!(
+        "Dust: {} UTXO(s) worth {} KAS",
+        result.dust_utxo_count,
+        format_kas(result.dust_value).trim()
+    );
+    println!("Fee: {} KAS", format_kas(result.fee).trim());
+    if let Some(applied_fee) = result.applied_fee {
+        println!("Fee rate: {} sompi/gram", applied_fee.fee_rate);
+    }
+    if !result.is_economic {
+        println!("Not swept: the fee is not lower than the dust's value at this fee rate");
+        return Ok(());
+    }
+    if dry_run {
+        println!("Dry run: nothing was broadcast");
+        return Ok(());
+    }
+
+    println!(
+        "Broadcasted {} transaction(s)",
+        result.transaction_ids.len()
+    );
+    println!("Transaction ID(s):");
+    for tx_id in &result.transaction_ids {
+        println!("  {}", tx_id);
+    }
+
+    Ok(())
+}
+
+/// Sweep an external private key or mnemonic into the wallet
+#[allow(clippy::too_many_arguments)]
+pub async fn sweep_external(
+    daemon_address: &str,
+    mnemonic: bool,
+    derivation_path: Option<String>,
+    gap_limit: Option<u32>,
+    max_fee_rate: Option<f64>,
+    fee_priority: Option<Priority>,
+    fee_target_seconds: Option<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    let fee_policy = build_fee_policy(max_fee_rate, None, None, fee_priority, fee_target_seconds);
+    // Read without echo, and never from the command line, so the key stays out of shell history.
+    let key = if mnemonic {
+        ExternalKeySource::Mnemonic {
+            phrase: get_password("Mnemonic: ", None)?,
+            passphrase: String::new(),
+            derivation_path: derivation_path.unwrap_or_default(),
+            gap_limit: gap_limit.unwrap_or(0),
+        }
+    } else {
+        ExternalKeySource::PrivateKey(get_password("Private key: ", None)?)
+    };
+
+    let result = client.sweep_external(key, fee_policy, dry_run).await?;
+
+    if result.utxo_count == 0 {
+        println!("No spendable UTXOs found for the external key");
+        return Ok(());
+    }
+    println!("From:");
+    for address in &result.from_addresses {
+        println!("  {}", address);
+    }
+    println!(
+        "Found {} UTXO(s) worth {} KAS",
+        result.utxo_count,
+        format_kas(result.swept_value).trim()
+    );
+    println!("Fee: {} KAS", format_kas(result.fee).trim());
+    if let Some(applied_fee) = result.applied_fee {
+        println!("Fee rate: {} sompi/gram", applied_fee.fee_rate);
+    }
+    println!("To: {}", result.to_address);
+    if dry_run {
+        println!("Dry run: nothing was broadcast");
+        return Ok(());
+    }
+
+    println!(
+        "Broadcasted {} transaction(s)",
+        result.transaction_ids.len()
+    );
+    println!("Transaction ID(s):");
+    for tx_id in &result.transaction_ids {
+        println!("  {}", tx_id);
+    }
+
+    Ok(())
+}
+
 /// Sign unsigned transactions
 pub async fn sign(
     daemon_address: &str,
@@ -425,18 +1244,64 @@ pub async fn sign(
     Ok(())
 }
 
+/// Sign a message with the key of a wallet address
+pub async fn sign_message(
+    daemon_address: &str,
+    address: String,
+    message: String,
+    password: Option<String>,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    let password = get_password("Password: ", password)?;
+
+    let signature = client.sign_message(address, message, password).await?;
+
+    println!("Signature: {}", signature);
+
+    Ok(())
+}
+
+/// Verify a message signature against an address
+pub async fn verify_message(
+    daemon_address: &str,
+    address: String,
+    message: String,
+    signature: String,
+) -> Result<()> {
+    let mut client = connect(daemon_address).await?;
+
+    let is_valid = client.verify_message(address, message, signature).await?;
+
+    if is_valid {
+        println!("Signature is valid");
+    } else {
+        println!("Signature is NOT valid");
+    }
+
+    Ok(())
+}
+
 /// Broadcast signed transactions
 pub async fn broadcast(
     daemon_address: &str,
     transaction: Option<String>,
     transaction_file: Option<String>,
+    idempotency_key: Option<String>,
 ) -> Result<()> {
     let transactions_hex = get_transactions_hex(transaction, transaction_file)?;
     let transactions = parse_transactions_hex(&transactions_hex)?;
 
     let mut client = connect(daemon_address).await?;
 
-    let tx_ids = client.broadcast(transactions).await?;
+    let tx_ids = match idempotency_key {
+        Some(idempotency_key) => {
+            client
+                .broadcast_with_idempotency_key(transactions, idempotency_key)
+                .await?
+        }
+        None => client.broadcast(transactions).await?,
+    };
 
     println!("Broadcasted {} transaction(s)", tx_ids.len());
     println!("Transaction ID(s):");
@@ -447,6 +1312,30 @@ pub async fn broadcast(
     Ok(())
 }
 
+/// Release the UTXO reservations of unsigned transactions
+pub async fn release_reservation(
+    daemon_address: &str,
+    transaction: Option<String>,
+    transaction_file: Option<String>,
+) -> Result<()> {
+    let transactions_hex = get_transactions_hex(transaction, transaction_file)?;
+    let transaction_ids = parse_transactions_hex(&transactions_hex)?
+        .iter()
+        .map(|transaction| transaction.transaction.inner().id())
+        .collect();
+
+    let mut client = connect(daemon_address).await?;
+
+    let released_outpoints = client.release_reservation(transaction_ids).await?;
+
+    println!("Released {} UTXO(s)", released_outpoints.len());
+    for outpoint in &released_outpoints {
+        println!("    {}:{}", outpoint.transaction_id, outpoint.index);
+    }
+
+    Ok(())
+}
+
 fn get_transactions_hex(
     transaction: Option<String>,
     transaction_file: Option<String>,
@@ -562,6 +1451,7 @@ pub async fn address_balances(daemon_address: &str) -> Result<()> {
 
             AddressDetailOutput {
                 address: ab.address.clone(),
+                label: ab.label.clone(),
                 available: ab.available,
                 pending: ab.pending,
                 utxos,
@@ -33,11 +33,28 @@ async fn main() {
             verbose,
         } => commands::balance(&daemon_address, verbose).await,
 
-        Commands::ShowAddresses { daemon_address } => {
-            commands::show_addresses(&daemon_address).await
-        }
+        Commands::ShowAddresses {
+            daemon_address,
+            keychain,
+            used,
+            unused,
+            verbose,
+        } => commands::show_addresses(&daemon_address, keychain, used, unused, verbose).await,
 
-        Commands::NewAddress { daemon_address } => commands::new_address(&daemon_address).await,
+        Commands::NewAddress {
+            daemon_address,
+            count,
+            uri,
+            qr,
+            amount,
+            label,
+            message,
+        } => commands::new_address(&daemon_address, count, uri, qr, amount, label, message).await,
+
+        Commands::ValidateAddress {
+            daemon_address,
+            address,
+        } => commands::validate_address(&daemon_address, address).await,
 
         Commands::GetDaemonVersion { daemon_address } => {
             commands::get_daemon_version(&daemon_address).await
@@ -50,33 +67,107 @@ async fn main() {
             include_dust,
         } => commands::get_utxos(&daemon_address, addresses, include_pending, include_dust).await,
 
+        Commands::FreezeUtxos {
+            daemon_address,
+            outpoints,
+            reason,
+        } => commands::freeze_utxos(&daemon_address, outpoints, reason).await,
+
+        Commands::UnfreezeUtxos {
+            daemon_address,
+            outpoints,
+        } => commands::unfreeze_utxos(&daemon_address, outpoints).await,
+
+        Commands::SetLabel {
+            daemon_address,
+            address,
+            label,
+        } => commands::set_label(&daemon_address, address, label).await,
+
+        Commands::ListLabels { daemon_address } => commands::list_labels(&daemon_address).await,
+
+        Commands::AddContact {
+            daemon_address,
+            alias,
+            address,
+            note,
+        } => commands::add_contact(&daemon_address, alias, address, note).await,
+
+        Commands::RemoveContact {
+            daemon_address,
+            alias,
+        } => commands::remove_contact(&daemon_address, alias).await,
+
+        Commands::ListContacts { daemon_address } => commands::list_contacts(&daemon_address).await,
+
+        Commands::CreateInvoice {
+            daemon_address,
+            amount,
+            memo,
+            expiry_seconds,
+        } => commands::create_invoice(&daemon_address, amount, memo, expiry_seconds).await,
+
+        Commands::GetInvoice {
+            daemon_address,
+            invoice_id,
+        } => commands::get_invoice(&daemon_address, invoice_id).await,
+
+        Commands::ListInvoices {
+            daemon_address,
+            states,
+        } => commands::list_invoices(&daemon_address, states).await,
+
+        Commands::InvoiceEvents {
+            daemon_address,
+            after_sequence,
+            limit,
+        } => commands::invoice_events(&daemon_address, after_sequence, limit).await,
+
+        Commands::ListFrozenUtxos { daemon_address } => {
+            commands::list_frozen_utxos(&daemon_address).await
+        }
+
         Commands::Send {
             daemon_address,
             to_address,
+            uri,
             send_amount,
             is_send_all,
+            subtract_fee_from_amount,
+            coin_selection,
+            subnetwork_id,
             from_addresses,
             use_existing_change_address,
             max_fee_rate,
             exact_fee_rate: fee_rate,
             max_fee,
+            fee_priority,
+            fee_target_seconds,
             password,
             show_transactions,
             payload,
+            idempotency_key,
         } => {
             commands::send(
                 &daemon_address,
-                &to_address,
+                to_address,
+                uri,
                 send_amount.as_deref(),
                 is_send_all,
+                subtract_fee_from_amount,
+                coin_selection,
+                subnetwork_id,
                 from_addresses,
                 use_existing_change_address,
                 max_fee_rate,
                 fee_rate,
                 max_fee,
+                fee_priority,
+                fee_target_seconds,
                 password,
                 show_transactions,
                 payload.as_deref(),
+                idempotency_key,
             )
             .await
         }
@@ -84,30 +175,134 @@ async fn main() {
         Commands::CreateUnsignedTransaction {
             daemon_address,
             to_address,
+            uri,
             send_amount,
             is_send_all,
+            subtract_fee_from_amount,
+            coin_selection,
+            subnetwork_id,
             from_addresses,
             use_existing_change_address,
             max_fee_rate,
             exact_fee_rate,
             max_fee,
+            fee_priority,
+            fee_target_seconds,
             payload,
         } => {
             commands::create_unsigned_transaction(
                 &daemon_address,
-                &to_address,
+                to_address,
+                uri,
                 send_amount.as_deref(),
                 is_send_all,
+                subtract_fee_from_amount,
+                coin_selection,
+                subnetwork_id,
                 from_addresses,
                 use_existing_change_address,
                 max_fee_rate,
                 exact_fee_rate,
                 max_fee,
+                fee_priority,
+                fee_target_seconds,
                 payload.as_deref(),
             )
             .await
         }
 
+        Commands::MaxSendable {
+            daemon_address,
+            to_address,
+            from_addresses,
+            utxos,
+            max_fee_rate,
+            exact_fee_rate,
+            max_fee,
+            fee_priority,
+            fee_target_seconds,
+        } => {
+            commands::max_sendable(
+                &daemon_address,
+                &to_address,
+                from_addresses,
+                utxos,
+                max_fee_rate,
+                exact_fee_rate,
+                max_fee,
+                fee_priority,
+                fee_target_seconds,
+            )
+            .await
+        }
+
+        Commands::Consolidate {
+            daemon_address,
+            target_utxo_count,
+            min_input,
+            max_input,
+            from_addresses,
+            max_fee_rate,
+            broadcast,
+            password,
+        } => {
+            commands::consolidate(
+                &daemon_address,
+                target_utxo_count,
+                min_input.as_deref(),
+                max_input.as_deref(),
+                from_addresses,
+                max_fee_rate,
+                broadcast,
+                password,
+            )
+            .await
+        }
+
+        Commands::SweepDust {
+            daemon_address,
+            from_addresses,
+            max_fee_rate,
+            fee_priority,
+            fee_target_seconds,
+            dry_run,
+            password,
+        } => {
+            commands::sweep_dust(
+                &daemon_address,
+                from_addresses,
+                max_fee_rate,
+                fee_priority,
+                fee_target_seconds,
+                dry_run,
+                password,
+            )
+            .await
+        }
+
+        Commands::SweepExternal {
+            daemon_address,
+            mnemonic,
+            derivation_path,
+            gap_limit,
+            max_fee_rate,
+            fee_priority,
+            fee_target_seconds,
+            dry_run,
+        } => {
+            commands::sweep_external(
+                &daemon_address,
+                mnemonic,
+                derivation_path,
+                gap_limit,
+                max_fee_rate,
+                fee_priority,
+                fee_target_seconds,
+                dry_run,
+            )
+            .await
+        }
+
         Commands::Sign {
             daemon_address,
             transaction,
@@ -115,11 +310,40 @@ async fn main() {
             password,
         } => commands::sign(&daemon_address, transaction, transaction_file, password).await,
 
+        Commands::SignMessage {
+            daemon_address,
+            address,
+            message,
+            password,
+        } => commands::sign_message(&daemon_address, address, message, password).await,
+
+        Commands::VerifyMessage {
+            daemon_address,
+            address,
+            message,
+            signature,
+        } => commands::verify_message(&daemon_address, address, message, signature).await,
+
         Commands::Broadcast {
             daemon_address,
             transaction,
             transaction_file,
-        } => commands::broadcast(&daemon_address, transaction, transaction_file).await,
+            idempotency_key,
+        } => {
+            commands::broadcast(
+                &daemon_address,
+                transaction,
+                transaction_file,
+                idempotency_key,
+            )
+            .await
+        }
+
+        Commands::ReleaseReservation {
+            daemon_address,
+            transaction,
+            transaction_file,
+        } => commands::release_reservation(&daemon_address, transaction, transaction_file).await,
 
         Commands::AddressBalances { daemon_address } => {
             commands::address_balances(&daemon_address).await
@@ -1,19 +1,41 @@
-use crate::model::{AddressUtxos, BalanceInfo, SendResult};
+use crate::model::{
+    AccelerateResult, AddressInfo, AddressOwnership, AddressPage, AddressUtxos, AddressValidation,
+    BalanceInfo, BumpFeeResult, ConsolidateResult, ContactInfo, ExternalKeySource, FrozenUtxoInfo,
+    InvoiceEventInfo, InvoiceInfo, InvoiceState, MaxSendableInfo, PendingTransactionInfo,
+    SendResult, SweepDustResult, SweepExternalResult, TransactionState, TransactionStatusInfo,
+};
+use crate::payment_uri::PaymentUri;
 use common::error_location::ErrorLocation;
 use common::errors::{UserInputError, WalletError, WalletResult};
-use common::model::WalletSignableTransaction;
+use common::model::{WalletAddress, WalletSignableTransaction};
 use common::status_classify::{classify_rpc_status, classify_submit_status, classify_transport};
+use kaspa_addresses::Prefix;
 use kaspa_hashes::Hash;
+use proto::kaswallet_proto::sweep_external_request;
 use proto::kaswallet_proto::wallet_client::WalletClient as GrpcWalletClient;
 use proto::kaswallet_proto::{
-    BroadcastRequest, CreateUnsignedTransactionsRequest, GetAddressesRequest, GetBalanceRequest,
-    GetUtxosRequest, GetVersionRequest, NewAddressRequest, SendRequest, SignRequest,
-    TransactionDescription,
+    AbandonTransactionRequest, AccelerateRequest, AddContactRequest, BroadcastRequest,
+    BumpFeeRequest, ConsolidateRequest, CreateInvoiceRequest, CreateUnsignedTransactionsRequest,
+    ExternalMnemonic, FeePolicy, FreezeUtxosRequest, GetAddressesRequest, GetBalanceRequest,
+    GetInvoiceRequest, GetMaxSendableRequest, GetTransactionStatusRequest, GetUtxosRequest,
+    GetVersionRequest, Invoice as ProtoInvoice, InvoiceState as ProtoInvoiceState, KeychainFilter,
+    ListAddressLabelsRequest, ListContactsRequest, ListFrozenUtxosRequest,
+    ListInvoiceEventsRequest, ListInvoicesRequest, ListPendingTransactionsRequest,
+    NewAddressRequest, Outpoint, ReleaseReservationRequest, RemoveContactRequest, SendRequest,
+    SetAddressLabelRequest, SignMessageRequest, SignRequest, SweepDustRequest,
+    SweepExternalRequest, TransactionDescription, TransactionState as ProtoTransactionState,
+    TransactionStatus, UnfreezeUtxosRequest, UsageFilter, ValidateAddressRequest,
+    VerifyMessageRequest,
 };
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::time::Duration;
 use tonic::Request;
 use tonic::transport::{Channel, Endpoint};
 
+/// Addresses fetched per request by [`KaswalletClient::get_addresses`].
+const GET_ADDRESSES_PAGE_SIZE: u32 = 1000;
+
 /// A convenient wrapper around the kaswallet gRPC client.
 ///
 /// This client abstracts away the gRPC boilerplate and provides a clean,
@@ -66,28 +88,340 @@ impl KaswalletClient {
         Ok(response.version)
     }
 
-    /// Get all addresses in the wallet.
+    /// Get all receive addresses generated by [`Self::new_address`].
     pub async fn get_addresses(&mut self) -> WalletResult<Vec<String>> {
+        let mut addresses = vec![];
+        let mut page_token = None;
+        loop {
+            let page = self
+                .list_addresses(
+                    KeychainFilter::External,
+                    UsageFilter::All,
+                    GET_ADDRESSES_PAGE_SIZE,
+                    page_token,
+                )
+                .await?;
+            addresses.extend(page.addresses.into_iter().map(|info| info.address));
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => return Ok(addresses),
+            }
+        }
+    }
+
+    /// List the wallet's issued addresses with their derivation, balance and usage, a page at a
+    /// time. A `page_size` of 0 returns every matching address at once.
+    pub async fn list_addresses(
+        &mut self,
+        keychain: KeychainFilter,
+        usage: UsageFilter,
+        page_size: u32,
+        page_token: Option<String>,
+    ) -> WalletResult<AddressPage> {
         let response = self
             .grpc_client
-            .get_addresses(Request::new(GetAddressesRequest {}))
+            .get_addresses(Request::new(GetAddressesRequest {
+                keychain: keychain.into(),
+                usage: usage.into(),
+                page_size,
+                page_token: page_token.unwrap_or_default(),
+            }))
             .await
             .map_err(|s| WalletError::from(classify_rpc_status("get_addresses", s)))?
             .into_inner();
-        Ok(response.address)
+        Ok(AddressPage {
+            addresses: response
+                .address_infos
+                .into_iter()
+                .map(AddressInfo::from)
+                .collect(),
+            next_page_token: (!response.next_page_token.is_empty())
+                .then_some(response.next_page_token),
+        })
     }
 
     /// Generate a new address in the wallet.
     pub async fn new_address(&mut self) -> WalletResult<String> {
         let response = self
             .grpc_client
-            .new_address(Request::new(NewAddressRequest {}))
+            .new_address(Request::new(NewAddressRequest { count: 1 }))
             .await
             .map_err(|s| WalletError::from(classify_rpc_status("new_address", s)))?
             .into_inner();
         Ok(response.address)
     }
 
+    /// Generate `count` consecutive new addresses in the wallet, at most 10000 per call.
+    pub async fn new_addresses(&mut self, count: u32) -> WalletResult<Vec<String>> {
+        let response = self
+            .grpc_client
+            .new_address(Request::new(NewAddressRequest { count }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("new_address", s)))?
+            .into_inner();
+        Ok(response.addresses)
+    }
+
+    /// Parse a `kaspa:` payment URI, refusing one whose address is not on the daemon's network.
+    pub async fn parse_payment_uri(&mut self, uri: &str) -> WalletResult<PaymentUri> {
+        let address = PaymentUri::from_str(uri)?.address.to_string();
+        let network_prefix = self.network_prefix(address).await?;
+        crate::payment_uri::parse_payment_uri(uri, network_prefix)
+    }
+
+    /// Build a payment URI for `address`, which must be on the daemon's network. `amount` is in
+    /// sompi.
+    pub async fn build_payment_uri(
+        &mut self,
+        address: &str,
+        amount: Option<u64>,
+        label: Option<String>,
+        message: Option<String>,
+    ) -> WalletResult<PaymentUri> {
+        let network_prefix = self.network_prefix(address.to_string()).await?;
+        crate::payment_uri::build_payment_uri(address, network_prefix, amount, label, message)
+    }
+
+    /// The daemon's network prefix, as reported by `ValidateAddress` for `address`.
+    async fn network_prefix(&mut self, address: String) -> WalletResult<Prefix> {
+        let validation = self.validate_address(address).await?;
+        Prefix::try_from(validation.network_prefix.as_str()).map_err(|e| {
+            WalletError::from(common::errors::RpcError::MalformedResponse {
+                operation: "validate_address",
+                reason: format!("invalid network_prefix: {e}"),
+                location: ErrorLocation::capture(),
+            })
+        })
+    }
+
+    /// Check that an address parses and is on the daemon's network, and whether it belongs to
+    /// the wallet.
+    pub async fn validate_address(&mut self, address: String) -> WalletResult<AddressValidation> {
+        let response = self
+            .grpc_client
+            .validate_address(Request::new(ValidateAddressRequest { address }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("validate_address", s)))?
+            .into_inner();
+
+        let version = response.version();
+        let ownership = if response.is_mine {
+            let wallet_address = response.wallet_address.ok_or_else(|| {
+                WalletError::from(UserInputError::MissingField {
+                    field: "wallet_address",
+                    location: ErrorLocation::capture(),
+                })
+            })?;
+            Some(AddressOwnership {
+                wallet_address: WalletAddress::try_from(wallet_address)?,
+                derivation_path: response.derivation_path,
+            })
+        } else {
+            None
+        };
+        Ok(AddressValidation {
+            is_valid: response.is_valid,
+            invalid_reason: (!response.is_valid).then_some(response.invalid_reason),
+            is_network_match: response.is_network_match,
+            prefix: response.prefix,
+            version,
+            ownership,
+            network_prefix: response.network_prefix,
+        })
+    }
+
+    /// Label one of the wallet's addresses. An empty label removes it.
+    pub async fn set_address_label(&mut self, address: String, label: String) -> WalletResult<()> {
+        self.grpc_client
+            .set_address_label(Request::new(SetAddressLabelRequest { address, label }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("set_address_label", s)))?;
+        Ok(())
+    }
+
+    /// All address labels, keyed by address.
+    pub async fn list_address_labels(&mut self) -> WalletResult<BTreeMap<String, String>> {
+        let response = self
+            .grpc_client
+            .list_address_labels(Request::new(ListAddressLabelsRequest {}))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_address_labels", s)))?
+            .into_inner();
+        Ok(response
+            .labels
+            .into_iter()
+            .map(|label| (label.address, label.label))
+            .collect())
+    }
+
+    /// Save an external address under `alias`, which can then be passed as a `to_address`.
+    pub async fn add_contact(
+        &mut self,
+        alias: String,
+        address: String,
+        note: String,
+    ) -> WalletResult<ContactInfo> {
+        let response = self
+            .grpc_client
+            .add_contact(Request::new(AddContactRequest {
+                alias,
+                address,
+                note,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("add_contact", s)))?
+            .into_inner();
+        let contact = response.contact.ok_or_else(|| {
+            WalletError::from(UserInputError::MissingField {
+                field: "contact",
+                location: ErrorLocation::capture(),
+            })
+        })?;
+        Ok(contact.into())
+    }
+
+    /// Remove a contact. Returns whether there was one with `alias`.
+    pub async fn remove_contact(&mut self, alias: String) -> WalletResult<bool> {
+        let response = self
+            .grpc_client
+            .remove_contact(Request::new(RemoveContactRequest { alias }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("remove_contact", s)))?
+            .into_inner();
+        Ok(response.removed)
+    }
+
+    /// All contacts, in alias order.
+    pub async fn list_contacts(&mut self) -> WalletResult<Vec<ContactInfo>> {
+        let response = self
+            .grpc_client
+            .list_contacts(Request::new(ListContactsRequest {}))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_contacts", s)))?
+            .into_inner();
+        Ok(response.contacts.into_iter().map(Into::into).collect())
+    }
+
+    /// Sign `message` with the key of one of the wallet's schnorr P2PK addresses, proving control
+    /// of it. Returns the signature in hex.
+    pub async fn sign_message(
+        &mut self,
+        address: String,
+        message: String,
+        password: String,
+    ) -> WalletResult<String> {
+        let response = self
+            .grpc_client
+            .sign_message(Request::new(SignMessageRequest {
+                address,
+                message,
+                password,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("sign_message", s)))?
+            .into_inner();
+        Ok(response.signature)
+    }
+
+    /// Check a hex message signature against a schnorr P2PK address, which need not belong to
+    /// the wallet.
+    pub async fn verify_message(
+        &mut self,
+        address: String,
+        message: String,
+        signature: String,
+    ) -> WalletResult<bool> {
+        let response = self
+            .grpc_client
+            .verify_message(Request::new(VerifyMessageRequest {
+                address,
+                message,
+                signature,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("verify_message", s)))?
+            .into_inner();
+        Ok(response.is_valid)
+    }
+
+    /// Create an invoice for `amount` sompi on a fresh address. `expiry` of `None` never expires.
+    pub async fn create_invoice(
+        &mut self,
+        amount: u64,
+        memo: String,
+        expiry: Option<Duration>,
+    ) -> WalletResult<InvoiceInfo> {
+        let response = self
+            .grpc_client
+            .create_invoice(Request::new(CreateInvoiceRequest {
+                amount,
+                memo,
+                expiry_seconds: expiry.map(|expiry| expiry.as_secs()).unwrap_or_default(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("create_invoice", s)))?
+            .into_inner();
+        Self::invoice_from_response(response.invoice)
+    }
+
+    pub async fn get_invoice(&mut self, invoice_id: u64) -> WalletResult<InvoiceInfo> {
+        let response = self
+            .grpc_client
+            .get_invoice(Request::new(GetInvoiceRequest { invoice_id }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("get_invoice", s)))?
+            .into_inner();
+        Self::invoice_from_response(response.invoice)
+    }
+
+    /// Invoices in any of `states`, or all of them if it is empty, oldest first.
+    pub async fn list_invoices(
+        &mut self,
+        states: Vec<InvoiceState>,
+    ) -> WalletResult<Vec<InvoiceInfo>> {
+        let response = self
+            .grpc_client
+            .list_invoices(Request::new(ListInvoicesRequest {
+                states: states
+                    .into_iter()
+                    .map(|state| ProtoInvoiceState::from(state) as i32)
+                    .collect(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_invoices", s)))?
+            .into_inner();
+        Ok(response.invoices.into_iter().map(Into::into).collect())
+    }
+
+    /// Up to `limit` invoice state changes after `after_sequence`, oldest first. A `limit` of 0
+    /// returns all of them.
+    pub async fn list_invoice_events(
+        &mut self,
+        after_sequence: u64,
+        limit: u32,
+    ) -> WalletResult<Vec<InvoiceEventInfo>> {
+        let response = self
+            .grpc_client
+            .list_invoice_events(Request::new(ListInvoiceEventsRequest {
+                after_sequence,
+                limit,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_invoice_events", s)))?
+            .into_inner();
+        Ok(response.events.into_iter().map(Into::into).collect())
+    }
+
+    fn invoice_from_response(invoice: Option<ProtoInvoice>) -> WalletResult<InvoiceInfo> {
+        let invoice = invoice.ok_or_else(|| {
+            WalletError::from(UserInputError::MissingField {
+                field: "invoice",
+                location: ErrorLocation::capture(),
+            })
+        })?;
+        Ok(invoice.into())
+    }
+
     /// Get the balance of the wallet.
     pub async fn get_balance(
         &mut self,
@@ -105,6 +439,9 @@ impl KaswalletClient {
         Ok(BalanceInfo {
             available: response.available,
             pending: response.pending,
+            frozen: response.frozen,
+            covenant_bound: response.covenant_bound,
+            dust: response.dust,
             address_balances: response
                 .address_balances
                 .into_iter()
@@ -113,7 +450,7 @@ impl KaswalletClient {
         })
     }
 
-    /// Get UTXOs for the wallet.
+    /// Get UTXOs for the wallet. Frozen UTXOs are not included; see [`Self::list_frozen_utxos`].
     pub async fn get_utxos(
         &mut self,
         addresses: Vec<String>,
@@ -138,7 +475,34 @@ impl KaswalletClient {
             .collect())
     }
 
+    /// Get the covenant-bound UTXOs on the wallet's addresses. The wallet holds them but cannot
+    /// spend them.
+    pub async fn get_covenant_bound_utxos(
+        &mut self,
+        addresses: Vec<String>,
+    ) -> WalletResult<Vec<AddressUtxos>> {
+        let response = self
+            .grpc_client
+            .get_utxos(Request::new(GetUtxosRequest {
+                addresses,
+                include_pending: true,
+                include_dust: true,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("get_utxos", s)))?
+            .into_inner();
+
+        Ok(response
+            .covenant_bound_addresses_to_utxos
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     /// Create unsigned transactions based on the transaction description.
+    ///
+    /// Their inputs stay reserved until they are broadcast, released with
+    /// [`Self::release_reservation`] or the daemon's reservation TTL passes.
     pub async fn create_unsigned_transactions(
         &mut self,
         transaction_description: TransactionDescription,
@@ -159,6 +523,160 @@ impl KaswalletClient {
             .collect()
     }
 
+    /// How much a send-all of `transaction_description` would deliver once fees, dust,
+    /// immature coinbase outputs and merge transactions are accounted for. Nothing is created or
+    /// reserved. Its `amount`, `is_send_all` and `subtract_fee_from_amount` are ignored.
+    pub async fn get_max_sendable(
+        &mut self,
+        transaction_description: TransactionDescription,
+    ) -> WalletResult<MaxSendableInfo> {
+        let response = self
+            .grpc_client
+            .get_max_sendable(Request::new(GetMaxSendableRequest {
+                transaction_description: Some(transaction_description),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("get_max_sendable", s)))?
+            .into_inner();
+
+        Ok(MaxSendableInfo {
+            amount: response.amount,
+            fee: response.fee,
+            transaction_count: response.transaction_count,
+            applied_fee: response.applied_fee.map(Into::into),
+        })
+    }
+
+    /// Merge the smallest UTXOs matching the filters into one, until `target_utxo_count` matching
+    /// UTXOs remain. With a `password` the transactions are signed and broadcast, otherwise they
+    /// are returned unsigned.
+    ///
+    /// # Security Note
+    /// With a password, this command sends it over the network. Only use on trusted or secure
+    /// connections.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn consolidate(
+        &mut self,
+        target_utxo_count: u32,
+        min_input_value: u64,
+        max_input_value: Option<u64>,
+        from_addresses: Vec<String>,
+        fee_policy: Option<FeePolicy>,
+        password: Option<String>,
+    ) -> WalletResult<ConsolidateResult> {
+        let response = self
+            .grpc_client
+            .consolidate(Request::new(ConsolidateRequest {
+                target_utxo_count,
+                min_input_value,
+                max_input_value,
+                from_addresses,
+                fee_policy,
+                broadcast: password.is_some(),
+                password: password.unwrap_or_default(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("consolidate", s)))?
+            .into_inner();
+
+        let transactions = response
+            .transactions
+            .into_iter()
+            .map(WalletSignableTransaction::try_from)
+            .collect::<WalletResult<Vec<_>>>()?;
+        let transaction_ids = Self::transaction_ids_to_hashes(response.transaction_ids)?;
+
+        Ok(ConsolidateResult {
+            transactions,
+            transaction_ids,
+        })
+    }
+
+    /// Merge the wallet's dust UTXOs into change if the fee to do so is lower than their value.
+    /// Without a `password` this is a dry run that only reports the cost.
+    ///
+    /// # Security Note
+    /// With a password, this command sends it over the network. Only use on trusted or secure
+    /// connections.
+    pub async fn sweep_dust(
+        &mut self,
+        from_addresses: Vec<String>,
+        fee_policy: Option<FeePolicy>,
+        password: Option<String>,
+    ) -> WalletResult<SweepDustResult> {
+        let response = self
+            .grpc_client
+            .sweep_dust(Request::new(SweepDustRequest {
+                from_addresses,
+                fee_policy,
+                dry_run: password.is_none(),
+                password: password.unwrap_or_default(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("sweep_dust", s)))?
+            .into_inner();
+
+        Ok(SweepDustResult {
+            dust_utxo_count: response.dust_utxo_count,
+            dust_value: response.dust_value,
+            fee: response.fee,
+            is_economic: response.is_economic,
+            applied_fee: response.applied_fee.map(Into::into),
+            transaction_ids: Self::transaction_ids_to_hashes(response.transaction_ids)?,
+        })
+    }
+
+    /// Sweep the funds of a private key or mnemonic held outside the wallet to a fresh wallet
+    /// address. The daemon uses the key in memory only. With `dry_run` nothing is signed or
+    /// broadcast, and no address is derived.
+    ///
+    /// # Security Note
+    /// This command sends the external key over the network. Only use on trusted or secure
+    /// connections.
+    pub async fn sweep_external(
+        &mut self,
+        key: ExternalKeySource,
+        fee_policy: Option<FeePolicy>,
+        dry_run: bool,
+    ) -> WalletResult<SweepExternalResult> {
+        let key = match key {
+            ExternalKeySource::PrivateKey(private_key) => {
+                sweep_external_request::Key::PrivateKey(private_key)
+            }
+            ExternalKeySource::Mnemonic {
+                phrase,
+                passphrase,
+                derivation_path,
+                gap_limit,
+            } => sweep_external_request::Key::Mnemonic(ExternalMnemonic {
+                phrase,
+                passphrase,
+                derivation_path,
+                gap_limit,
+            }),
+        };
+        let response = self
+            .grpc_client
+            .sweep_external(Request::new(SweepExternalRequest {
+                key: Some(key),
+                fee_policy,
+                dry_run,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("sweep_external", s)))?
+            .into_inner();
+
+        Ok(SweepExternalResult {
+            to_address: response.to_address,
+            from_addresses: response.from_addresses,
+            utxo_count: response.utxo_count,
+            swept_value: response.swept_value,
+            fee: response.fee,
+            applied_fee: response.applied_fee.map(Into::into),
+            transaction_ids: Self::transaction_ids_to_hashes(response.transaction_ids)?,
+        })
+    }
+
     /// Sign unsigned transactions with the wallet's private keys.
     pub async fn sign(
         &mut self,
@@ -187,10 +705,52 @@ impl KaswalletClient {
         &mut self,
         transactions: Vec<WalletSignableTransaction>,
     ) -> WalletResult<Vec<Hash>> {
+        let (transaction_ids, _) = self.broadcast_inner(transactions, None, None).await?;
+        Ok(transaction_ids)
+    }
+
+    /// Broadcast signed transactions under a client-chosen idempotency key. Retrying with the
+    /// same key returns the original transaction ids instead of broadcasting again; reusing it
+    /// for different transactions fails.
+    pub async fn broadcast_with_idempotency_key(
+        &mut self,
+        transactions: Vec<WalletSignableTransaction>,
+        idempotency_key: String,
+    ) -> WalletResult<Vec<Hash>> {
+        let (transaction_ids, _) = self
+            .broadcast_inner(transactions, None, Some(idempotency_key))
+            .await?;
+        Ok(transaction_ids)
+    }
+
+    /// Broadcast signed transactions, then wait until each is accepted `confirmations` DAA score
+    /// units deep or rejected, for at most `timeout`. Transactions still pending at the deadline
+    /// are reported as such rather than as an error.
+    pub async fn broadcast_and_wait(
+        &mut self,
+        transactions: Vec<WalletSignableTransaction>,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> WalletResult<(Vec<Hash>, Vec<TransactionStatusInfo>)> {
+        self.broadcast_inner(transactions, Some((confirmations, timeout)), None)
+            .await
+    }
+
+    async fn broadcast_inner(
+        &mut self,
+        transactions: Vec<WalletSignableTransaction>,
+        wait: Option<(u64, Duration)>,
+        idempotency_key: Option<String>,
+    ) -> WalletResult<(Vec<Hash>, Vec<TransactionStatusInfo>)> {
         let response = self
             .grpc_client
             .broadcast(Request::new(BroadcastRequest {
                 transactions: transactions.into_iter().map(Into::into).collect(),
+                wait_for_confirmations: wait.map(|(confirmations, _)| confirmations),
+                wait_timeout_millis: wait
+                    .map(|(_, timeout)| timeout.as_millis() as u64)
+                    .unwrap_or_default(),
+                idempotency_key: idempotency_key.unwrap_or_default(),
             }))
             .await
             .map_err(|s| {
@@ -199,7 +759,10 @@ impl KaswalletClient {
             })?
             .into_inner();
 
-        Self::transaction_ids_to_hashes(response.transaction_ids)
+        Ok((
+            Self::transaction_ids_to_hashes(response.transaction_ids)?,
+            Self::transaction_statuses_from_proto(response.statuses)?,
+        ))
     }
 
     /// Send funds in a single operation (create, sign, and broadcast).
@@ -207,12 +770,66 @@ impl KaswalletClient {
         &mut self,
         transaction_description: TransactionDescription,
         password: String,
+    ) -> WalletResult<SendResult> {
+        self.send_inner(transaction_description, password, None, None)
+            .await
+    }
+
+    /// Send funds under a client-chosen idempotency key, so a request that timed out can be
+    /// retried safely: a repeated key returns the original transactions
+    /// ([`SendResult::idempotent_replay`]) instead of paying again. Reusing the key for a
+    /// different transaction description fails.
+    pub async fn send_with_idempotency_key(
+        &mut self,
+        transaction_description: TransactionDescription,
+        password: String,
+        idempotency_key: String,
+    ) -> WalletResult<SendResult> {
+        self.send_inner(
+            transaction_description,
+            password,
+            None,
+            Some(idempotency_key),
+        )
+        .await
+    }
+
+    /// Send funds, then wait until each transaction is accepted `confirmations` DAA score units
+    /// deep or rejected, for at most `timeout`. The outcome is reported in
+    /// [`SendResult::statuses`]; transactions still pending at the deadline are not an error.
+    pub async fn send_and_wait(
+        &mut self,
+        transaction_description: TransactionDescription,
+        password: String,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> WalletResult<SendResult> {
+        self.send_inner(
+            transaction_description,
+            password,
+            Some((confirmations, timeout)),
+            None,
+        )
+        .await
+    }
+
+    async fn send_inner(
+        &mut self,
+        transaction_description: TransactionDescription,
+        password: String,
+        wait: Option<(u64, Duration)>,
+        idempotency_key: Option<String>,
     ) -> WalletResult<SendResult> {
         let response = self
             .grpc_client
             .send(Request::new(SendRequest {
                 transaction_description: Some(transaction_description),
                 password,
+                wait_for_confirmations: wait.map(|(confirmations, _)| confirmations),
+                wait_timeout_millis: wait
+                    .map(|(_, timeout)| timeout.as_millis() as u64)
+                    .unwrap_or_default(),
+                idempotency_key: idempotency_key.unwrap_or_default(),
             }))
             .await
             .map_err(|s| {
@@ -231,12 +848,258 @@ impl KaswalletClient {
             .map(WalletSignableTransaction::try_from)
             .collect::<WalletResult<Vec<_>>>()?;
 
+        let statuses = Self::transaction_statuses_from_proto(response.statuses)?;
+
         Ok(SendResult {
             transaction_ids,
             signed_transactions,
+            statuses,
+            idempotent_replay: response.idempotent_replay,
+            applied_fee: response.applied_fee.map(Into::into),
         })
     }
 
+    /// Get the confirmation status of transactions submitted through the daemon.
+    pub async fn get_transaction_status(
+        &mut self,
+        transaction_ids: Vec<Hash>,
+    ) -> WalletResult<Vec<TransactionStatusInfo>> {
+        let response = self
+            .grpc_client
+            .get_transaction_status(Request::new(GetTransactionStatusRequest {
+                transaction_ids: transaction_ids.iter().map(ToString::to_string).collect(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("get_transaction_status", s)))?
+            .into_inner();
+
+        Self::transaction_statuses_from_proto(response.statuses)
+    }
+
+    fn transaction_statuses_from_proto(
+        statuses: Vec<TransactionStatus>,
+    ) -> WalletResult<Vec<TransactionStatusInfo>> {
+        statuses
+            .into_iter()
+            .map(|status| {
+                let proto_state = status.state();
+                let transaction_id = Self::transaction_ids_to_hashes(vec![status.transaction_id])?
+                    .pop()
+                    .unwrap_or_default();
+                let state = match proto_state {
+                    ProtoTransactionState::Unknown => TransactionState::Unknown,
+                    ProtoTransactionState::Pending => TransactionState::Pending,
+                    ProtoTransactionState::Accepted => TransactionState::Accepted {
+                        accepting_block_hash: Self::transaction_ids_to_hashes(vec![
+                            status.accepting_block_hash,
+                        ])?
+                        .pop()
+                        .unwrap_or_default(),
+                        accepting_daa_score: status.accepting_daa_score,
+                    },
+                    ProtoTransactionState::Rejected => TransactionState::Rejected {
+                        reason: status.rejection_reason,
+                    },
+                };
+                Ok(TransactionStatusInfo {
+                    transaction_id,
+                    state,
+                    confirmations: status.confirmations,
+                })
+            })
+            .collect()
+    }
+
+    /// Replace a wallet transaction stuck in the mempool with a higher-fee version of itself.
+    ///
+    /// # Security Note
+    /// This command sends the password over the network. Only use on trusted or secure connections.
+    pub async fn bump_fee(
+        &mut self,
+        transaction_id: Hash,
+        fee_policy: Option<FeePolicy>,
+        password: String,
+    ) -> WalletResult<BumpFeeResult> {
+        let response = self
+            .grpc_client
+            .bump_fee(Request::new(BumpFeeRequest {
+                transaction_id: transaction_id.to_string(),
+                fee_policy,
+                password,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("bump_fee", s)))?
+            .into_inner();
+
+        let mut transaction_ids = Self::transaction_ids_to_hashes(vec![
+            response.transaction_id,
+            response.replaced_transaction_id,
+        ])?;
+        let replaced_transaction_id = transaction_ids.pop().unwrap_or_default();
+        let transaction_id = transaction_ids.pop().unwrap_or_default();
+        let signed_transaction = response
+            .signed_transaction
+            .map(WalletSignableTransaction::try_from)
+            .transpose()?;
+
+        Ok(BumpFeeResult {
+            transaction_id,
+            replaced_transaction_id,
+            fee: response.fee,
+            signed_transaction,
+        })
+    }
+
+    /// Speed up an incoming low-fee transaction by spending its outputs to this wallet with a
+    /// child transaction that raises the package fee rate to `target_fee_rate`.
+    ///
+    /// # Security Note
+    /// This command sends the password over the network. Only use on trusted or secure connections.
+    pub async fn accelerate(
+        &mut self,
+        transaction_id: Hash,
+        target_fee_rate: f64,
+        password: String,
+    ) -> WalletResult<AccelerateResult> {
+        let response = self
+            .grpc_client
+            .accelerate(Request::new(AccelerateRequest {
+                transaction_id: transaction_id.to_string(),
+                target_fee_rate,
+                password,
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("accelerate", s)))?
+            .into_inner();
+
+        let transaction_id = Self::transaction_ids_to_hashes(vec![response.transaction_id])?
+            .pop()
+            .unwrap_or_default();
+        let signed_transaction = response
+            .signed_transaction
+            .map(WalletSignableTransaction::try_from)
+            .transpose()?;
+
+        Ok(AccelerateResult {
+            transaction_id,
+            fee: response.fee,
+            signed_transaction,
+        })
+    }
+
+    /// List the wallet-generated transactions that were submitted but not yet accepted.
+    pub async fn list_pending_transactions(&mut self) -> WalletResult<Vec<PendingTransactionInfo>> {
+        let response = self
+            .grpc_client
+            .list_pending_transactions(Request::new(ListPendingTransactionsRequest {}))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_pending_transactions", s)))?
+            .into_inner();
+
+        response
+            .pending_transactions
+            .into_iter()
+            .map(|pending| {
+                let transaction_id = Self::transaction_ids_to_hashes(vec![pending.transaction_id])?
+                    .pop()
+                    .unwrap_or_default();
+                let transaction = pending
+                    .transaction
+                    .map(WalletSignableTransaction::try_from)
+                    .transpose()?;
+                Ok(PendingTransactionInfo {
+                    transaction_id,
+                    submitted_daa_score: pending.submitted_daa_score,
+                    fee: pending.fee,
+                    rebroadcast_attempts: pending.rebroadcast_attempts,
+                    last_rebroadcast_error: Some(pending.last_rebroadcast_error)
+                        .filter(|error| !error.is_empty()),
+                    transaction,
+                })
+            })
+            .collect()
+    }
+
+    /// Stop tracking a pending transaction that kaspad rejected or evicted, making its inputs
+    /// spendable again. Pending transactions spending its outputs are abandoned with it.
+    ///
+    /// Returns the ids of all abandoned transactions.
+    pub async fn abandon_transaction(&mut self, transaction_id: Hash) -> WalletResult<Vec<Hash>> {
+        let response = self
+            .grpc_client
+            .abandon_transaction(Request::new(AbandonTransactionRequest {
+                transaction_id: transaction_id.to_string(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("abandon_transaction", s)))?
+            .into_inner();
+
+        Self::transaction_ids_to_hashes(response.abandoned_transaction_ids)
+    }
+
+    /// Exclude UTXOs from automatic coin selection until they are unfrozen. `reason` is an
+    /// optional label stored with the freeze.
+    pub async fn freeze_utxos(
+        &mut self,
+        outpoints: Vec<Outpoint>,
+        reason: String,
+    ) -> WalletResult<Vec<FrozenUtxoInfo>> {
+        let response = self
+            .grpc_client
+            .freeze_utxos(Request::new(FreezeUtxosRequest { outpoints, reason }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("freeze_utxos", s)))?
+            .into_inner();
+
+        Ok(response.frozen_utxos.into_iter().map(Into::into).collect())
+    }
+
+    /// Make frozen UTXOs selectable again. Returns the given outpoints that were frozen.
+    pub async fn unfreeze_utxos(
+        &mut self,
+        outpoints: Vec<Outpoint>,
+    ) -> WalletResult<Vec<Outpoint>> {
+        let response = self
+            .grpc_client
+            .unfreeze_utxos(Request::new(UnfreezeUtxosRequest { outpoints }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("unfreeze_utxos", s)))?
+            .into_inner();
+
+        Ok(response.unfrozen_outpoints)
+    }
+
+    /// Release the UTXOs reserved for unsigned transactions that will not be broadcast, making
+    /// them available to other selections before the reservation expires. Returns the released
+    /// outpoints.
+    pub async fn release_reservation(
+        &mut self,
+        transaction_ids: Vec<Hash>,
+    ) -> WalletResult<Vec<Outpoint>> {
+        let response = self
+            .grpc_client
+            .release_reservation(Request::new(ReleaseReservationRequest {
+                transaction_ids: transaction_ids.iter().map(ToString::to_string).collect(),
+            }))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("release_reservation", s)))?
+            .into_inner();
+
+        Ok(response.released_outpoints)
+    }
+
+    /// List all frozen UTXOs, oldest freeze first.
+    pub async fn list_frozen_utxos(&mut self) -> WalletResult<Vec<FrozenUtxoInfo>> {
+        let response = self
+            .grpc_client
+            .list_frozen_utxos(Request::new(ListFrozenUtxosRequest {}))
+            .await
+            .map_err(|s| WalletError::from(classify_rpc_status("list_frozen_utxos", s)))?
+            .into_inner();
+
+        Ok(response.frozen_utxos.into_iter().map(Into::into).collect())
+    }
+
     fn transaction_ids_to_hashes(transaction_ids: Vec<String>) -> WalletResult<Vec<Hash>> {
         transaction_ids
             .into_iter()
@@ -1,4 +1,5 @@
 pub mod client;
 pub mod model;
+pub mod payment_uri;
 
 pub use common::errors::{WalletError, WalletResult};
@@ -1,10 +1,13 @@
 use crate::client::KaswalletClient;
 use common::errors::WalletResult;
-use common::model::WalletSignableTransaction;
+use common::model::{Keychain, WalletAddress, WalletSignableTransaction};
 use kaspa_hashes::Hash;
 use proto::kaswallet_proto::{
-    AddressBalances as ProtoAddressBalances, AddressToUtxos as ProtoAddressToUtxos, FeePolicy,
-    Outpoint, TransactionDescription, Utxo as ProtoUtxo,
+    AddressBalances as ProtoAddressBalances, AddressInfo as ProtoAddressInfo,
+    AddressToUtxos as ProtoAddressToUtxos, AddressVersion, AppliedFee as ProtoAppliedFee,
+    CoinSelectionStrategy, Contact as ProtoContact, FeePolicy, FrozenUtxo as ProtoFrozenUtxo,
+    Invoice as ProtoInvoice, InvoiceEvent as ProtoInvoiceEvent, InvoiceState as ProtoInvoiceState,
+    Outpoint, ScriptPublicKey, TransactionDescription, Utxo as ProtoUtxo,
 };
 
 /// Balance information for a specific address.
@@ -13,14 +16,22 @@ pub struct AddressBalance {
     pub address: String,
     pub available: u64,
     pub pending: u64,
+    pub frozen: u64,
+    pub covenant_bound: u64,
+    pub dust: u64,
+    pub label: Option<String>,
 }
 
 impl From<ProtoAddressBalances> for AddressBalance {
     fn from(value: ProtoAddressBalances) -> Self {
         Self {
+            label: (!value.label.is_empty()).then_some(value.label),
             address: value.address,
             available: value.available,
             pending: value.pending,
+            frozen: value.frozen,
+            covenant_bound: value.covenant_bound,
+            dust: value.dust,
         }
     }
 }
@@ -30,9 +41,78 @@ impl From<ProtoAddressBalances> for AddressBalance {
 pub struct BalanceInfo {
     pub available: u64,
     pub pending: u64,
+    /// Held back by a freeze; counted in neither `available` nor `pending`.
+    pub frozen: u64,
+    /// Bound to a covenant the wallet cannot spend; counted nowhere else.
+    pub covenant_bound: u64,
+    /// Dust left out of `available` and `pending` by the daemon's `--hide-dust-from-balance`.
+    pub dust: u64,
     pub address_balances: Vec<AddressBalance>,
 }
 
+/// One of the wallet's issued addresses, from [`KaswalletClient::list_addresses`].
+#[derive(Debug, Clone)]
+pub struct AddressInfo {
+    pub address: String,
+    pub keychain: Keychain,
+    pub index: u32,
+    /// From the master key, e.g. `m/44'/111111'/0'/0/1`.
+    pub derivation_path: String,
+    /// Whether the address has ever held funds. Stays true once it is spent from.
+    pub is_used: bool,
+    /// In sompi, confirmed and pending.
+    pub balance: u64,
+    pub utxo_count: u32,
+    pub label: Option<String>,
+}
+
+impl From<ProtoAddressInfo> for AddressInfo {
+    fn from(value: ProtoAddressInfo) -> Self {
+        Self {
+            keychain: value.keychain().into(),
+            address: value.address,
+            index: value.index,
+            derivation_path: value.derivation_path,
+            is_used: value.is_used,
+            balance: value.balance,
+            utxo_count: value.utxo_count,
+            label: (!value.label.is_empty()).then_some(value.label),
+        }
+    }
+}
+
+/// A page of [`KaswalletClient::list_addresses`].
+#[derive(Debug, Clone)]
+pub struct AddressPage {
+    pub addresses: Vec<AddressInfo>,
+    /// Pass to the next call for the following page; `None` on the last page.
+    pub next_page_token: Option<String>,
+}
+
+/// What the daemon knows about an address, from [`KaswalletClient::validate_address`].
+#[derive(Debug, Clone)]
+pub struct AddressValidation {
+    pub is_valid: bool,
+    /// Why the address does not parse; set only if not `is_valid`.
+    pub invalid_reason: Option<String>,
+    /// Whether the prefix is the daemon's network.
+    pub is_network_match: bool,
+    pub prefix: String,
+    pub version: AddressVersion,
+    /// Set only for the wallet's own addresses.
+    pub ownership: Option<AddressOwnership>,
+    /// The daemon's network prefix, reported even for an invalid address.
+    pub network_prefix: String,
+}
+
+/// Where one of the wallet's own addresses comes from.
+#[derive(Debug, Clone)]
+pub struct AddressOwnership {
+    pub wallet_address: WalletAddress,
+    /// From the master key, e.g. `m/44'/111111'/0'/0/1`.
+    pub derivation_path: String,
+}
+
 /// UTXO information.
 #[derive(Debug, Clone)]
 pub struct Utxo {
@@ -44,6 +124,8 @@ pub struct Utxo {
     pub is_coinbase: bool,
     pub is_pending: bool,
     pub is_dust: bool,
+    /// Hex covenant id of a covenant-bound UTXO, which the wallet cannot spend.
+    pub covenant_id: Option<String>,
 }
 
 impl From<ProtoUtxo> for Utxo {
@@ -61,6 +143,35 @@ impl From<ProtoUtxo> for Utxo {
             is_coinbase: utxo_entry.is_coinbase,
             is_pending: value.is_pending,
             is_dust: value.is_dust,
+            covenant_id: value.is_covenant_bound.then_some(value.covenant_id),
+        }
+    }
+}
+
+/// A UTXO excluded from automatic coin selection.
+#[derive(Debug, Clone)]
+pub struct FrozenUtxoInfo {
+    pub outpoint: Outpoint,
+    pub reason: String,
+    pub frozen_at_unix_millis: u64,
+    /// `None` once the UTXO is no longer in the wallet's UTXO set.
+    pub address: Option<String>,
+    pub amount: Option<u64>,
+}
+
+impl From<ProtoFrozenUtxo> for FrozenUtxoInfo {
+    fn from(value: ProtoFrozenUtxo) -> Self {
+        let (address, amount) = if value.is_in_wallet {
+            (Some(value.address), Some(value.amount))
+        } else {
+            (None, None)
+        };
+        Self {
+            outpoint: value.outpoint.unwrap_or_default(),
+            reason: value.reason,
+            frozen_at_unix_millis: value.frozen_at_unix_millis,
+            address,
+            amount,
         }
     }
 }
@@ -70,6 +181,7 @@ impl From<ProtoUtxo> for Utxo {
 pub struct AddressUtxos {
     pub address: String,
     pub utxos: Vec<Utxo>,
+    pub label: Option<String>,
 }
 
 impl From<ProtoAddressToUtxos> for AddressUtxos {
@@ -77,6 +189,123 @@ impl From<ProtoAddressToUtxos> for AddressUtxos {
         Self {
             address: value.address,
             utxos: value.utxos.into_iter().map(Into::into).collect(),
+            label: (!value.label.is_empty()).then_some(value.label),
+        }
+    }
+}
+
+/// An external address saved in the daemon's address book. Its alias can be used as the
+/// recipient of a transaction.
+#[derive(Debug, Clone)]
+pub struct ContactInfo {
+    pub alias: String,
+    pub address: String,
+    pub note: Option<String>,
+    pub created_at_unix_millis: u64,
+}
+
+impl From<ProtoContact> for ContactInfo {
+    fn from(value: ProtoContact) -> Self {
+        Self {
+            alias: value.alias,
+            address: value.address,
+            note: (!value.note.is_empty()).then_some(value.note),
+            created_at_unix_millis: value.created_at_unix_millis,
+        }
+    }
+}
+
+/// Where an invoice stands against its amount and expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvoiceState {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+    Overpaid,
+    /// Expired short of its amount; later payments do not change it.
+    Expired,
+}
+
+impl From<ProtoInvoiceState> for InvoiceState {
+    fn from(value: ProtoInvoiceState) -> Self {
+        match value {
+            ProtoInvoiceState::Unpaid => Self::Unpaid,
+            ProtoInvoiceState::PartiallyPaid => Self::PartiallyPaid,
+            ProtoInvoiceState::Paid => Self::Paid,
+            ProtoInvoiceState::Overpaid => Self::Overpaid,
+            ProtoInvoiceState::Expired => Self::Expired,
+        }
+    }
+}
+
+impl From<InvoiceState> for ProtoInvoiceState {
+    fn from(value: InvoiceState) -> Self {
+        match value {
+            InvoiceState::Unpaid => Self::Unpaid,
+            InvoiceState::PartiallyPaid => Self::PartiallyPaid,
+            InvoiceState::Paid => Self::Paid,
+            InvoiceState::Overpaid => Self::Overpaid,
+            InvoiceState::Expired => Self::Expired,
+        }
+    }
+}
+
+/// A payment expected on a fresh address of the wallet.
+#[derive(Debug, Clone)]
+pub struct InvoiceInfo {
+    pub invoice_id: u64,
+    pub address: String,
+    /// In sompi.
+    pub amount: u64,
+    pub memo: Option<String>,
+    pub created_at_unix_millis: u64,
+    /// `None` if the invoice never expires.
+    pub expires_at_unix_millis: Option<u64>,
+    pub state: InvoiceState,
+    /// In sompi, including UTXOs spent since they were received.
+    pub received: u64,
+    /// `kaspa:` URI to hand to the payer.
+    pub payment_uri: String,
+}
+
+impl From<ProtoInvoice> for InvoiceInfo {
+    fn from(value: ProtoInvoice) -> Self {
+        Self {
+            state: value.state().into(),
+            invoice_id: value.invoice_id,
+            address: value.address,
+            amount: value.amount,
+            memo: (!value.memo.is_empty()).then_some(value.memo),
+            created_at_unix_millis: value.created_at_unix_millis,
+            expires_at_unix_millis: (value.expires_at_unix_millis > 0)
+                .then_some(value.expires_at_unix_millis),
+            received: value.received,
+            payment_uri: value.payment_uri,
+        }
+    }
+}
+
+/// A change of an invoice's state, from [`KaswalletClient::list_invoice_events`].
+#[derive(Debug, Clone)]
+pub struct InvoiceEventInfo {
+    pub sequence: u64,
+    pub invoice_id: u64,
+    pub previous_state: InvoiceState,
+    pub state: InvoiceState,
+    /// In sompi, at the time of the change.
+    pub received: u64,
+    pub at_unix_millis: u64,
+}
+
+impl From<ProtoInvoiceEvent> for InvoiceEventInfo {
+    fn from(value: ProtoInvoiceEvent) -> Self {
+        Self {
+            previous_state: value.previous_state().into(),
+            state: value.state().into(),
+            sequence: value.sequence,
+            invoice_id: value.invoice_id,
+            received: value.received,
+            at_unix_millis: value.at_unix_millis,
         }
     }
 }
@@ -86,6 +315,153 @@ impl From<ProtoAddressToUtxos> for AddressUtxos {
 pub struct SendResult {
     pub transaction_ids: Vec<Hash>,
     pub signed_transactions: Vec<WalletSignableTransaction>,
+    /// Confirmation status per transaction; empty unless confirmations were waited for.
+    pub statuses: Vec<TransactionStatusInfo>,
+    /// The idempotency key was already used: `transaction_ids` are those of the original
+    /// request and `signed_transactions` is empty.
+    pub idempotent_replay: bool,
+    /// The fee rate the transactions were built with; `None` on an idempotent replay.
+    pub applied_fee: Option<AppliedFeeInfo>,
+}
+
+/// The fee rate a transaction was built with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AppliedFeeInfo {
+    /// In sompi/gram.
+    pub fee_rate: f64,
+    /// Kaspad's expected time to acceptance at `fee_rate`.
+    pub estimated_seconds: f64,
+}
+
+impl From<ProtoAppliedFee> for AppliedFeeInfo {
+    fn from(value: ProtoAppliedFee) -> Self {
+        Self {
+            fee_rate: value.fee_rate,
+            estimated_seconds: value.estimated_seconds,
+        }
+    }
+}
+
+/// Where a wallet transaction stands on its way to acceptance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionState {
+    /// Not submitted through this daemon, or no longer retained.
+    Unknown,
+    Pending,
+    Accepted {
+        accepting_block_hash: Hash,
+        accepting_daa_score: u64,
+    },
+    Rejected {
+        reason: String,
+    },
+}
+
+/// Confirmation status of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatusInfo {
+    pub transaction_id: Hash,
+    pub state: TransactionState,
+    /// Virtual DAA score minus the accepting block's DAA score; 0 unless accepted.
+    pub confirmations: u64,
+}
+
+/// Result of a UTXO consolidation.
+#[derive(Debug, Clone)]
+pub struct ConsolidateResult {
+    /// In submission order; unsigned unless broadcast. Empty if there was nothing to consolidate.
+    pub transactions: Vec<WalletSignableTransaction>,
+    /// Set when the transactions were broadcast.
+    pub transaction_ids: Vec<Hash>,
+}
+
+/// Result of a dust sweep, from [`KaswalletClient::sweep_dust`].
+#[derive(Debug, Clone)]
+pub struct SweepDustResult {
+    pub dust_utxo_count: u32,
+    /// In sompi.
+    pub dust_value: u64,
+    /// In sompi; estimated if nothing was swept.
+    pub fee: u64,
+    /// Whether the fee is lower than `dust_value`. Nothing is swept otherwise.
+    pub is_economic: bool,
+    pub applied_fee: Option<AppliedFeeInfo>,
+    /// Empty for a dry run or when the sweep is not economic.
+    pub transaction_ids: Vec<Hash>,
+}
+
+/// Key material held outside the wallet, swept by [`KaswalletClient::sweep_external`].
+#[derive(Clone)]
+pub enum ExternalKeySource {
+    /// 64 hex characters or WIF.
+    PrivateKey(String),
+    Mnemonic {
+        phrase: String,
+        /// BIP39 passphrase, usually empty.
+        passphrase: String,
+        /// Account path; empty for m/44'/111111'/0'.
+        derivation_path: String,
+        /// Unused addresses scanned per keychain, at most 1000; 0 for the daemon default.
+        gap_limit: u32,
+    },
+}
+
+/// Result of an external sweep, from [`KaswalletClient::sweep_external`].
+#[derive(Debug, Clone)]
+pub struct SweepExternalResult {
+    /// The wallet address swept to.
+    pub to_address: String,
+    /// External addresses that held spendable UTXOs.
+    pub from_addresses: Vec<String>,
+    pub utxo_count: u32,
+    /// In sompi, before fees.
+    pub swept_value: u64,
+    /// In sompi.
+    pub fee: u64,
+    pub applied_fee: Option<AppliedFeeInfo>,
+    /// Empty for a dry run.
+    pub transaction_ids: Vec<Hash>,
+}
+
+/// How much a send-all would deliver, from [`KaswalletClient::get_max_sendable`].
+#[derive(Debug, Clone)]
+pub struct MaxSendableInfo {
+    /// In sompi, received by the recipient.
+    pub amount: u64,
+    /// In sompi, over all transactions.
+    pub fee: u64,
+    /// More than 1 if the inputs need merge transactions first.
+    pub transaction_count: u32,
+    pub applied_fee: Option<AppliedFeeInfo>,
+}
+
+/// Result of a fee bump.
+#[derive(Debug, Clone)]
+pub struct BumpFeeResult {
+    pub transaction_id: Hash,
+    pub replaced_transaction_id: Hash,
+    pub fee: u64,
+    pub signed_transaction: Option<WalletSignableTransaction>,
+}
+
+/// Result of a child-pays-for-parent acceleration.
+#[derive(Debug, Clone)]
+pub struct AccelerateResult {
+    pub transaction_id: Hash,
+    pub fee: u64,
+    pub signed_transaction: Option<WalletSignableTransaction>,
+}
+
+/// A wallet-generated transaction that is submitted but not yet accepted.
+#[derive(Debug, Clone)]
+pub struct PendingTransactionInfo {
+    pub transaction_id: Hash,
+    pub submitted_daa_score: u64,
+    pub fee: u64,
+    pub rebroadcast_attempts: u32,
+    /// `None` if the most recent rebroadcast (if any) succeeded.
+    pub last_rebroadcast_error: Option<String>,
+    pub transaction: Option<WalletSignableTransaction>,
 }
 
 /// Builder pattern for transaction operations with a more ergonomic API.
@@ -101,10 +477,15 @@ pub struct TransactionBuilder {
     utxos: Vec<Outpoint>,
     use_existing_change_address: bool,
     fee_policy: Option<FeePolicy>,
+    to_script_public_key: Option<ScriptPublicKey>,
+    to_redeem_script: Vec<u8>,
+    subtract_fee_from_amount: bool,
+    coin_selection_strategy: CoinSelectionStrategy,
+    subnetwork_id: Option<String>,
 }
 
 impl TransactionBuilder {
-    /// Create a new transaction builder with the destination address.
+    /// Create a new transaction builder with the destination address or contact alias.
     pub fn new(to_address: String) -> Self {
         Self {
             to_address,
@@ -115,6 +496,27 @@ impl TransactionBuilder {
             utxos: Vec::new(),
             use_existing_change_address: false,
             fee_policy: None,
+            to_script_public_key: None,
+            to_redeem_script: Vec::new(),
+            subtract_fee_from_amount: false,
+            coin_selection_strategy: CoinSelectionStrategy::Unspecified,
+            subnetwork_id: None,
+        }
+    }
+
+    /// Create a new transaction builder paying to a raw script public key.
+    pub fn new_to_script_public_key(script_public_key: ScriptPublicKey) -> Self {
+        Self {
+            to_script_public_key: Some(script_public_key),
+            ..Self::new(String::new())
+        }
+    }
+
+    /// Create a new transaction builder paying to the P2SH commitment of a redeem script.
+    pub fn new_to_redeem_script(redeem_script: Vec<u8>) -> Self {
+        Self {
+            to_redeem_script: redeem_script,
+            ..Self::new(String::new())
         }
     }
 
@@ -132,6 +534,12 @@ impl TransactionBuilder {
         self
     }
 
+    /// Deduct the fee from the amount so the recipient pays it (incompatible with send_all).
+    pub fn subtract_fee_from_amount(mut self, subtract_fee_from_amount: bool) -> Self {
+        self.subtract_fee_from_amount = subtract_fee_from_amount;
+        self
+    }
+
     /// Set the transaction payload.
     pub fn payload(mut self, payload: Vec<u8>) -> Self {
         self.payload = payload;
@@ -162,6 +570,18 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set how inputs are picked; unspecified uses the daemon's default strategy.
+    pub fn coin_selection_strategy(mut self, strategy: CoinSelectionStrategy) -> Self {
+        self.coin_selection_strategy = strategy;
+        self
+    }
+
+    /// Build on the given lane namespace (e.g. `97b10000`); it must be allowed by the daemon.
+    pub fn subnetwork_id(mut self, subnetwork_id: String) -> Self {
+        self.subnetwork_id = Some(subnetwork_id);
+        self
+    }
+
     pub fn transaction_description(&self) -> TransactionDescription {
         TransactionDescription {
             to_address: self.to_address.clone(),
@@ -172,6 +592,11 @@ impl TransactionBuilder {
             utxos: self.utxos.clone(),
             use_existing_change_address: self.use_existing_change_address,
             fee_policy: self.fee_policy,
+            to_script_public_key: self.to_script_public_key.clone(),
+            to_redeem_script: self.to_redeem_script.clone().into(),
+            subtract_fee_from_amount: self.subtract_fee_from_amount,
+            coin_selection_strategy: self.coin_selection_strategy.into(),
+            subnetwork_id: self.subnetwork_id.clone(),
         }
     }
 
@@ -0,0 +1,80 @@
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use kaspa_addresses::{Address, Prefix};
+use std::str::FromStr;
+
+pub use common::payment_uri::{PaymentUri, format_kas_amount, parse_kas_amount};
+
+/// Parse a `kaspa:<address>?amount=...&label=...&message=...` payment request, refusing one
+/// whose address is for a network other than `network_prefix`.
+pub fn parse_payment_uri(uri: &str, network_prefix: Prefix) -> WalletResult<PaymentUri> {
+    let payment_uri = PaymentUri::from_str(uri)?;
+    check_prefix(uri, &payment_uri.address, network_prefix)?;
+    Ok(payment_uri)
+}
+
+/// Build a payment request for `address` on the `network_prefix` network. `amount` is in sompi.
+pub fn build_payment_uri(
+    address: &str,
+    network_prefix: Prefix,
+    amount: Option<u64>,
+    label: Option<String>,
+    message: Option<String>,
+) -> WalletResult<PaymentUri> {
+    let parsed_address = Address::try_from(address).map_err(|e| {
+        WalletError::from(UserInputError::InvalidAddress {
+            input: address.to_string(),
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })
+    })?;
+    check_prefix(address, &parsed_address, network_prefix)?;
+    Ok(PaymentUri {
+        amount,
+        label: label.filter(|label| !label.is_empty()),
+        message: message.filter(|message| !message.is_empty()),
+        ..PaymentUri::new(parsed_address)
+    })
+}
+
+fn check_prefix(input: &str, address: &Address, network_prefix: Prefix) -> WalletResult<()> {
+    if address.prefix == network_prefix {
+        return Ok(());
+    }
+    Err(WalletError::from(UserInputError::InvalidPaymentUri {
+        input: input.to_string(),
+        reason: format!(
+            "address is for {}, expected an address for {network_prefix}",
+            address.prefix
+        ),
+        location: ErrorLocation::capture(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+
+    #[test]
+    fn network_prefix_is_checked() {
+        let address = Address::new(Prefix::Testnet, Version::PubKey, &[3u8; 32]).to_string();
+        let uri = format!("{address}?amount=2&label=shop");
+
+        let payment_uri = parse_payment_uri(&uri, Prefix::Testnet).unwrap();
+        assert_eq!(payment_uri.amount, Some(200_000_000));
+        assert_eq!(payment_uri.label.as_deref(), Some("shop"));
+        assert!(parse_payment_uri(&uri, Prefix::Mainnet).is_err());
+
+        let built = build_payment_uri(
+            &address,
+            Prefix::Testnet,
+            Some(200_000_000),
+            Some("shop".to_string()),
+            Some(String::new()),
+        )
+        .unwrap();
+        assert_eq!(built.to_string(), uri);
+        assert!(build_payment_uri(&address, Prefix::Mainnet, None, None, None).is_err());
+    }
+}
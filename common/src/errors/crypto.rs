@@ -66,6 +66,12 @@ pub enum CryptoError {
         location: ErrorLocation,
     },
 
+    #[error("{location} MessageSigningFailed: {reason}")]
+    MessageSigningFailed {
+        reason: String,
+        location: ErrorLocation,
+    },
+
     #[error("{location} PrefixMismatch: expected={expected:?}, got={got:?}")]
     PrefixMismatch {
         expected: Prefix,
@@ -94,6 +100,7 @@ impl CryptoError {
             Self::EncryptionFailed { .. } => "EncryptionFailed",
             Self::Bip32Derivation { .. } => "Bip32Derivation",
             Self::SignatureFailed { .. } => "SignatureFailed",
+            Self::MessageSigningFailed { .. } => "MessageSigningFailed",
             Self::PrefixMismatch { .. } => "PrefixMismatch",
             Self::ScriptError { .. } => "ScriptError",
         }
@@ -108,6 +115,7 @@ impl CryptoError {
             | Self::EncryptionFailed { location, .. }
             | Self::Bip32Derivation { location, .. }
             | Self::SignatureFailed { location, .. }
+            | Self::MessageSigningFailed { location, .. }
             | Self::PrefixMismatch { location, .. }
             | Self::ScriptError { location, .. } => *location,
         }
@@ -129,6 +137,9 @@ impl CryptoError {
                 reason,
                 ..
             } => format!("signature failed at input {input_index}: {reason}"),
+            Self::MessageSigningFailed { reason, .. } => {
+                format!("message signing failed: {reason}")
+            }
             Self::PrefixMismatch { expected, got, .. } => {
                 format!("address prefix mismatch: expected {expected:?}, got {got:?}")
             }
@@ -103,12 +103,14 @@ impl WalletError {
             Self::Transaction(e) => match e {
                 TransactionError::InsufficientFunds { .. }
                 | TransactionError::FeeTooLow { .. }
+                | TransactionError::FeeRateTooLow { .. }
                 | TransactionError::InvalidSignature { .. }
                 | TransactionError::DoubleSpend { .. }
                 | TransactionError::NotFullySigned { .. } => Code::InvalidArgument,
                 TransactionError::Rejected { .. } | TransactionError::Orphan { .. } => {
                     Code::Aborted
                 }
+                TransactionError::TransactionNotFound { .. } => Code::NotFound,
                 TransactionError::BuildFailed { .. }
                 | TransactionError::UtxoNotFound { .. }
                 | TransactionError::MassExceeded { .. }
@@ -95,6 +95,15 @@ fn to_status_mapping_table() {
             .into(),
             Code::InvalidArgument,
         ),
+        (
+            TransactionError::FeeRateTooLow {
+                provided_fee_rate: 0.5,
+                required_fee_rate: 1.0,
+                location: loc(),
+            }
+            .into(),
+            Code::InvalidArgument,
+        ),
         (
             TransactionError::InvalidSignature {
                 input_index: 0,
@@ -180,6 +189,16 @@ fn orphan_maps_to_aborted() {
     assert_eq!(err.to_status().code(), Code::Aborted);
 }
 
+#[test]
+fn transaction_not_found_maps_to_not_found() {
+    let err: WalletError = TransactionError::TransactionNotFound {
+        tx_id: kaspa_hashes::Hash::from_bytes([0; 32]),
+        location: loc(),
+    }
+    .into();
+    assert_eq!(err.to_status().code(), Code::NotFound);
+}
+
 #[test]
 fn double_spend_maps_to_invalid_argument() {
     use kaspa_consensus_core::tx::TransactionOutpoint;
@@ -79,6 +79,15 @@ pub enum TransactionError {
         location: ErrorLocation,
     },
 
+    #[error(
+        "{location} FeeRateTooLow: provided={provided_fee_rate} sompi/gram, required={required_fee_rate} sompi/gram"
+    )]
+    FeeRateTooLow {
+        provided_fee_rate: f64,
+        required_fee_rate: f64,
+        location: ErrorLocation,
+    },
+
     #[error("{location} Rejected: tx_id={tx_id}, node_message={node_message}")]
     Rejected {
         tx_id: TransactionId,
@@ -103,6 +112,15 @@ pub enum TransactionError {
         location: ErrorLocation,
     },
 
+    // The referenced transaction is not one the wallet is tracking (never
+    // submitted by this daemon, already accepted, or already dropped).
+    // Maps to `Code::NotFound`.
+    #[error("{location} TransactionNotFound: tx_id={tx_id}")]
+    TransactionNotFound {
+        tx_id: TransactionId,
+        location: ErrorLocation,
+    },
+
     #[error("{location} SubmitRpc: tx_id={tx_id}, source=({source})")]
     SubmitRpc {
         tx_id: TransactionId,
@@ -126,9 +144,11 @@ impl TransactionError {
             Self::SerializationFailed { .. } => "SerializationFailed",
             Self::MassExceeded { .. } => "MassExceeded",
             Self::FeeTooLow { .. } => "FeeTooLow",
+            Self::FeeRateTooLow { .. } => "FeeRateTooLow",
             Self::Rejected { .. } => "Rejected",
             Self::Orphan { .. } => "Orphan",
             Self::DoubleSpend { .. } => "DoubleSpend",
+            Self::TransactionNotFound { .. } => "TransactionNotFound",
             Self::SubmitRpc { .. } => "SubmitRpc",
         }
     }
@@ -145,9 +165,11 @@ impl TransactionError {
             | Self::SerializationFailed { location, .. }
             | Self::MassExceeded { location, .. }
             | Self::FeeTooLow { location, .. }
+            | Self::FeeRateTooLow { location, .. }
             | Self::Rejected { location, .. }
             | Self::Orphan { location, .. }
             | Self::DoubleSpend { location, .. }
+            | Self::TransactionNotFound { location, .. }
             | Self::SubmitRpc { location, .. } => *location,
         }
     }
@@ -188,6 +210,13 @@ impl TransactionError {
             } => format!(
                 "fee too low: provided {provided_sompi} sompi, required at least {required_sompi} sompi"
             ),
+            Self::FeeRateTooLow {
+                provided_fee_rate,
+                required_fee_rate,
+                ..
+            } => format!(
+                "fee rate too low: provided {provided_fee_rate} sompi/gram, required at least {required_fee_rate} sompi/gram"
+            ),
             Self::Rejected {
                 tx_id,
                 node_message,
@@ -202,6 +231,9 @@ impl TransactionError {
             Self::DoubleSpend { tx_id, .. } => {
                 format!("transaction {tx_id} attempts a double spend")
             }
+            Self::TransactionNotFound { tx_id, .. } => {
+                format!("transaction {tx_id} is not tracked by the wallet")
+            }
             Self::SubmitRpc { tx_id, source, .. } => {
                 format!(
                     "rpc submit failed for transaction {tx_id}: {}",
@@ -283,6 +315,17 @@ mod tests {
         };
     }
 
+    #[test]
+    fn fee_rate_too_low_reports_rates_not_sompi() {
+        let err = TransactionError::FeeRateTooLow {
+            provided_fee_rate: 0.5,
+            required_fee_rate: 1.0,
+            location: ErrorLocation::capture(),
+        };
+        assert!(err.user_message().contains("provided 0.5 sompi/gram"));
+        assert_eq!(err.kind_name(), "FeeRateTooLow");
+    }
+
     #[test]
     fn not_fully_signed_user_message_omits_index() {
         let err = TransactionError::NotFullySigned {
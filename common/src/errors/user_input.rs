@@ -16,6 +16,13 @@ pub enum UserInputError {
         location: ErrorLocation,
     },
 
+    #[error("{location} InvalidPaymentUri: input={input}, reason={reason}")]
+    InvalidPaymentUri {
+        input: String,
+        reason: String,
+        location: ErrorLocation,
+    },
+
     #[error("{location} InvalidTransactionId: input={input}")]
     InvalidTransactionId {
         input: String,
@@ -58,6 +65,7 @@ impl UserInputError {
         match self {
             Self::InvalidAddress { .. } => "InvalidAddress",
             Self::InvalidAmount { .. } => "InvalidAmount",
+            Self::InvalidPaymentUri { .. } => "InvalidPaymentUri",
             Self::InvalidTransactionId { .. } => "InvalidTransactionId",
             Self::InvalidPrefix { .. } => "InvalidPrefix",
             Self::MissingField { .. } => "MissingField",
@@ -71,6 +79,7 @@ impl UserInputError {
         match self {
             Self::InvalidAddress { location, .. }
             | Self::InvalidAmount { location, .. }
+            | Self::InvalidPaymentUri { location, .. }
             | Self::InvalidTransactionId { location, .. }
             | Self::InvalidPrefix { location, .. }
             | Self::MissingField { location, .. }
@@ -86,6 +95,9 @@ impl UserInputError {
                 format!("invalid address {input:?}: {reason}")
             }
             Self::InvalidAmount { input, .. } => format!("invalid amount {input:?}"),
+            Self::InvalidPaymentUri { input, reason, .. } => {
+                format!("invalid payment URI {input:?}: {reason}")
+            }
             Self::InvalidTransactionId { input, .. } => {
                 format!("invalid transaction id {input:?}")
             }
@@ -0,0 +1,110 @@
+use crate::error_location::ErrorLocation;
+use crate::errors::StorageError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current time as stored in the JSON stores' timestamps.
+pub fn unix_millis_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Read a JSON file written by [`write_json_file`]. Returns `None` if the file does not exist
+/// yet, so stores start out empty on a fresh wallet.
+pub fn read_json_file<T: DeserializeOwned>(
+    path: &str,
+    kind: &'static str,
+) -> Result<Option<T>, StorageError> {
+    let serialized = match fs::read_to_string(path) {
+        Ok(serialized) => serialized,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(StorageError::Io {
+                path: path.to_string(),
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            });
+        }
+    };
+    serde_json::from_str(&serialized)
+        .map(Some)
+        .map_err(|e| StorageError::Deserialize {
+            kind,
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })
+}
+
+/// Write `value` as pretty-printed JSON. The file is written to a temporary sibling first and
+/// renamed into place, so a crash mid-write leaves the previous contents intact.
+pub fn write_json_file<T: Serialize>(
+    path: &str,
+    kind: &'static str,
+    value: &T,
+) -> Result<(), StorageError> {
+    let serialized = serde_json::to_string_pretty(value).map_err(|e| StorageError::Serialize {
+        kind,
+        reason: e.to_string(),
+        location: ErrorLocation::capture(),
+    })?;
+
+    let io_error = |path: &Path, e: std::io::Error| StorageError::Io {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+        location: ErrorLocation::capture(),
+    };
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    let mut temporary_file_name = path.file_name().unwrap_or_default().to_os_string();
+    temporary_file_name.push(".tmp");
+    let temporary_path = path.with_file_name(temporary_file_name);
+
+    let mut file = fs::File::create(&temporary_path).map_err(|e| io_error(&temporary_path, e))?;
+    file.write_all(serialized.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| io_error(&temporary_path, e))?;
+    fs::rename(&temporary_path, path).map_err(|e| io_error(path, e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_json_file_returns_none_when_missing() {
+        let res: Option<Vec<u32>> =
+            read_json_file("/nonexistent/path/store.json", "store.json").unwrap();
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn write_then_read_round_trips() {
+        let dir = std::env::temp_dir().join("kaswallet-json-store-test");
+        let path = dir.join("store.json");
+        let path = path.to_str().unwrap();
+
+        write_json_file(path, "store.json", &vec![1u32, 2, 3]).unwrap();
+        let res: Option<Vec<u32>> = read_json_file(path, "store.json").unwrap();
+        assert_eq!(res, Some(vec![1, 2, 3]));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn read_json_file_rejects_malformed_contents() {
+        let path = std::env::temp_dir().join("kaswallet-json-store-malformed-test.json");
+        std::fs::write(&path, "not json").unwrap();
+        let err = read_json_file::<Vec<u32>>(path.to_str().unwrap(), "store.json").unwrap_err();
+        assert_eq!(err.kind_name(), "Deserialize", "got: {err}");
+        let _ = std::fs::remove_file(&path);
+    }
+}
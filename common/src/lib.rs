@@ -2,8 +2,10 @@ pub mod args;
 pub mod encrypted_mnemonic;
 pub mod error_location;
 pub mod errors;
+pub mod json_store;
 pub mod keys;
 pub mod model;
+pub mod payment_uri;
 pub mod proto_convert;
 pub mod status_classify;
 
@@ -1,5 +1,5 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use kaspa_addresses::Address;
+use kaspa_addresses::{Address, Prefix};
 use kaspa_bip32::DerivationPath;
 use kaspa_consensus_core::sign::Signed;
 use kaspa_consensus_core::tx::{
@@ -7,6 +7,9 @@ use kaspa_consensus_core::tx::{
 };
 use kaspa_hashes::Hash;
 use kaspa_rpc_core::{RpcTransactionOutpoint, RpcUtxoEntry};
+use kaspa_txscript::{
+    extract_script_pub_key_address, pay_to_address_script, pay_to_script_hash_script,
+};
 use std::fmt::{Display, Formatter};
 
 /// Wallet-side mirror of `kaspa_consensus_core::sign::Signed`.
@@ -183,7 +186,7 @@ impl From<WalletUtxoEntry> for UtxoEntry {
 // `From<UtxoEntry>` is intentionally kept infallible: every `UtxoEntry`
 // constructed inside this crate (proto round-trip, mempool replay) sets
 // `covenant_id: None`. The external risk vector — kaspad RPC supplying a
-// covenant-bound entry — flows through `RpcUtxoEntry` and is filtered at
+// covenant-bound entry — flows through `RpcUtxoEntry` and is diverted at
 // the sync boundary in `UtxoManager::update_utxo_set`.
 //
 // `is_unconfirmed` defaults to `false`. The two callers — proto round-trip
@@ -220,8 +223,8 @@ impl TryFrom<RpcUtxoEntry> for WalletUtxoEntry {
         // The wallet does not support spending covenant-bound UTXOs.
         // The fallible conversion forces every call site to handle (or
         // explicitly skip) covenant-bound entries; the runtime gate in
-        // `UtxoManager::update_utxo_set` filters them with a warn log
-        // before reaching here.
+        // `UtxoManager::update_utxo_set` diverts them into a separate
+        // covenant-bound view before reaching here.
         //
         // `RpcUtxoEntry` reaches us only through `get_utxos_by_addresses`,
         // which surfaces confirmed (block-included) UTXOs — kaspad's
@@ -241,6 +244,14 @@ impl TryFrom<RpcUtxoEntry> for WalletUtxoEntry {
     }
 }
 
+/// A UTXO on a wallet address that is bound to a covenant. The wallet cannot spend it, so it is
+/// kept out of coin selection and reported on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CovenantBoundUtxo {
+    pub utxo: WalletUtxo,
+    pub covenant_id: Hash,
+}
+
 /// True if an `RpcUtxoEntry` is spendable by this wallet — i.e. carries
 /// no covenant binding. Use this to filter at the kaspad RPC boundary
 /// (the only path covenant-bound entries can reach the wallet) before
@@ -270,13 +281,56 @@ impl WalletUtxo {
     }
 }
 
+/// Where a payment output sends its value.
+///
+/// Most outputs pay to a parsed `Address`, but contracts, HTLCs and
+/// covenant-related outputs need a raw `ScriptPublicKey` (or the P2SH
+/// commitment of a redeem script). `address` is the standard address form of
+/// `script_public_key` when one exists and `None` for non-standard scripts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentDestination {
+    pub script_public_key: ScriptPublicKey,
+    pub address: Option<Address>,
+}
+
+impl PaymentDestination {
+    pub fn from_address(address: Address) -> Self {
+        Self {
+            script_public_key: pay_to_address_script(&address),
+            address: Some(address),
+        }
+    }
+
+    /// Pay to a raw script public key. The address form is recovered when
+    /// the script is one of the standard classes (P2PK, P2PK-ECDSA, P2SH).
+    pub fn from_script_public_key(script_public_key: ScriptPublicKey, prefix: Prefix) -> Self {
+        let address = extract_script_pub_key_address(&script_public_key, prefix).ok();
+        Self {
+            script_public_key,
+            address,
+        }
+    }
+
+    /// Pay to the P2SH commitment of `redeem_script`.
+    pub fn from_redeem_script(redeem_script: &[u8], prefix: Prefix) -> Self {
+        Self::from_script_public_key(pay_to_script_hash_script(redeem_script), prefix)
+    }
+}
+
 pub struct WalletPayment {
-    pub address: Address,
+    pub destination: PaymentDestination,
     pub amount: u64,
 }
 impl WalletPayment {
     pub fn new(address: Address, amount: u64) -> Self {
-        Self { address, amount }
+        Self::to_destination(PaymentDestination::from_address(address), amount)
+    }
+
+    pub fn to_destination(destination: PaymentDestination, amount: u64) -> Self {
+        Self {
+            destination,
+            amount,
+        }
     }
 }
 
@@ -289,14 +343,15 @@ pub struct WalletSignableTransaction {
     pub transaction: WalletSigned,
     pub derivation_paths: Vec<DerivationPath>,
     pub address_by_input_index: Vec<WalletAddress>,
-    pub address_by_output_index: Vec<Address>,
+    /// `None` for outputs whose script has no address form.
+    pub address_by_output_index: Vec<Option<Address>>,
 }
 impl WalletSignableTransaction {
     pub fn new(
         transaction: WalletSigned,
         derivation_paths: Vec<DerivationPath>,
         address_by_input_index: Vec<WalletAddress>,
-        address_by_output_index: Vec<Address>,
+        address_by_output_index: Vec<Option<Address>>,
     ) -> Self {
         Self {
             transaction,
@@ -310,7 +365,7 @@ impl WalletSignableTransaction {
         transaction: SignableTransaction,
         derivation_paths: Vec<DerivationPath>,
         address_by_input_index: Vec<WalletAddress>,
-        address_by_output_index: Vec<Address>,
+        address_by_output_index: Vec<Option<Address>>,
     ) -> Self {
         Self {
             transaction: WalletSigned::Partially(transaction),
@@ -376,4 +431,24 @@ mod tests {
         assert_eq!(upstream.amount, 100);
         assert_eq!(upstream.covenant_id, None);
     }
+
+    #[test]
+    fn redeem_script_destination_carries_p2sh_address() {
+        let destination = PaymentDestination::from_redeem_script(&[0x51], Prefix::Mainnet);
+        let address = destination.address.expect("P2SH has an address form");
+        assert_eq!(address.version, kaspa_addresses::Version::ScriptHash);
+        assert_eq!(
+            destination.script_public_key,
+            pay_to_address_script(&address)
+        );
+    }
+
+    #[test]
+    fn non_standard_script_destination_has_no_address() {
+        let script_public_key = ScriptPublicKey::from_vec(0, vec![0x51, 0x51, 0x87]);
+        let destination =
+            PaymentDestination::from_script_public_key(script_public_key.clone(), Prefix::Mainnet);
+        assert_eq!(destination.address, None);
+        assert_eq!(destination.script_public_key, script_public_key);
+    }
 }
@@ -0,0 +1,228 @@
+use crate::error_location::ErrorLocation;
+use crate::errors::{UserInputError, WalletError, WalletResult};
+use kaspa_addresses::Address;
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A payment request for an address, in the BIP21 style Kaspa wallets use:
+/// `kaspa:<payload>?amount=<KAS>&label=<text>&message=<text>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUri {
+    pub address: Address,
+    /// In sompi.
+    pub amount: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+impl PaymentUri {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+        }
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = WalletError;
+
+    /// Parse a payment URI. The scheme is the address prefix, so a bare address is a valid URI.
+    /// Unknown parameters are ignored unless they start with `req-`, which marks parameters a
+    /// payer must understand.
+    fn from_str(input: &str) -> WalletResult<Self> {
+        let invalid = |reason: String| {
+            WalletError::from(UserInputError::InvalidPaymentUri {
+                input: input.to_string(),
+                reason,
+                location: ErrorLocation::capture(),
+            })
+        };
+
+        let uri = input.trim();
+        let (address, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let address =
+            Address::try_from(address).map_err(|e| invalid(format!("invalid address: {e}")))?;
+
+        let mut payment_uri = PaymentUri::new(address);
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let value = percent_decode(value)
+                .ok_or_else(|| invalid(format!("{name} is not valid percent-encoded UTF-8")))?;
+            let is_duplicate = match name {
+                "amount" => {
+                    let amount = parse_kas_amount(&value)
+                        .ok_or_else(|| invalid(format!("invalid amount {value:?}")))?;
+                    payment_uri.amount.replace(amount).is_some()
+                }
+                "label" => payment_uri.label.replace(value).is_some(),
+                "message" => payment_uri.message.replace(value).is_some(),
+                _ if name.starts_with("req-") => {
+                    return Err(invalid(format!("unsupported required parameter {name}")));
+                }
+                _ => false,
+            };
+            if is_duplicate {
+                return Err(invalid(format!("{name} is given more than once")));
+            }
+        }
+        Ok(payment_uri)
+    }
+}
+
+impl Display for PaymentUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.address)?;
+        let mut parameters = vec![];
+        if let Some(amount) = self.amount {
+            parameters.push(format!("amount={}", format_kas_amount(amount)));
+        }
+        if let Some(label) = &self.label {
+            parameters.push(format!("label={}", percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            parameters.push(format!("message={}", percent_encode(message)));
+        }
+        if !parameters.is_empty() {
+            write!(f, "?{}", parameters.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// `sompi` as a KAS decimal without trailing zeros, e.g. `1.5`.
+pub fn format_kas_amount(sompi: u64) -> String {
+    let whole = sompi / SOMPI_PER_KASPA;
+    let fraction = sompi % SOMPI_PER_KASPA;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{fraction:08}");
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+/// A KAS decimal such as `1.5` in sompi. `None` if it is malformed, has more than 8 decimals or
+/// overflows.
+pub fn parse_kas_amount(amount: &str) -> Option<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > 8
+    {
+        return None;
+    }
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: u64 = format!("{fraction:0<8}").parse().ok()?;
+    whole.checked_mul(SOMPI_PER_KASPA)?.checked_add(fraction)
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Undo percent-encoding. `None` if an escape is malformed or the result is not UTF-8.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Prefix, Version};
+
+    #[test]
+    fn formats_amounts_without_trailing_zeros() {
+        assert_eq!(format_kas_amount(0), "0");
+        assert_eq!(format_kas_amount(150_000_000), "1.5");
+        assert_eq!(format_kas_amount(1), "0.00000001");
+        assert_eq!(format_kas_amount(12_345_678_901), "123.45678901");
+    }
+
+    #[test]
+    fn builds_uri_with_encoded_parameters() {
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        assert_eq!(
+            PaymentUri::new(address.clone()).to_string(),
+            address.to_string()
+        );
+
+        let uri = PaymentUri {
+            amount: Some(150_000_000),
+            message: Some("Order #42 & co".to_string()),
+            ..PaymentUri::new(address.clone())
+        };
+        assert_eq!(
+            uri.to_string(),
+            format!("{address}?amount=1.5&message=Order%20%2342%20%26%20co")
+        );
+    }
+
+    #[test]
+    fn parses_what_it_builds() {
+        let address = Address::new(Prefix::Testnet, Version::PubKey, &[2u8; 32]);
+        let uri = PaymentUri {
+            amount: Some(12_345_678_901),
+            label: Some("Café".to_string()),
+            message: Some("a=b&c".to_string()),
+            ..PaymentUri::new(address.clone())
+        };
+        assert_eq!(PaymentUri::from_str(&uri.to_string()).unwrap(), uri);
+        assert_eq!(
+            PaymentUri::from_str(&address.to_string()).unwrap(),
+            PaymentUri::new(address)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_uris() {
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        for query in [
+            "amount=1.123456789",
+            "amount=-1",
+            "amount=",
+            "amount=1&amount=2",
+            "message=%ZZ",
+            "req-expires=1",
+        ] {
+            assert!(
+                PaymentUri::from_str(&format!("{address}?{query}")).is_err(),
+                "{query}"
+            );
+        }
+        assert!(PaymentUri::from_str("kaspa:notanaddress?amount=1").is_err());
+        // Parameters a payer may ignore are ignored.
+        let uri = PaymentUri::from_str(&format!("{address}?amount=.5&note=x")).unwrap();
+        assert_eq!(uri.amount, Some(50_000_000));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::error_location::ErrorLocation;
 use crate::errors::{UserInputError, WalletError, WalletResult};
 use crate::model::{
-    Keychain, WalletAddress, WalletOutpoint, WalletSignableTransaction, WalletUtxo, WalletUtxoEntry,
+    CovenantBoundUtxo, Keychain, WalletAddress, WalletOutpoint, WalletSignableTransaction,
+    WalletUtxo, WalletUtxoEntry,
 };
-use kaspa_addresses::Address;
+use kaspa_addresses::{Address, Version as AddressVersion};
 use kaspa_bip32::{ChildNumber, DerivationPath};
 use kaspa_consensus_core::sign::Signed;
 use kaspa_consensus_core::subnets::{SUBNETWORK_ID_SIZE, SubnetworkId};
@@ -13,13 +14,14 @@ use kaspa_consensus_core::tx::{
 };
 use kaspa_hashes::Hash;
 use proto::kaswallet_proto::{
-    DerivationPath as ProtoDerivationPath, Keychain as ProtoKeychain,
-    NonContextualMasses as ProtoNonContextualMasses, OptionalUtxoEntry as ProtoOptionalUtxoEntry,
-    Outpoint as ProtoOutpoint, ScriptPublicKey as ProtoScriptPublicKey,
-    SignableTransaction as ProtoSignableTransaction, SignedTransaction as ProtoSignedTransaction,
-    Transaction as ProtoTransaction, TransactionInput as ProtoTransactionInput,
-    TransactionOutpoint as ProtoTransactionOutpoint, TransactionOutput as ProtoTransactionOutput,
-    Utxo as ProtoUtxo, UtxoEntry as ProtoUtxoEntry, WalletAddress as ProtoWalletAddress,
+    AddressVersion as ProtoAddressVersion, DerivationPath as ProtoDerivationPath,
+    Keychain as ProtoKeychain, NonContextualMasses as ProtoNonContextualMasses,
+    OptionalUtxoEntry as ProtoOptionalUtxoEntry, Outpoint as ProtoOutpoint,
+    ScriptPublicKey as ProtoScriptPublicKey, SignableTransaction as ProtoSignableTransaction,
+    SignedTransaction as ProtoSignedTransaction, Transaction as ProtoTransaction,
+    TransactionInput as ProtoTransactionInput, TransactionOutpoint as ProtoTransactionOutpoint,
+    TransactionOutput as ProtoTransactionOutput, Utxo as ProtoUtxo, UtxoEntry as ProtoUtxoEntry,
+    WalletAddress as ProtoWalletAddress,
     WalletSignableTransaction as ProtoWalletSignableTransaction, signed_transaction,
 };
 use std::str::FromStr;
@@ -60,6 +62,16 @@ impl From<ProtoKeychain> for Keychain {
     }
 }
 
+impl From<AddressVersion> for ProtoAddressVersion {
+    fn from(value: AddressVersion) -> Self {
+        match value {
+            AddressVersion::PubKey => ProtoAddressVersion::PubKey,
+            AddressVersion::PubKeyECDSA => ProtoAddressVersion::PubKeyEcdsa,
+            AddressVersion::ScriptHash => ProtoAddressVersion::ScriptHash,
+        }
+    }
+}
+
 impl From<WalletAddress> for ProtoWalletAddress {
     fn from(value: WalletAddress) -> Self {
         ProtoWalletAddress {
@@ -187,6 +199,22 @@ impl WalletUtxo {
             utxo_entry: Some(self.utxo_entry.into()),
             is_pending,
             is_dust,
+            is_covenant_bound: false,
+            covenant_id: String::new(),
+        }
+    }
+}
+
+impl CovenantBoundUtxo {
+    /// Covenant-bound UTXOs are never spendable by the wallet, so dust does not apply.
+    pub fn into_proto(self, is_pending: bool) -> ProtoUtxo {
+        ProtoUtxo {
+            outpoint: Some(self.utxo.outpoint.into()),
+            utxo_entry: Some(self.utxo.utxo_entry.into()),
+            is_pending,
+            is_dust: false,
+            is_covenant_bound: true,
+            covenant_id: self.covenant_id.to_string(),
         }
     }
 }
@@ -506,10 +534,11 @@ impl From<WalletSignableTransaction> for ProtoWalletSignableTransaction {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            // Outputs without an address form travel as the empty string.
             address_by_output_index: value
                 .address_by_output_index
                 .into_iter()
-                .map(|addr| addr.to_string())
+                .map(|addr| addr.map(|addr| addr.to_string()).unwrap_or_default())
                 .collect(),
         }
     }
@@ -525,7 +554,10 @@ impl TryFrom<ProtoWalletSignableTransaction> for WalletSignableTransaction {
             .address_by_output_index
             .into_iter()
             .map(|s| {
-                Address::try_from(s.as_str()).map_err(|e| {
+                if s.is_empty() {
+                    return Ok(None);
+                }
+                Address::try_from(s.as_str()).map(Some).map_err(|e| {
                     WalletError::from(UserInputError::InvalidAddress {
                         input: s,
                         reason: e.to_string(),
@@ -731,4 +763,23 @@ mod tests {
             Err(other) => panic!("expected InvalidArgument, got: {other}"),
         }
     }
+
+    #[test]
+    fn address_less_output_roundtrips_as_empty_string() {
+        use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+        let output = TransactionOutput::new(1, ScriptPublicKey::from_vec(0, vec![0x51]));
+        let tx = Transaction::new(0, vec![], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let wallet_tx = WalletSignableTransaction::new_from_unsigned(
+            SignableTransaction::with_entries(tx, vec![]),
+            vec![],
+            vec![],
+            vec![None],
+        );
+
+        let proto: ProtoWalletSignableTransaction = wallet_tx.into();
+        assert_eq!(proto.address_by_output_index, vec![String::new()]);
+
+        let restored = WalletSignableTransaction::try_from(proto).expect("valid roundtrip");
+        assert_eq!(restored.address_by_output_index, vec![None]);
+    }
 }
@@ -175,6 +175,22 @@ pub fn classify_kaspad_rpc_error(
     }
 }
 
+/// Whether a `get_mempool_entry` failure for `transaction_id` is kaspad answering that it does
+/// not hold the transaction, as opposed to the call itself failing (timeout, transport error,
+/// kaspad restarting). Over gRPC the typed variant arrives only as its message, possibly behind
+/// a remote-error prefix, so that exact message is matched as well.
+pub fn is_mempool_entry_not_found(
+    err: &kaspa_rpc_core::RpcError,
+    transaction_id: TransactionId,
+) -> bool {
+    if matches!(err, kaspa_rpc_core::RpcError::TransactionNotFound(_)) {
+        return true;
+    }
+    let not_found_message =
+        kaspa_rpc_core::RpcError::TransactionNotFound(transaction_id).to_string();
+    err.to_string().ends_with(&not_found_message)
+}
+
 /// Classify a `submit_transaction` failure that came from the in-process
 /// kaspa-rpc-core client (so we have a typed `RpcError`, not a `Status`). We
 /// pattern-match the message against the same orphan/double-spend/rejection
@@ -295,6 +311,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transaction_not_found_message_is_pinned() {
+        // `is_mempool_entry_not_found` relies on this wording when the error crosses gRPC.
+        let transaction_id = TransactionId::from_bytes([7u8; 32]);
+        assert_eq!(
+            kaspa_rpc_core::RpcError::TransactionNotFound(transaction_id).to_string(),
+            format!("Transaction {transaction_id} not found")
+        );
+    }
+
+    #[test]
+    fn only_not_found_answers_mean_missing_from_mempool() {
+        let transaction_id = TransactionId::from_bytes([7u8; 32]);
+        assert!(is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::TransactionNotFound(transaction_id),
+            transaction_id
+        ));
+        assert!(is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::General(format!("Transaction {transaction_id} not found")),
+            transaction_id
+        ));
+        assert!(is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::General(format!(
+                "RPC Server (remote error) -> Transaction {transaction_id} not found"
+            )),
+            transaction_id
+        ));
+        assert!(!is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::General(format!(
+                "Transaction {} not found",
+                TransactionId::default()
+            )),
+            transaction_id
+        ));
+        assert!(!is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::General("block not found".to_string()),
+            transaction_id
+        ));
+        assert!(!is_mempool_entry_not_found(
+            &kaspa_rpc_core::RpcError::General("connection reset by peer".to_string()),
+            transaction_id
+        ));
+    }
+
     #[test]
     fn sanitises_control_characters_in_node_message() {
         // ANSI escape + bell + tab — none should reach logs verbatim.
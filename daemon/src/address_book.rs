@@ -0,0 +1,268 @@
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::json_store::{read_json_file, unix_millis_now, write_json_file};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const ADDRESS_BOOK_FILE_KIND: &str = "address_book.json";
+
+/// Longest accepted contact alias, in characters.
+pub const MAX_ALIAS_LENGTH: usize = 64;
+
+/// An external address saved under an alias.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contact {
+    pub address: String,
+    pub note: String,
+    pub created_at_unix_millis: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LabelJson {
+    address: String,
+    label: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContactJson {
+    alias: String,
+    address: String,
+    note: String,
+    created_at_unix_millis: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct AddressBookJson {
+    labels: Vec<LabelJson>,
+    contacts: Vec<ContactJson>,
+}
+
+/// Aliases are restricted to ASCII letters, digits, `-`, `_` and `.`, so none of them can be
+/// mistaken for an address, which always contains a `:`.
+pub fn validate_alias(alias: &str) -> WalletResult<()> {
+    let reason = if alias.is_empty() {
+        "alias must not be empty".to_string()
+    } else if alias.chars().count() > MAX_ALIAS_LENGTH {
+        format!("alias must be at most {MAX_ALIAS_LENGTH} characters")
+    } else if !alias
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        format!("alias {alias} may only contain ASCII letters, digits, '-', '_' and '.'")
+    } else {
+        return Ok(());
+    };
+    Err(WalletError::from(UserInputError::InvalidArgument {
+        reason,
+        location: ErrorLocation::capture(),
+    }))
+}
+
+/// Labels on the wallet's own addresses and aliases of external ones, persisted next to the keys
+/// file. Addresses are stored as strings; callers validate them.
+#[derive(Debug)]
+pub struct AddressBookStore {
+    file_path: Option<String>,
+    labels: BTreeMap<String, String>,
+    contacts: BTreeMap<String, Contact>,
+}
+
+impl AddressBookStore {
+    pub fn load(file_path: &str) -> WalletResult<Self> {
+        let address_book: AddressBookJson =
+            read_json_file(file_path, ADDRESS_BOOK_FILE_KIND)?.unwrap_or_default();
+        Ok(Self {
+            file_path: Some(file_path.to_string()),
+            labels: address_book
+                .labels
+                .into_iter()
+                .map(|entry| (entry.address, entry.label))
+                .collect(),
+            contacts: address_book
+                .contacts
+                .into_iter()
+                .map(|entry| {
+                    (
+                        entry.alias,
+                        Contact {
+                            address: entry.address,
+                            note: entry.note,
+                            created_at_unix_millis: entry.created_at_unix_millis,
+                        },
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// A store that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            file_path: None,
+            labels: BTreeMap::new(),
+            contacts: BTreeMap::new(),
+        }
+    }
+
+    pub fn label(&self, address: &str) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// All labels, keyed by address.
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// Label `address`, replacing any previous label. An empty label removes it.
+    pub fn set_label(&mut self, address: &str, label: &str) -> WalletResult<()> {
+        let label = label.trim();
+        let changed = if label.is_empty() {
+            self.labels.remove(address).is_some()
+        } else {
+            self.labels.insert(address.to_string(), label.to_string()) != Some(label.to_string())
+        };
+        if changed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn contact(&self, alias: &str) -> Option<&Contact> {
+        self.contacts.get(alias)
+    }
+
+    /// All contacts, keyed by alias.
+    pub fn contacts(&self) -> &BTreeMap<String, Contact> {
+        &self.contacts
+    }
+
+    /// Save `address` under `alias`. Re-adding an alias with the same address updates its note;
+    /// an alias is never silently repointed at a different address, since payments resolve it.
+    pub fn add_contact(&mut self, alias: &str, address: &str, note: &str) -> WalletResult<Contact> {
+        validate_alias(alias)?;
+        if let Some(existing) = self.contacts.get(alias) {
+            if existing.address != address {
+                return Err(WalletError::from(UserInputError::InvalidArgument {
+                    reason: format!(
+                        "alias {alias} already names {}; remove it first",
+                        existing.address
+                    ),
+                    location: ErrorLocation::capture(),
+                }));
+            }
+        }
+        let contact = self
+            .contacts
+            .entry(alias.to_string())
+            .and_modify(|contact| contact.note = note.to_string())
+            .or_insert_with(|| Contact {
+                address: address.to_string(),
+                note: note.to_string(),
+                created_at_unix_millis: unix_millis_now(),
+            })
+            .clone();
+        self.save()?;
+        Ok(contact)
+    }
+
+    /// Remove the contact saved under `alias`, returning it if there was one.
+    pub fn remove_contact(&mut self, alias: &str) -> WalletResult<Option<Contact>> {
+        let removed = self.contacts.remove(alias);
+        if removed.is_some() {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    fn save(&self) -> WalletResult<()> {
+        let Some(file_path) = &self.file_path else {
+            return Ok(());
+        };
+        let address_book = AddressBookJson {
+            labels: self
+                .labels
+                .iter()
+                .map(|(address, label)| LabelJson {
+                    address: address.clone(),
+                    label: label.clone(),
+                })
+                .collect(),
+            contacts: self
+                .contacts
+                .iter()
+                .map(|(alias, contact)| ContactJson {
+                    alias: alias.clone(),
+                    address: contact.address.clone(),
+                    note: contact.note.clone(),
+                    created_at_unix_millis: contact.created_at_unix_millis,
+                })
+                .collect(),
+        };
+        write_json_file(file_path, ADDRESS_BOOK_FILE_KIND, &address_book)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The store does not parse addresses.
+    const ADDRESS: &str = "kaspa:first";
+    const OTHER_ADDRESS: &str = "kaspa:second";
+
+    #[test]
+    fn labels_and_contacts_persist_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("address_book.json");
+        let path = path.to_str().unwrap();
+
+        let mut store = AddressBookStore::load(path).unwrap();
+        store.set_label(ADDRESS, " cold storage ").unwrap();
+        store
+            .add_contact("exchange", OTHER_ADDRESS, "deposits")
+            .unwrap();
+
+        let reloaded = AddressBookStore::load(path).unwrap();
+        assert_eq!(reloaded.label(ADDRESS), Some("cold storage"));
+        let contact = reloaded.contact("exchange").unwrap();
+        assert_eq!(contact.address, OTHER_ADDRESS);
+        assert_eq!(contact.note, "deposits");
+    }
+
+    #[test]
+    fn empty_label_removes_it() {
+        let mut store = AddressBookStore::in_memory();
+        store.set_label(ADDRESS, "savings").unwrap();
+        store.set_label(ADDRESS, "").unwrap();
+        assert_eq!(store.label(ADDRESS), None);
+        assert!(store.labels().is_empty());
+    }
+
+    #[test]
+    fn contact_alias_is_not_repointed() {
+        let mut store = AddressBookStore::in_memory();
+        let created_at = store
+            .add_contact("exchange", ADDRESS, "")
+            .unwrap()
+            .created_at_unix_millis;
+
+        let updated = store.add_contact("exchange", ADDRESS, "new note").unwrap();
+        assert_eq!(updated.note, "new note");
+        assert_eq!(updated.created_at_unix_millis, created_at);
+        assert!(store.add_contact("exchange", OTHER_ADDRESS, "").is_err());
+
+        assert!(store.remove_contact("exchange").unwrap().is_some());
+        assert!(store.remove_contact("exchange").unwrap().is_none());
+        store.add_contact("exchange", OTHER_ADDRESS, "").unwrap();
+    }
+
+    #[test]
+    fn aliases_cannot_look_like_addresses() {
+        assert!(validate_alias("alice.cold-1").is_ok());
+        assert!(validate_alias("").is_err());
+        assert!(validate_alias(ADDRESS).is_err());
+        assert!(validate_alias("two words").is_err());
+        assert!(validate_alias(&"a".repeat(MAX_ALIAS_LENGTH + 1)).is_err());
+    }
+}
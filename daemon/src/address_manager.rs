@@ -1,13 +1,14 @@
+use crate::used_addresses::UsedAddressStore;
 use common::addresses::{multisig_address, p2pk_address};
 use common::error_location::ErrorLocation;
 use common::errors::{CryptoError, WalletResult};
-use common::keys::Keys;
+use common::keys::{Keys, master_key_path};
 use common::model::{KEYCHAINS, Keychain, WalletAddress};
 use kaspa_addresses::{Address, Prefix as AddressPrefix};
 use kaspa_bip32::secp256k1::PublicKey;
 use kaspa_bip32::{DerivationPath, ExtendedPublicKey};
 use kaspa_rpc_core::RpcBalancesByAddressesEntry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::Ordering::Relaxed;
@@ -19,6 +20,7 @@ pub struct AddressManager {
     keys_file: Arc<Keys>,
     extended_public_keys: Arc<Vec<ExtendedPublicKey<PublicKey>>>,
     addresses: Mutex<AddressSet>,
+    used_addresses: Mutex<UsedAddressStore>,
     is_multisig: bool,
     prefix: AddressPrefix,
 
@@ -26,13 +28,14 @@ pub struct AddressManager {
 }
 
 impl AddressManager {
-    pub fn new(keys: Arc<Keys>, prefix: AddressPrefix) -> Self {
+    pub fn new(keys: Arc<Keys>, prefix: AddressPrefix, used_addresses: UsedAddressStore) -> Self {
         let is_multisig = keys.public_keys.len() > 1;
 
         Self {
             keys_file: keys.clone(),
             extended_public_keys: Arc::new(keys.public_keys.clone()),
             addresses: Mutex::new(HashMap::new()),
+            used_addresses: Mutex::new(used_addresses),
             is_multisig,
             prefix,
             address_cache: Mutex::new(HashMap::new()),
@@ -45,6 +48,12 @@ impl AddressManager {
         address.cloned()
     }
 
+    /// Every address kaspad has ever reported a balance for. Unlike holding UTXOs, an address
+    /// stays used once it is spent from.
+    pub async fn used_addresses(&self) -> HashSet<WalletAddress> {
+        self.used_addresses.lock().await.used_addresses()
+    }
+
     pub async fn address_set(&self) -> AddressSet {
         let addresses = self.addresses.lock().await;
         addresses.clone()
@@ -61,30 +70,51 @@ impl AddressManager {
     }
 
     pub async fn new_address(&self) -> WalletResult<(String, WalletAddress)> {
+        let mut new_addresses = self.new_addresses(1).await?;
+        Ok(new_addresses.remove(0))
+    }
+
+    /// Issue `count` consecutive external addresses, persisting the new last used index once.
+    pub async fn new_addresses(&self, count: u32) -> WalletResult<Vec<(String, WalletAddress)>> {
         let last_used_external_index_previous_value = self
             .keys_file
             .last_used_external_index
-            .fetch_add(1, Relaxed);
-        let last_used_external_index = last_used_external_index_previous_value + 1;
+            .fetch_add(count, Relaxed);
         self.keys_file.save()?;
 
+        let mut new_addresses = vec![];
+        for index in last_used_external_index_previous_value + 1
+            ..=last_used_external_index_previous_value + count
+        {
+            let wallet_address =
+                WalletAddress::new(index, self.keys_file.cosigner_index, Keychain::External);
+            let address = self
+                .kaspa_address_from_wallet_address(&wallet_address, true)
+                .await?;
+            new_addresses.push((address.to_string(), wallet_address));
+        }
+
+        {
+            let mut addresses = self.addresses.lock().await;
+            for (address_string, wallet_address) in &new_addresses {
+                addresses.insert(address_string.clone(), wallet_address.clone());
+            }
+        }
+
+        Ok(new_addresses)
+    }
+
+    /// The address the next `new_address` call returns, without deriving or persisting it.
+    pub async fn peek_new_address(&self) -> WalletResult<(Address, WalletAddress)> {
         let wallet_address = WalletAddress::new(
-            last_used_external_index,
+            self.keys_file.last_used_external_index.load(Relaxed) + 1,
             self.keys_file.cosigner_index,
             Keychain::External,
         );
         let address = self
             .kaspa_address_from_wallet_address(&wallet_address, true)
             .await?;
-
-        {
-            self.addresses
-                .lock()
-                .await
-                .insert(address.to_string(), wallet_address.clone());
-        }
-
-        Ok((address.to_string(), wallet_address))
+        Ok((address, wallet_address))
     }
 
     pub async fn addresses_to_query(&self, start: u32, end: u32) -> WalletResult<AddressSet> {
@@ -110,6 +140,7 @@ impl AddressManager {
         mut address_set: AddressSet,
         get_balances_by_addresses_response: Vec<RpcBalancesByAddressesEntry>,
     ) -> WalletResult<()> {
+        let mut used_addresses = vec![];
         // create scope to release last_used_internal/external_index before keys_file.save() is called
         {
             for entry in get_balances_by_addresses_response {
@@ -135,6 +166,7 @@ impl AddressManager {
                         .store(wallet_address.index, Relaxed);
                 }
 
+                used_addresses.push(wallet_address.clone());
                 self.addresses
                     .lock()
                     .await
@@ -142,6 +174,7 @@ impl AddressManager {
             }
         }
 
+        self.used_addresses.lock().await.mark_used(used_addresses)?;
         self.keys_file.save()?;
 
         Ok(())
@@ -208,6 +241,23 @@ impl AddressManager {
         Ok(path)
     }
 
+    /// Path of `wallet_address` from the master key: the account path followed by the path
+    /// `calculate_address_path` returns relative to it.
+    pub fn calculate_full_address_path(
+        &self,
+        wallet_address: &WalletAddress,
+    ) -> WalletResult<DerivationPath> {
+        let mut path = master_key_path(self.is_multisig);
+        for child_number in self.calculate_address_path(wallet_address)?.as_ref() {
+            path.push(*child_number);
+        }
+        Ok(path)
+    }
+
+    pub fn prefix(&self) -> AddressPrefix {
+        self.prefix
+    }
+
     fn p2pk_address(&self, derivation_path: &DerivationPath) -> WalletResult<Address> {
         p2pk_address(
             self.extended_public_keys.first().unwrap(),
@@ -263,4 +313,87 @@ impl AddressManager {
 
         Ok((address, wallet_address))
     }
+
+    /// The address `change_address(false, from_addresses)` would return next, without deriving
+    /// it or persisting anything. Used to build transactions that are never returned.
+    pub async fn peek_change_address(
+        &self,
+        from_addresses: &[&WalletAddress],
+    ) -> WalletResult<(Address, WalletAddress)> {
+        let wallet_address = if !from_addresses.is_empty() {
+            from_addresses[0].clone()
+        } else {
+            WalletAddress::new(
+                self.keys_file.last_used_internal_index.load(Relaxed) + 1,
+                self.keys_file.cosigner_index,
+                Keychain::Internal,
+            )
+        };
+        let address = self
+            .kaspa_address_from_wallet_address(&wallet_address, true)
+            .await?;
+        Ok((address, wallet_address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external_keys::mnemonic_account_key;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                          abandon abandon abandon about";
+
+    fn make_address_manager(keys_path: &str, last_used_external_index: u32) -> AddressManager {
+        let account_key = mnemonic_account_key(PHRASE, "", "").unwrap();
+        let keys = Keys::new(
+            keys_path.to_string(),
+            1,
+            vec![],
+            kaspa_bip32::Prefix::KPUB,
+            vec![account_key.public_key()],
+            last_used_external_index,
+            0,
+            1,
+            0,
+        );
+        AddressManager::new(
+            Arc::new(keys),
+            AddressPrefix::Devnet,
+            UsedAddressStore::in_memory(),
+        )
+    }
+
+    #[tokio::test]
+    async fn new_addresses_issues_a_consecutive_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys_path = dir.path().join("keys.json");
+        let address_manager = make_address_manager(keys_path.to_str().unwrap(), 4);
+
+        let batch = address_manager.new_addresses(3).await.unwrap();
+        let indexes: Vec<u32> = batch
+            .iter()
+            .map(|(_, wallet_address)| wallet_address.index)
+            .collect();
+        assert_eq!(indexes, vec![5, 6, 7]);
+        for (address_string, wallet_address) in &batch {
+            assert_eq!(wallet_address.keychain, Keychain::External);
+            assert_eq!(
+                address_manager
+                    .wallet_address_from_string(address_string)
+                    .await
+                    .as_ref(),
+                Some(wallet_address)
+            );
+        }
+        let distinct_addresses: HashSet<&String> = batch
+            .iter()
+            .map(|(address_string, _)| address_string)
+            .collect();
+        assert_eq!(distinct_addresses.len(), 3);
+
+        // The next issuance continues after the batch.
+        let (_, next) = address_manager.new_address().await.unwrap();
+        assert_eq!(next.index, 8);
+    }
 }
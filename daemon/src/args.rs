@@ -1,3 +1,4 @@
+use crate::coin_selection::CoinSelectionStrategy;
 use clap::{Parser, ValueEnum};
 use common::args::parse_network_type;
 use kaspa_consensus_core::network::NetworkId;
@@ -125,6 +126,15 @@ pub struct Args {
     )]
     pub subnetwork_id: Option<SubnetworkId>,
 
+    #[arg(
+        long = "allowed-subnetwork-id",
+        value_parser = parse_subnetwork_id_arg,
+        help = "Additional subnetwork ID (same form as --subnetwork-id) that requests may \
+                select per transaction. Repeat for several lanes. --subnetwork-id stays \
+                the default lane and is always allowed."
+    )]
+    pub allowed_subnetwork_ids: Vec<SubnetworkId>,
+
     #[arg(long, help = "Enable tokio console")]
     #[cfg(debug_assertions)]
     pub enable_tokio_console: bool,
@@ -136,6 +146,70 @@ pub struct Args {
         hide = true
     )]
     pub sync_interval_millis: u64,
+
+    #[arg(
+        long,
+        default_value = "36000",
+        help = "Drop a tracked wallet transaction once kaspad has not had it in its mempool \
+                for this many DAA score units since a sync first found it missing, restoring \
+                its inputs. 0 disables expiry."
+    )]
+    pub mempool_transaction_expiry_daa_window: u64,
+
+    #[arg(
+        long,
+        default_value = "30000",
+        help = "Interval in milliseconds at which wallet transactions dropped from kaspad's \
+                mempool are rebroadcast. 0 disables rebroadcast."
+    )]
+    pub rebroadcast_interval_millis: u64,
+
+    #[arg(
+        long,
+        default_value = "120000",
+        help = "How long in milliseconds the inputs of a transaction returned by \
+                CreateUnsignedTransactions stay reserved for it, unless it is broadcast or \
+                released first. 0 disables reservations."
+    )]
+    pub utxo_reservation_ttl_millis: u64,
+
+    #[arg(
+        long,
+        default_value = "86400",
+        help = "How long in seconds the result of a Send or Broadcast request is kept for its \
+                idempotency key. A retry with the same key within this window returns the \
+                original transactions instead of paying again."
+    )]
+    pub idempotency_key_retention_secs: u64,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "smallest-first",
+        help = "How inputs are picked from the wallet's UTXOs when a request does not choose a \
+                strategy itself"
+    )]
+    pub coin_selection_strategy: CoinSelectionStrategy,
+
+    #[arg(
+        long,
+        help = "UTXOs worth less than this many sompi are dust. Overrides --dust-fee-multiple."
+    )]
+    pub dust_threshold_sompi: Option<u64>,
+
+    #[arg(
+        long,
+        default_value = "1.0",
+        help = "UTXOs worth at most this multiple of the fee to spend them at the normal fee \
+                rate are dust"
+    )]
+    pub dust_fee_multiple: f64,
+
+    #[arg(
+        long,
+        help = "Leave dust out of the available and pending balances and report it separately"
+    )]
+    pub hide_dust_from_balance: bool,
 }
 
 impl Default for Args {
@@ -152,9 +226,18 @@ impl Default for Args {
             server: None,
             listen: "".to_string(),
             subnetwork_id: None,
+            allowed_subnetwork_ids: vec![],
             #[cfg(debug_assertions)]
             enable_tokio_console: false,
             sync_interval_millis: 10,
+            mempool_transaction_expiry_daa_window: 36000,
+            rebroadcast_interval_millis: 30000,
+            utxo_reservation_ttl_millis: 120000,
+            idempotency_key_retention_secs: 86400,
+            coin_selection_strategy: CoinSelectionStrategy::default(),
+            dust_threshold_sompi: None,
+            dust_fee_multiple: 1.0,
+            hide_dust_from_balance: false,
         }
     }
 }
@@ -228,6 +311,21 @@ mod tests {
         let args =
             Args::try_parse_from(["kaswallet-daemon"]).expect("clap should parse with no flags");
         assert!(args.subnetwork_id.is_none(), "default must be None");
+        assert!(args.allowed_subnetwork_ids.is_empty());
+    }
+
+    #[test]
+    fn args_allowed_subnetwork_id_flag_is_repeatable() {
+        let args = Args::try_parse_from([
+            "kaswallet-daemon",
+            "--allowed-subnetwork-id",
+            IGRA_LANE_NAMESPACE_HEX,
+            "--allowed-subnetwork-id",
+            NATIVE_NAMESPACE_HEX,
+        ])
+        .expect("clap should parse repeated allowed-subnetwork-id flags");
+        assert_eq!(args.allowed_subnetwork_ids.len(), 2);
+        assert!(args.allowed_subnetwork_ids[1].is_native());
     }
 
     #[test]
@@ -0,0 +1,563 @@
+use clap::ValueEnum;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::model::{WalletAddress, WalletUtxo};
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use proto::kaswallet_proto::CoinSelectionStrategy as ProtoCoinSelectionStrategy;
+use std::cmp::min;
+use std::collections::HashMap;
+
+// The minimal change amount to target in order to avoid large storage mass (see KIP9 for more details).
+// By having at least 10KAS in the change output we make sure that the storage mass charged for change is
+// at most 1000 gram. Generally, if the payment is above 10KAS as well, the resulting storage mass will be
+// in the order of magnitude of compute mass and will not incur additional charges.
+// Additionally, every transaction with send value > ~0.1 KAS should succeed (at most ~99K storage mass for payment
+// output, thus overall lower than standard mass upper bound which is 100K gram)
+const MIN_CHANGE_TARGET: u64 = SOMPI_PER_KASPA * 10;
+
+// Upper bound on the branch-and-bound search steps, so a large wallet cannot stall a request.
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+
+/// What a selection has to cover.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionTarget {
+    pub amount: u64,
+    /// When the recipient pays the fee, it comes out of `amount` instead of on top of it.
+    pub subtract_fee_from_amount: bool,
+    pub fee_per_input: u64,
+    /// Cap on the whole fee, including any excess a changeless selection gives to the fee.
+    pub max_fee: u64,
+}
+
+impl SelectionTarget {
+    /// The most a changeless selection of `input_count` inputs may exceed the spend by, the
+    /// excess going to the fee: up to one input's fee, keeping the whole fee within `max_fee`.
+    /// None at all when the recipient pays the fee, as the wallet would pay the excess.
+    fn changeless_excess_limit(&self, input_count: usize) -> u64 {
+        if self.subtract_fee_from_amount {
+            return 0;
+        }
+        let fee = min(
+            self.fee_per_input.saturating_mul(input_count as u64),
+            self.max_fee,
+        );
+        min(self.fee_per_input, self.max_fee - fee)
+    }
+
+    /// Total input value needed when spending `input_count` inputs.
+    pub fn spend(&self, input_count: usize) -> u64 {
+        if self.subtract_fee_from_amount {
+            self.amount
+        } else {
+            self.amount + self.fee_per_input * input_count as u64
+        }
+    }
+
+    /// The value `utxo` contributes towards `amount` once its own fee is paid.
+    fn effective_value(&self, utxo: &WalletUtxo) -> u64 {
+        if self.subtract_fee_from_amount {
+            utxo.utxo_entry.amount
+        } else {
+            utxo.utxo_entry.amount.saturating_sub(self.fee_per_input)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CoinSelection {
+    pub utxos: Vec<WalletUtxo>,
+    /// The inputs match the spend closely enough that the excess goes to the fee instead of
+    /// a change output.
+    pub changeless: bool,
+}
+
+impl CoinSelection {
+    fn with_change(utxos: Vec<WalletUtxo>) -> Self {
+        Self {
+            utxos,
+            changeless: false,
+        }
+    }
+
+    pub fn total_value(&self) -> u64 {
+        self.utxos.iter().map(|utxo| utxo.utxo_entry.amount).sum()
+    }
+}
+
+/// Picks the inputs of a payment from the spendable UTXOs.
+pub trait CoinSelector: Send + Sync {
+    /// `candidates` are spendable and sorted by ascending amount. Returns as many UTXOs as it
+    /// could gather if they do not cover the target; the caller reports insufficient funds.
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection;
+}
+
+/// The built-in coin selectors, selectable per request and as the daemon default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum CoinSelectionStrategy {
+    #[default]
+    SmallestFirst,
+    LargestFirst,
+    BranchAndBound,
+    OldestFirst,
+    AvoidAddressMixing,
+}
+
+impl CoinSelectionStrategy {
+    /// The strategy requested in a `TransactionDescription`, or `None` if it was left unspecified.
+    pub fn from_proto(value: i32) -> WalletResult<Option<Self>> {
+        let strategy = ProtoCoinSelectionStrategy::try_from(value).map_err(|_| {
+            WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("unknown coin selection strategy {value}"),
+                location: ErrorLocation::capture(),
+            })
+        })?;
+        Ok(match strategy {
+            ProtoCoinSelectionStrategy::Unspecified => None,
+            ProtoCoinSelectionStrategy::SmallestFirst => Some(Self::SmallestFirst),
+            ProtoCoinSelectionStrategy::LargestFirst => Some(Self::LargestFirst),
+            ProtoCoinSelectionStrategy::BranchAndBound => Some(Self::BranchAndBound),
+            ProtoCoinSelectionStrategy::OldestFirst => Some(Self::OldestFirst),
+            ProtoCoinSelectionStrategy::AvoidAddressMixing => Some(Self::AvoidAddressMixing),
+        })
+    }
+
+    pub fn selector(self) -> &'static dyn CoinSelector {
+        match self {
+            Self::SmallestFirst => &SmallestFirst,
+            Self::LargestFirst => &LargestFirst,
+            Self::BranchAndBound => &BranchAndBound,
+            Self::OldestFirst => &OldestFirst,
+            Self::AvoidAddressMixing => &AvoidAddressMixing,
+        }
+    }
+}
+
+/// Accumulate `utxos` in order until the target is covered. Two break cases:
+///     1. total == spend, so there's no change needed -> number of outputs = 1, so a single input is sufficient
+///     2. total > spend, so there will be change and 2 outputs, therefore in order to not struggle with --
+///        2.1 go-nodes dust patch we try and find at least 2 inputs (even though the next one is not necessary
+///        in terms of spend value) when `min_inputs_with_change` is 2
+///        2.2 KIP9 we try and make sure that the change amount is not too small
+fn accumulate<'a>(
+    utxos: impl IntoIterator<Item = &'a WalletUtxo>,
+    target: &SelectionTarget,
+    min_inputs_with_change: usize,
+) -> Vec<WalletUtxo> {
+    let mut selected = vec![];
+    let mut total_value = 0;
+    for utxo in utxos {
+        selected.push(utxo.clone());
+        total_value += utxo.utxo_entry.amount;
+        let total_spend = target.spend(selected.len());
+        if total_value == total_spend {
+            break;
+        }
+        if total_value >= total_spend + MIN_CHANGE_TARGET
+            && selected.len() >= min_inputs_with_change
+        {
+            break;
+        }
+    }
+    selected
+}
+
+/// The original kaswallet strategy: smallest UTXOs first, sweeping dust as a side effect.
+pub struct SmallestFirst;
+
+impl CoinSelector for SmallestFirst {
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection {
+        CoinSelection::with_change(accumulate(candidates, target, 2))
+    }
+}
+
+/// Largest UTXOs first, keeping the input count (and fee) as low as possible.
+pub struct LargestFirst;
+
+impl CoinSelector for LargestFirst {
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection {
+        CoinSelection::with_change(accumulate(candidates.iter().rev(), target, 1))
+    }
+}
+
+/// Oldest UTXOs first by `block_daa_score`, so coins do not sit unspent indefinitely.
+pub struct OldestFirst;
+
+impl CoinSelector for OldestFirst {
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection {
+        let mut by_age: Vec<&WalletUtxo> = candidates.iter().collect();
+        by_age.sort_by_key(|utxo| utxo.utxo_entry.block_daa_score);
+        CoinSelection::with_change(accumulate(by_age, target, 2))
+    }
+}
+
+/// Searches for a set of inputs that pays the target with no change output, allowing up to one
+/// input's fee of excess (which goes to the fee) within `max_fee`, and no excess when the
+/// recipient pays the fee. Falls back to [`SmallestFirst`] when no such set exists or the target
+/// is zero.
+pub struct BranchAndBound;
+
+impl CoinSelector for BranchAndBound {
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection {
+        match branch_and_bound(candidates, target) {
+            Some(utxos) => CoinSelection {
+                utxos,
+                changeless: true,
+            },
+            None => SmallestFirst.select(candidates, target),
+        }
+    }
+}
+
+fn branch_and_bound(
+    candidates: &[WalletUtxo],
+    target: &SelectionTarget,
+) -> Option<Vec<WalletUtxo>> {
+    // The empty set would "match" a zero target exactly, which is no selection at all.
+    if target.amount == 0 {
+        return None;
+    }
+    let mut pool: Vec<(u64, &WalletUtxo)> = candidates
+        .iter()
+        .map(|utxo| (target.effective_value(utxo), utxo))
+        .filter(|(effective_value, _)| *effective_value > 0)
+        .collect();
+    pool.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let lower_bound = target.amount;
+    let upper_bound = target
+        .amount
+        .saturating_add(target.changeless_excess_limit(0));
+    let mut remaining_value: u64 = pool
+        .iter()
+        .map(|(effective_value, _)| effective_value)
+        .sum();
+    let mut current_value = 0;
+    let mut included: Vec<usize> = vec![];
+    let mut position = 0;
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    // Depth-first over include/exclude decisions, largest values first. `remaining_value` is the
+    // sum of the undecided positions.
+    for _ in 0..BRANCH_AND_BOUND_MAX_TRIES {
+        let backtrack =
+            if current_value + remaining_value < lower_bound || current_value > upper_bound {
+                true
+            } else if current_value >= lower_bound {
+                let excess = current_value - lower_bound;
+                if excess <= target.changeless_excess_limit(included.len())
+                    && best
+                        .as_ref()
+                        .is_none_or(|(best_excess, _)| excess < *best_excess)
+                {
+                    best = Some((excess, included.clone()));
+                }
+                if excess == 0 {
+                    break;
+                }
+                true
+            } else {
+                false
+            };
+
+        if backtrack {
+            // Exclude the most recently included position; the positions after it become
+            // undecided again.
+            let Some(last) = included.pop() else {
+                break;
+            };
+            remaining_value += pool[last + 1..position]
+                .iter()
+                .map(|(effective_value, _)| effective_value)
+                .sum::<u64>();
+            current_value -= pool[last].0;
+            position = last + 1;
+            continue;
+        }
+
+        remaining_value -= pool[position].0;
+        current_value += pool[position].0;
+        included.push(position);
+        position += 1;
+    }
+
+    best.map(|(_, included)| {
+        included
+            .into_iter()
+            .map(|position| pool[position].1.clone())
+            .collect()
+    })
+}
+
+/// Spends from as few addresses as possible, so a payment does not link the wallet's addresses
+/// together on-chain. Prefers the smallest single address that covers the target; otherwise
+/// spends whole addresses, largest first, until the target is covered.
+pub struct AvoidAddressMixing;
+
+impl CoinSelector for AvoidAddressMixing {
+    fn select(&self, candidates: &[WalletUtxo], target: &SelectionTarget) -> CoinSelection {
+        let mut group_index_by_address: HashMap<&WalletAddress, usize> = HashMap::new();
+        let mut groups: Vec<Vec<&WalletUtxo>> = vec![];
+        for utxo in candidates {
+            let group_index = *group_index_by_address
+                .entry(&utxo.address)
+                .or_insert_with(|| {
+                    groups.push(vec![]);
+                    groups.len() - 1
+                });
+            groups[group_index].push(utxo);
+        }
+        let group_value = |group: &[&WalletUtxo]| -> u64 {
+            group.iter().map(|utxo| utxo.utxo_entry.amount).sum()
+        };
+
+        // Among the addresses that can pay alone, spend from the one holding the least, so the
+        // payment reveals as little of the wallet's balance as possible.
+        let single_address = groups
+            .iter()
+            .map(|group| {
+                (
+                    group_value(group),
+                    accumulate(group.iter().copied(), target, 2),
+                )
+            })
+            .filter(|(_, selected)| {
+                let total_value: u64 = selected.iter().map(|utxo| utxo.utxo_entry.amount).sum();
+                total_value >= target.spend(selected.len())
+            })
+            .min_by_key(|(value, _)| *value)
+            .map(|(_, selected)| selected);
+        if let Some(selected) = single_address {
+            return CoinSelection::with_change(selected);
+        }
+
+        groups.sort_by_key(|group| std::cmp::Reverse(group_value(group)));
+        let mut selected = vec![];
+        let mut total_value = 0;
+        for group in groups {
+            if total_value >= target.spend(selected.len()) {
+                break;
+            }
+            total_value += group_value(&group);
+            selected.extend(group.into_iter().cloned());
+        }
+        CoinSelection::with_change(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::model::{Keychain, WalletOutpoint, WalletUtxoEntry};
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+
+    const FEE_PER_INPUT: u64 = 1_000;
+
+    fn utxo(id: u8, amount: u64, address_index: u32, block_daa_score: u64) -> WalletUtxo {
+        WalletUtxo::new(
+            WalletOutpoint::new(kaspa_hashes::Hash::from_bytes([id; 32]), 0),
+            WalletUtxoEntry::new(
+                amount,
+                ScriptPublicKey::new(0, vec![].into()),
+                block_daa_score,
+                false,
+                false,
+            ),
+            WalletAddress::new(address_index, 0, Keychain::External),
+        )
+    }
+
+    fn sorted_by_amount(mut utxos: Vec<WalletUtxo>) -> Vec<WalletUtxo> {
+        utxos.sort_by_key(|utxo| utxo.utxo_entry.amount);
+        utxos
+    }
+
+    fn target(amount: u64) -> SelectionTarget {
+        SelectionTarget {
+            amount,
+            subtract_fee_from_amount: false,
+            fee_per_input: FEE_PER_INPUT,
+            max_fee: SOMPI_PER_KASPA,
+        }
+    }
+
+    fn amounts(selection: &CoinSelection) -> Vec<u64> {
+        selection
+            .utxos
+            .iter()
+            .map(|utxo| utxo.utxo_entry.amount)
+            .collect()
+    }
+
+    #[test]
+    fn smallest_first_takes_a_second_input_for_change() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 100 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 200 * SOMPI_PER_KASPA, 0, 0),
+            utxo(3, 300 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let selection = SmallestFirst.select(&candidates, &target(50 * SOMPI_PER_KASPA));
+        assert_eq!(
+            amounts(&selection),
+            vec![100 * SOMPI_PER_KASPA, 200 * SOMPI_PER_KASPA]
+        );
+        assert!(!selection.changeless);
+    }
+
+    #[test]
+    fn largest_first_uses_a_single_large_input() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 100 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 200 * SOMPI_PER_KASPA, 0, 0),
+            utxo(3, 300 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let selection = LargestFirst.select(&candidates, &target(50 * SOMPI_PER_KASPA));
+        assert_eq!(amounts(&selection), vec![300 * SOMPI_PER_KASPA]);
+    }
+
+    #[test]
+    fn oldest_first_orders_by_block_daa_score() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 100 * SOMPI_PER_KASPA, 0, 30),
+            utxo(2, 200 * SOMPI_PER_KASPA, 0, 10),
+            utxo(3, 300 * SOMPI_PER_KASPA, 0, 20),
+        ]);
+        let selection = OldestFirst.select(&candidates, &target(50 * SOMPI_PER_KASPA));
+        assert_eq!(
+            amounts(&selection),
+            vec![200 * SOMPI_PER_KASPA, 300 * SOMPI_PER_KASPA]
+        );
+    }
+
+    #[test]
+    fn branch_and_bound_finds_a_changeless_match() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 3 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 5 * SOMPI_PER_KASPA + FEE_PER_INPUT, 0, 0),
+            utxo(3, 7 * SOMPI_PER_KASPA + FEE_PER_INPUT, 0, 0),
+            utxo(4, 11 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let target = target(12 * SOMPI_PER_KASPA);
+        let selection = BranchAndBound.select(&candidates, &target);
+        assert!(selection.changeless);
+        let mut selected = amounts(&selection);
+        selected.sort();
+        assert_eq!(
+            selected,
+            vec![
+                5 * SOMPI_PER_KASPA + FEE_PER_INPUT,
+                7 * SOMPI_PER_KASPA + FEE_PER_INPUT
+            ]
+        );
+        assert_eq!(selection.total_value(), target.spend(2));
+    }
+
+    #[test]
+    fn branch_and_bound_does_not_give_excess_to_the_fee_when_the_recipient_pays_it() {
+        // Matches 12 KAS within one input's fee, but not exactly.
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 5 * SOMPI_PER_KASPA + 400, 0, 0),
+            utxo(2, 7 * SOMPI_PER_KASPA, 0, 0),
+            utxo(3, 30 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let recipient_pays = SelectionTarget {
+            subtract_fee_from_amount: true,
+            ..target(12 * SOMPI_PER_KASPA)
+        };
+        assert!(
+            !BranchAndBound
+                .select(&candidates, &recipient_pays)
+                .changeless
+        );
+
+        // An exact match costs the wallet nothing extra.
+        let exact = sorted_by_amount(vec![
+            utxo(1, 5 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 7 * SOMPI_PER_KASPA, 0, 0),
+            utxo(3, 30 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let selection = BranchAndBound.select(&exact, &recipient_pays);
+        assert!(selection.changeless);
+        assert_eq!(selection.total_value(), 12 * SOMPI_PER_KASPA);
+    }
+
+    #[test]
+    fn branch_and_bound_keeps_the_excess_within_max_fee() {
+        // Two inputs cost 2_000 in fees and leave 400 of excess.
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 5 * SOMPI_PER_KASPA + FEE_PER_INPUT + 400, 0, 0),
+            utxo(2, 7 * SOMPI_PER_KASPA + FEE_PER_INPUT, 0, 0),
+            utxo(3, 30 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let within_cap = SelectionTarget {
+            max_fee: 2 * FEE_PER_INPUT + 400,
+            ..target(12 * SOMPI_PER_KASPA)
+        };
+        assert!(BranchAndBound.select(&candidates, &within_cap).changeless);
+
+        let over_cap = SelectionTarget {
+            max_fee: 2 * FEE_PER_INPUT + 399,
+            ..target(12 * SOMPI_PER_KASPA)
+        };
+        assert!(!BranchAndBound.select(&candidates, &over_cap).changeless);
+    }
+
+    #[test]
+    fn branch_and_bound_falls_back_to_smallest_first() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 100 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 200 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let selection = BranchAndBound.select(&candidates, &target(50 * SOMPI_PER_KASPA));
+        assert!(!selection.changeless);
+        assert_eq!(
+            amounts(&selection),
+            vec![100 * SOMPI_PER_KASPA, 200 * SOMPI_PER_KASPA]
+        );
+    }
+
+    #[test]
+    fn branch_and_bound_does_not_match_a_zero_target_with_nothing() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 100 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 200 * SOMPI_PER_KASPA, 0, 0),
+        ]);
+        let selection = BranchAndBound.select(&candidates, &target(0));
+        assert!(!selection.changeless);
+        assert_eq!(
+            amounts(&selection),
+            amounts(&SmallestFirst.select(&candidates, &target(0)))
+        );
+        assert!(!selection.utxos.is_empty());
+    }
+
+    #[test]
+    fn avoid_address_mixing_prefers_the_smallest_sufficient_address() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 30 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 40 * SOMPI_PER_KASPA, 1, 0),
+            utxo(3, 100 * SOMPI_PER_KASPA, 2, 0),
+            utxo(4, 500 * SOMPI_PER_KASPA, 3, 0),
+        ]);
+        let selection = AvoidAddressMixing.select(&candidates, &target(50 * SOMPI_PER_KASPA));
+        assert_eq!(amounts(&selection), vec![100 * SOMPI_PER_KASPA]);
+    }
+
+    #[test]
+    fn avoid_address_mixing_spends_whole_addresses_when_none_suffices() {
+        let candidates = sorted_by_amount(vec![
+            utxo(1, 10 * SOMPI_PER_KASPA, 0, 0),
+            utxo(2, 20 * SOMPI_PER_KASPA, 1, 0),
+            utxo(3, 25 * SOMPI_PER_KASPA, 1, 0),
+            utxo(4, 30 * SOMPI_PER_KASPA, 2, 0),
+        ]);
+        let selection = AvoidAddressMixing.select(&candidates, &target(60 * SOMPI_PER_KASPA));
+        assert_eq!(
+            amounts(&selection),
+            vec![
+                20 * SOMPI_PER_KASPA,
+                25 * SOMPI_PER_KASPA,
+                30 * SOMPI_PER_KASPA
+            ]
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use crate::kaspad_client::is_in_mempool;
+use crate::utxo_manager::UtxoManager;
+use common::error_location::ErrorLocation;
+use common::errors::{RpcError, WalletResult};
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_rpc_core::RpcHash;
+use kaspa_wallet_core::rpc::RpcApi;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, interval, timeout_at};
+use tracing::{debug, info, warn};
+
+/// Consecutive polls a pending transaction must be missing from both kaspad's mempool and the
+/// wallet's mempool tracking before it is reported as rejected. One poll is not enough: an
+/// acceptance may land between the chain poll and the mempool check.
+const MISSING_POLLS_BEFORE_REJECTED: u32 = 2;
+
+/// How long (in DAA score units) settled transactions stay queryable. ~1 day at 10 BPS.
+const SETTLED_RETENTION_DAA_SCORE: u64 = 864_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationState {
+    Pending,
+    Accepted {
+        accepting_block_hash: RpcHash,
+        accepting_daa_score: u64,
+    },
+    Rejected {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionConfirmation {
+    pub state: ConfirmationState,
+    /// Virtual DAA score minus the accepting block's DAA score; 0 unless accepted.
+    pub confirmations: u64,
+}
+
+impl TransactionConfirmation {
+    /// True once waiting for `depth` confirmations can stop: the transaction is rejected, or
+    /// accepted at least `depth` DAA score units deep.
+    pub fn is_settled(&self, depth: u64) -> bool {
+        match self.state {
+            ConfirmationState::Pending => false,
+            ConfirmationState::Accepted { .. } => self.confirmations >= depth,
+            ConfirmationState::Rejected { .. } => true,
+        }
+    }
+}
+
+struct TrackedTransaction {
+    state: ConfirmationState,
+    missing_polls: u32,
+    last_update_daa_score: u64,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    transactions: HashMap<TransactionId, TrackedTransaction>,
+    // Last virtual selected chain block processed; `None` until the first poll.
+    chain_tip: Option<RpcHash>,
+    virtual_daa_score: u64,
+}
+
+/// Follows wallet-submitted transactions to acceptance by walking the virtual selected chain.
+pub struct ConfirmationTracker {
+    kaspa_client: Arc<GrpcClient>,
+    utxo_manager: Arc<Mutex<UtxoManager>>,
+
+    poll_interval_millis: u64,
+    state: Mutex<TrackerState>,
+    updated: Notify,
+}
+
+impl ConfirmationTracker {
+    pub fn new(
+        kaspa_client: Arc<GrpcClient>,
+        utxo_manager: Arc<Mutex<UtxoManager>>,
+        poll_interval_millis: u64,
+    ) -> Self {
+        Self {
+            kaspa_client,
+            utxo_manager,
+            poll_interval_millis,
+            state: Mutex::new(TrackerState::default()),
+            updated: Notify::new(),
+        }
+    }
+
+    pub fn start(confirmation_tracker: Arc<ConfirmationTracker>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(core::time::Duration::from_millis(
+                confirmation_tracker.poll_interval_millis,
+            ));
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = confirmation_tracker.poll().await {
+                    warn!("Confirmation tracking poll failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Start following a transaction that was just submitted to kaspad.
+    pub async fn track(&self, transaction_id: TransactionId) {
+        let mut state = self.state.lock().await;
+        let virtual_daa_score = state.virtual_daa_score;
+        state
+            .transactions
+            .entry(transaction_id)
+            .or_insert(TrackedTransaction {
+                state: ConfirmationState::Pending,
+                missing_polls: 0,
+                last_update_daa_score: virtual_daa_score,
+            });
+    }
+
+    /// Settle a tracked transaction as rejected, e.g. because it was replaced or abandoned.
+    pub async fn mark_rejected(&self, transaction_id: &TransactionId, reason: String) {
+        let mut state = self.state.lock().await;
+        let virtual_daa_score = state.virtual_daa_score;
+        if let Some(tracked) = state.transactions.get_mut(transaction_id) {
+            tracked.state = ConfirmationState::Rejected { reason };
+            tracked.last_update_daa_score = virtual_daa_score;
+        }
+        drop(state);
+        self.updated.notify_waiters();
+    }
+
+    /// Current status of each id; `None` for ids this tracker does not know.
+    pub async fn statuses(
+        &self,
+        transaction_ids: &[TransactionId],
+    ) -> Vec<Option<TransactionConfirmation>> {
+        let state = self.state.lock().await;
+        transaction_ids
+            .iter()
+            .map(|transaction_id| {
+                state.transactions.get(transaction_id).map(|tracked| {
+                    let confirmations = match tracked.state {
+                        ConfirmationState::Accepted {
+                            accepting_daa_score,
+                            ..
+                        } => state.virtual_daa_score.saturating_sub(accepting_daa_score),
+                        _ => 0,
+                    };
+                    TransactionConfirmation {
+                        state: tracked.state.clone(),
+                        confirmations,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Wait until every id is settled at `depth` confirmations (see
+    /// [`TransactionConfirmation::is_settled`]) or `deadline` passes, then return the statuses.
+    /// Unknown ids never become settled, so they do not hold up the wait.
+    pub async fn wait_for_confirmations(
+        &self,
+        transaction_ids: &[TransactionId],
+        depth: u64,
+        deadline: Instant,
+    ) -> Vec<Option<TransactionConfirmation>> {
+        loop {
+            // Register interest before reading, so an update between the read and the wait is
+            // not missed.
+            let updated = self.updated.notified();
+            let statuses = self.statuses(transaction_ids).await;
+            let is_settled = statuses.iter().all(|status| {
+                status
+                    .as_ref()
+                    .is_none_or(|status| status.is_settled(depth))
+            });
+            if is_settled {
+                return statuses;
+            }
+            if timeout_at(deadline, updated).await.is_err() {
+                return self.statuses(transaction_ids).await;
+            }
+        }
+    }
+
+    async fn poll(&self) -> WalletResult<()> {
+        let block_dag_info =
+            self.kaspa_client
+                .get_block_dag_info()
+                .await
+                .map_err(|e| RpcError::Transport {
+                    reason: e.to_string(),
+                    location: ErrorLocation::capture(),
+                })?;
+
+        let chain_tip = {
+            let mut state = self.state.lock().await;
+            state.virtual_daa_score = block_dag_info.virtual_daa_score;
+            state.chain_tip
+        };
+        match chain_tip {
+            None => {
+                // Transactions are only tracked from now on, so walking the chain can start at
+                // the current sink.
+                let mut state = self.state.lock().await;
+                state.chain_tip = Some(block_dag_info.sink);
+            }
+            Some(chain_tip) => self.process_virtual_chain(chain_tip).await?,
+        }
+
+        self.check_pending_transactions().await?;
+        self.prune().await;
+        self.updated.notify_waiters();
+        Ok(())
+    }
+
+    async fn process_virtual_chain(&self, chain_tip: RpcHash) -> WalletResult<()> {
+        let virtual_chain = self
+            .kaspa_client
+            .get_virtual_chain_from_block(chain_tip, true, None)
+            .await
+            .map_err(|e| RpcError::Transport {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            })?;
+
+        let removed: HashSet<RpcHash> = virtual_chain
+            .removed_chain_block_hashes
+            .iter()
+            .copied()
+            .collect();
+
+        // Resolve accepting DAA scores without holding the state lock across RPCs.
+        let tracked_ids: HashSet<TransactionId> = {
+            let state = self.state.lock().await;
+            state.transactions.keys().copied().collect()
+        };
+        let mut acceptances = vec![];
+        for accepted in &virtual_chain.accepted_transaction_ids {
+            let accepted_ids: Vec<TransactionId> = accepted
+                .accepted_transaction_ids
+                .iter()
+                .filter(|transaction_id| tracked_ids.contains(transaction_id))
+                .copied()
+                .collect();
+            if accepted_ids.is_empty() {
+                continue;
+            }
+            let accepting_block = self
+                .kaspa_client
+                .get_block(accepted.accepting_block_hash, false)
+                .await
+                .map_err(|e| RpcError::Transport {
+                    reason: e.to_string(),
+                    location: ErrorLocation::capture(),
+                })?;
+            acceptances.push((
+                accepted.accepting_block_hash,
+                accepting_block.header.daa_score,
+                accepted_ids,
+            ));
+        }
+
+        let mut state = self.state.lock().await;
+        let virtual_daa_score = state.virtual_daa_score;
+        if !removed.is_empty() {
+            for (transaction_id, tracked) in state.transactions.iter_mut() {
+                if let ConfirmationState::Accepted {
+                    accepting_block_hash,
+                    ..
+                } = &tracked.state
+                {
+                    if removed.contains(accepting_block_hash) {
+                        info!(
+                            tx_id = %transaction_id,
+                            "accepting block left the selected chain; transaction is pending again"
+                        );
+                        tracked.state = ConfirmationState::Pending;
+                        tracked.missing_polls = 0;
+                        tracked.last_update_daa_score = virtual_daa_score;
+                    }
+                }
+            }
+        }
+        for (accepting_block_hash, accepting_daa_score, accepted_ids) in acceptances {
+            for transaction_id in accepted_ids {
+                if let Some(tracked) = state.transactions.get_mut(&transaction_id) {
+                    debug!(
+                        tx_id = %transaction_id,
+                        accepting_block_hash = %accepting_block_hash,
+                        accepting_daa_score,
+                        "transaction accepted"
+                    );
+                    tracked.state = ConfirmationState::Accepted {
+                        accepting_block_hash,
+                        accepting_daa_score,
+                    };
+                    tracked.last_update_daa_score = virtual_daa_score;
+                }
+            }
+        }
+        if let Some(last_added) = virtual_chain.added_chain_block_hashes.last() {
+            state.chain_tip = Some(*last_added);
+        }
+        Ok(())
+    }
+
+    /// Reject pending transactions that neither kaspad nor the wallet's mempool tracking holds
+    /// any more, and which were not accepted. A failed mempool lookup aborts the check, so an
+    /// unreachable kaspad does not count as the transactions going missing.
+    async fn check_pending_transactions(&self) -> WalletResult<()> {
+        let pending_ids: Vec<TransactionId> = {
+            let state = self.state.lock().await;
+            state
+                .transactions
+                .iter()
+                .filter(|(_, tracked)| tracked.state == ConfirmationState::Pending)
+                .map(|(transaction_id, _)| *transaction_id)
+                .collect()
+        };
+        if pending_ids.is_empty() {
+            return Ok(());
+        }
+        // Never hold the UTXO manager and the tracker state together: submit paths take them
+        // in the opposite order.
+        let wallet_tracked_ids: HashSet<TransactionId> = {
+            let utxo_manager = self.utxo_manager.lock().await;
+            utxo_manager
+                .mempool_transactions()
+                .iter()
+                .map(|tracked_transaction| tracked_transaction.id())
+                .collect()
+        };
+
+        let mut missing = HashSet::new();
+        for transaction_id in &pending_ids {
+            if wallet_tracked_ids.contains(transaction_id) {
+                continue;
+            }
+            if !is_in_mempool(&self.kaspa_client, *transaction_id).await? {
+                missing.insert(*transaction_id);
+            }
+        }
+
+        let mut state = self.state.lock().await;
+        let virtual_daa_score = state.virtual_daa_score;
+        for transaction_id in pending_ids {
+            let Some(tracked) = state.transactions.get_mut(&transaction_id) else {
+                continue;
+            };
+            if tracked.state != ConfirmationState::Pending {
+                continue;
+            }
+            if !missing.contains(&transaction_id) {
+                tracked.missing_polls = 0;
+                continue;
+            }
+            tracked.missing_polls += 1;
+            if tracked.missing_polls >= MISSING_POLLS_BEFORE_REJECTED {
+                warn!(
+                    tx_id = %transaction_id,
+                    "transaction dropped without being accepted"
+                );
+                tracked.state = ConfirmationState::Rejected {
+                    reason: "dropped from kaspad's mempool without being accepted".to_string(),
+                };
+                tracked.last_update_daa_score = virtual_daa_score;
+            }
+        }
+        Ok(())
+    }
+
+    async fn prune(&self) {
+        let mut state = self.state.lock().await;
+        let virtual_daa_score = state.virtual_daa_score;
+        state.transactions.retain(|_, tracked| {
+            tracked.state == ConfirmationState::Pending
+                || tracked.last_update_daa_score + SETTLED_RETENTION_DAA_SCORE > virtual_daa_score
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmation(state: ConfirmationState, confirmations: u64) -> TransactionConfirmation {
+        TransactionConfirmation {
+            state,
+            confirmations,
+        }
+    }
+
+    fn accepted() -> ConfirmationState {
+        ConfirmationState::Accepted {
+            accepting_block_hash: RpcHash::from_bytes([1u8; 32]),
+            accepting_daa_score: 100,
+        }
+    }
+
+    #[test]
+    fn is_settled_waits_for_depth() {
+        assert!(!confirmation(accepted(), 9).is_settled(10));
+        assert!(confirmation(accepted(), 10).is_settled(10));
+    }
+
+    #[test]
+    fn is_settled_zero_depth_means_accepted() {
+        assert!(confirmation(accepted(), 0).is_settled(0));
+        assert!(!confirmation(ConfirmationState::Pending, 0).is_settled(0));
+    }
+
+    #[test]
+    fn is_settled_rejected_stops_waiting() {
+        let rejected = ConfirmationState::Rejected {
+            reason: "double spend".to_string(),
+        };
+        assert!(confirmation(rejected, 0).is_settled(u64::MAX));
+    }
+}
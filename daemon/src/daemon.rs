@@ -1,9 +1,20 @@
+use crate::address_book::AddressBookStore;
 use crate::address_manager::AddressManager;
 use crate::args::Args;
 use crate::args::resolve_subnetwork_id;
+use crate::confirmation_tracker::ConfirmationTracker;
+use crate::dust_policy::DustPolicy;
+use crate::frozen_utxos::FrozenUtxoStore;
+use crate::idempotency::IdempotencyStore;
+use crate::invoice_tracker::InvoiceTracker;
+use crate::invoices::InvoiceStore;
+use crate::rebroadcast_manager::RebroadcastManager;
 use crate::service::kaswallet_service::KasWalletService;
+use crate::subnetworks::AllowedSubnetworks;
 use crate::sync_manager::SyncManager;
 use crate::transaction_generator::TransactionGenerator;
+use crate::used_addresses::UsedAddressStore;
+use crate::utxo_reservations::UtxoReservations;
 use crate::{kaspad_client, utxo_manager};
 use common::args::calculate_path;
 use common::error_location::ErrorLocation;
@@ -14,7 +25,9 @@ use kaspa_consensus_core::config::params::Params;
 use kaspa_grpc_client::GrpcClient;
 use kaspa_wallet_core::tx::MassCalculator;
 use proto::kaswallet_proto::wallet_server::WalletServer;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tonic::transport::Server;
@@ -24,12 +37,21 @@ pub struct Daemon {
     args: Arc<Args>,
 }
 
+/// Background tasks of a running daemon. The daemon stops being useful once any of them exits.
+pub struct DaemonHandles {
+    pub sync_manager: JoinHandle<()>,
+    pub rebroadcast_manager: JoinHandle<()>,
+    pub confirmation_tracker: JoinHandle<()>,
+    pub invoice_tracker: JoinHandle<()>,
+    pub server: JoinHandle<()>,
+}
+
 impl Daemon {
     pub fn new(args: Arc<Args>) -> Self {
         Self { args }
     }
 
-    pub async fn start(&self) -> WalletResult<(JoinHandle<()>, JoinHandle<()>)> {
+    pub async fn start(&self) -> WalletResult<DaemonHandles> {
         let network_id = self.args.network_id();
         let kaspa_rpc_client =
             Arc::new(kaspad_client::connect(&self.args.server, &network_id).await?);
@@ -43,7 +65,7 @@ impl Daemon {
         &self,
         kaspa_rpc_client: Arc<GrpcClient>,
         consensus_params: Params,
-    ) -> WalletResult<(JoinHandle<()>, JoinHandle<()>)> {
+    ) -> WalletResult<DaemonHandles> {
         let network_id = self.args.network_id();
 
         let extended_keys_prefix = Prefix::from(network_id);
@@ -51,16 +73,38 @@ impl Daemon {
         debug!("Keys file path: {}", keys_file_path);
         let keys = Arc::new(Keys::load(&keys_file_path, extended_keys_prefix)?);
         info!("Loaded keys from file {}", keys_file_path);
+        let frozen_utxos_file_path = sibling_file_path(&keys_file_path, "frozen_utxos.json");
+        let frozen_utxos = FrozenUtxoStore::load(&frozen_utxos_file_path)?;
+        debug!("Frozen UTXOs file path: {}", frozen_utxos_file_path);
+        let idempotency_keys_file_path =
+            sibling_file_path(&keys_file_path, "idempotency_keys.json");
+        let idempotency_store = IdempotencyStore::load(
+            &idempotency_keys_file_path,
+            Duration::from_secs(self.args.idempotency_key_retention_secs),
+        )?;
+        let address_book_file_path = sibling_file_path(&keys_file_path, "address_book.json");
+        let address_book = AddressBookStore::load(&address_book_file_path)?;
+        debug!("Address book file path: {}", address_book_file_path);
+        let invoices_file_path = sibling_file_path(&keys_file_path, "invoices.json");
+        let invoice_store = InvoiceStore::load(&invoices_file_path)?;
+        debug!("Invoices file path: {}", invoices_file_path);
+        let used_addresses_file_path = sibling_file_path(&keys_file_path, "used_addresses.json");
+        let used_addresses = UsedAddressStore::load(&used_addresses_file_path)?;
+        debug!("Used addresses file path: {}", used_addresses_file_path);
         let mass_calculator = Arc::new(MassCalculator::new(&network_id.network_type.into()));
 
         let address_prefix = network_id.network_type.into();
         let address_manager = Arc::new(Mutex::new(AddressManager::new(
             keys.clone(),
             address_prefix,
+            used_addresses,
         )));
         let utxo_manager = Arc::new(Mutex::new(utxo_manager::UtxoManager::new(
             address_manager.clone(),
             consensus_params.clone(),
+            self.args.mempool_transaction_expiry_daa_window,
+            frozen_utxos,
+            UtxoReservations::new(Duration::from_millis(self.args.utxo_reservation_ttl_millis)),
         )));
         let subnetwork_id = resolve_subnetwork_id(self.args.subnetwork_id);
         // Warn only when (a) the env var is set, (b) the resolved id is
@@ -90,7 +134,9 @@ impl Daemon {
         // a deployment intended to run as an IGRA lane container, that is
         // a silent misconfiguration. Surface it loudly at startup so the
         // operator notices before the daemon serves traffic.
-        if self.args.subnetwork_id.is_some() && subnetwork_id.is_native() {
+        let allowed_subnetworks =
+            AllowedSubnetworks::new(subnetwork_id, &self.args.allowed_subnetwork_ids);
+        if self.args.subnetwork_id.is_some() && !allowed_subnetworks.is_restricted() {
             warn!(
                 "--subnetwork-id resolved to native ({subnetwork_id}); lane enforcement is \
                  DISABLED for wire-supplied transactions. If this daemon is intended to run \
@@ -99,9 +145,10 @@ impl Daemon {
             );
         }
         info!(
-            "Transaction generator subnetwork_id: {} (native={})",
+            "Transaction generator subnetwork_id: {} (native={}), allowed: {}",
             subnetwork_id,
             subnetwork_id.is_native(),
+            allowed_subnetworks,
         );
         let transaction_generator = Arc::new(Mutex::new(TransactionGenerator::new(
             kaspa_rpc_client.clone(),
@@ -109,8 +156,9 @@ impl Daemon {
             address_manager.clone(),
             mass_calculator.clone(),
             address_prefix,
-            subnetwork_id,
+            allowed_subnetworks.clone(),
             &consensus_params,
+            self.args.coin_selection_strategy,
         )?));
         let sync_manager = Arc::new(SyncManager::new(
             kaspa_rpc_client.clone(),
@@ -120,7 +168,32 @@ impl Daemon {
             self.args.sync_interval_millis,
         ));
         let sync_manager_handle = SyncManager::start(sync_manager.clone());
+        let rebroadcast_manager = Arc::new(RebroadcastManager::new(
+            kaspa_rpc_client.clone(),
+            address_manager.clone(),
+            utxo_manager.clone(),
+            self.args.rebroadcast_interval_millis,
+        ));
+        let rebroadcast_manager_handle = RebroadcastManager::start(rebroadcast_manager);
+        let confirmation_tracker = Arc::new(ConfirmationTracker::new(
+            kaspa_rpc_client.clone(),
+            utxo_manager.clone(),
+            self.args.sync_interval_millis,
+        ));
+        let confirmation_tracker_handle = ConfirmationTracker::start(confirmation_tracker.clone());
+        let invoice_tracker = Arc::new(InvoiceTracker::new(
+            address_manager.clone(),
+            utxo_manager.clone(),
+            invoice_store,
+            self.args.sync_interval_millis,
+        ));
+        let invoice_tracker_handle = InvoiceTracker::start(invoice_tracker.clone());
 
+        let dust_policy = DustPolicy::new(
+            self.args.dust_threshold_sompi,
+            self.args.dust_fee_multiple,
+            self.args.hide_dust_from_balance,
+        )?;
         let service = KasWalletService::new(
             kaspa_rpc_client.clone(),
             keys,
@@ -128,7 +201,12 @@ impl Daemon {
             utxo_manager.clone(),
             transaction_generator.clone(),
             sync_manager.clone(),
-            subnetwork_id,
+            confirmation_tracker,
+            idempotency_store,
+            address_book,
+            invoice_tracker,
+            allowed_subnetworks,
+            dust_policy,
         );
 
         // Parse `--listen` at daemon-start time rather than inside the
@@ -155,6 +233,21 @@ impl Daemon {
                 tracing::error!(error = %e, "wallet server task exited with error");
             }
         });
-        Ok((sync_manager_handle, server_handle))
+        Ok(DaemonHandles {
+            sync_manager: sync_manager_handle,
+            rebroadcast_manager: rebroadcast_manager_handle,
+            confirmation_tracker: confirmation_tracker_handle,
+            invoice_tracker: invoice_tracker_handle,
+            server: server_handle,
+        })
     }
 }
+
+/// Path of a wallet data file stored in the same directory as the keys file, so each wallet
+/// keeps its own state.
+fn sibling_file_path(keys_file_path: &str, file_name: &str) -> String {
+    Path::new(keys_file_path)
+        .with_file_name(file_name)
+        .display()
+        .to_string()
+}
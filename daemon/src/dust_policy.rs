@@ -0,0 +1,106 @@
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+
+/// When a UTXO counts as dust.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DustThreshold {
+    /// Worth less than this many sompi.
+    Sompi(u64),
+    /// Worth at most this multiple of the fee to spend it at the current normal fee rate.
+    FeeMultiple(f64),
+}
+
+/// How the daemon classifies and reports dust, from `--dust-threshold-sompi`,
+/// `--dust-fee-multiple` and `--hide-dust-from-balance`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DustPolicy {
+    pub threshold: DustThreshold,
+    /// Leave dust out of `available` and `pending` in GetBalance and report it on its own.
+    pub hide_from_balance: bool,
+}
+
+impl Default for DustPolicy {
+    /// A UTXO is dust when spending it costs at least its value, and balances still count it.
+    fn default() -> Self {
+        Self {
+            threshold: DustThreshold::FeeMultiple(1.0),
+            hide_from_balance: false,
+        }
+    }
+}
+
+impl DustPolicy {
+    pub fn new(
+        threshold_sompi: Option<u64>,
+        fee_multiple: f64,
+        hide_from_balance: bool,
+    ) -> WalletResult<Self> {
+        let threshold = match threshold_sompi {
+            Some(sompi) => DustThreshold::Sompi(sompi),
+            None => {
+                if !fee_multiple.is_finite() || fee_multiple <= 0.0 {
+                    return Err(WalletError::from(UserInputError::InvalidArgument {
+                        reason: format!(
+                            "--dust-fee-multiple must be a positive number, got {fee_multiple}"
+                        ),
+                        location: ErrorLocation::capture(),
+                    }));
+                }
+                DustThreshold::FeeMultiple(fee_multiple)
+            }
+        };
+        Ok(Self {
+            threshold,
+            hide_from_balance,
+        })
+    }
+
+    /// Whether `is_dust` reads the fee to spend the UTXO, so callers can skip estimating it.
+    pub fn is_fee_relative(&self) -> bool {
+        matches!(self.threshold, DustThreshold::FeeMultiple(_))
+    }
+
+    /// `fee_to_spend` is the fee for a transaction spending only this UTXO, and is ignored by a
+    /// sompi threshold.
+    pub fn is_dust(&self, amount: u64, fee_to_spend: u64) -> bool {
+        match self.threshold {
+            DustThreshold::Sompi(threshold) => amount < threshold,
+            DustThreshold::FeeMultiple(multiple) => amount as f64 <= fee_to_spend as f64 * multiple,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_matches_fee_to_spend() {
+        let policy = DustPolicy::default();
+        assert!(policy.is_dust(1_000, 1_000));
+        assert!(!policy.is_dust(1_001, 1_000));
+    }
+
+    #[test]
+    fn sompi_threshold_ignores_fee() {
+        let policy = DustPolicy::new(Some(5_000), 1.0, false).unwrap();
+        assert!(!policy.is_fee_relative());
+        assert!(policy.is_dust(4_999, 0));
+        assert!(!policy.is_dust(5_000, 1_000_000));
+    }
+
+    #[test]
+    fn fee_multiple_scales_fee_to_spend() {
+        let policy = DustPolicy::new(None, 3.0, true).unwrap();
+        assert!(policy.is_dust(3_000, 1_000));
+        assert!(!policy.is_dust(3_001, 1_000));
+    }
+
+    #[test]
+    fn non_positive_fee_multiple_is_rejected() {
+        assert!(DustPolicy::new(None, 0.0, false).is_err());
+        assert!(DustPolicy::new(None, f64::NAN, false).is_err());
+        // Ignored when a sompi threshold is set.
+        DustPolicy::new(Some(1), 0.0, false).unwrap();
+    }
+}
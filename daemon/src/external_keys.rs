@@ -0,0 +1,296 @@
+use common::error_location::ErrorLocation;
+use common::errors::{CryptoError, UserInputError, WalletError, WalletResult};
+use common::keys::master_key_path;
+use common::model::{Keychain, WalletOutpoint, WalletUtxoEntry};
+use kaspa_addresses::{Address, Prefix, Version};
+use kaspa_bip32::{DerivationPath, ExtendedPrivateKey, Language, Mnemonic, SecretKey, secp256k1};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+/// Unused addresses scanned past the last used one on each keychain when sweeping a mnemonic.
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// Largest gap limit a mnemonic sweep accepts.
+pub const MAX_GAP_LIMIT: u32 = 1_000;
+
+/// Addresses looked up in one kaspad query while scanning a mnemonic's keychain.
+const SCAN_BATCH_SIZE: u32 = 100;
+
+/// A private key held outside the wallet, used in memory only to sweep its funds into the
+/// wallet. The key bytes are zeroized on drop and never printed.
+pub struct ExternalKey {
+    secret: Zeroizing<[u8; 32]>,
+    pub address: Address,
+}
+
+impl ExternalKey {
+    /// `secret` must be a valid secp256k1 private key; the address is its schnorr P2PK address.
+    pub fn new(secret: Zeroizing<[u8; 32]>, prefix: Prefix) -> WalletResult<Self> {
+        let keypair = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &*secret)
+            .map_err(|_| invalid_private_key("not a valid secp256k1 private key"))?;
+        let payload = keypair.public_key().x_only_public_key().0.serialize();
+        Ok(Self {
+            secret,
+            address: Address::new(prefix, Version::PubKey, &payload),
+        })
+    }
+
+    pub fn secret_bytes(&self) -> &[u8; 32] {
+        &self.secret
+    }
+}
+
+impl fmt::Debug for ExternalKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalKey")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A UTXO of an external key, as discovered through kaspad.
+#[derive(Debug, Clone)]
+pub struct ExternalUtxo {
+    pub outpoint: WalletOutpoint,
+    pub utxo_entry: WalletUtxoEntry,
+    pub address: Address,
+}
+
+fn invalid_private_key(reason: &str) -> WalletError {
+    // Never echo the input: it is key material.
+    WalletError::from(UserInputError::InvalidArgument {
+        reason: format!("invalid private key: {reason}"),
+        location: ErrorLocation::capture(),
+    })
+}
+
+/// Parse a private key given as 64 hex characters or in WIF (base58check of a version byte, the
+/// 32 key bytes and an optional compression flag).
+pub fn parse_private_key(input: &str) -> WalletResult<Zeroizing<[u8; 32]>> {
+    let input = input.trim();
+    let mut secret = Zeroizing::new([0u8; 32]);
+    if input.len() == 64 && input.chars().all(|c| c.is_ascii_hexdigit()) {
+        hex::decode_to_slice(input, &mut *secret)
+            .map_err(|_| invalid_private_key("malformed hex"))?;
+    } else {
+        let payload = Zeroizing::new(
+            bs58::decode(input)
+                .with_check(None)
+                .into_vec()
+                .map_err(|_| invalid_private_key("expected 64 hex characters or WIF"))?,
+        );
+        let key_bytes = match payload.len() {
+            33 => &payload[1..],
+            34 if payload[33] == 0x01 => &payload[1..33],
+            _ => return Err(invalid_private_key("unexpected WIF payload length")),
+        };
+        secret.copy_from_slice(key_bytes);
+    }
+    secp256k1::SecretKey::from_slice(&*secret)
+        .map_err(|_| invalid_private_key("not a valid secp256k1 private key"))?;
+    Ok(secret)
+}
+
+/// The account key of a BIP39 mnemonic at `derivation_path`, or at the wallet's own single-signer
+/// account path (m/44'/111111'/0') when empty.
+pub fn mnemonic_account_key(
+    phrase: &str,
+    passphrase: &str,
+    derivation_path: &str,
+) -> WalletResult<ExtendedPrivateKey<SecretKey>> {
+    let mnemonic = Mnemonic::new(phrase.trim(), Language::English).map_err(|_| {
+        WalletError::from(UserInputError::InvalidArgument {
+            reason: "invalid mnemonic".to_string(),
+            location: ErrorLocation::capture(),
+        })
+    })?;
+    let derivation_path = if derivation_path.is_empty() {
+        master_key_path(false)
+    } else {
+        DerivationPath::from_str(derivation_path).map_err(|e| {
+            WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("invalid derivation path {derivation_path}: {e}"),
+                location: ErrorLocation::capture(),
+            })
+        })?
+    };
+    let seed = mnemonic.to_seed(passphrase);
+    ExtendedPrivateKey::new(seed)
+        .and_then(|master_key| master_key.derive_path(&derivation_path))
+        .map_err(|e| {
+            WalletError::from(CryptoError::Bip32Derivation {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            })
+        })
+}
+
+/// The gap limit a mnemonic sweep requested, with 0 standing for [`DEFAULT_GAP_LIMIT`].
+pub fn resolve_gap_limit(requested: u32) -> WalletResult<u32> {
+    match requested {
+        0 => Ok(DEFAULT_GAP_LIMIT),
+        gap_limit if gap_limit > MAX_GAP_LIMIT => {
+            Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("gap limit {gap_limit} exceeds the maximum of {MAX_GAP_LIMIT}"),
+                location: ErrorLocation::capture(),
+            }))
+        }
+        gap_limit => Ok(gap_limit),
+    }
+}
+
+/// The next indexes of a keychain to scan, given that everything before `next_index` was
+/// scanned and `last_used_index` is the highest one found holding UTXOs. Returns at most
+/// [`SCAN_BATCH_SIZE`] indexes, or `None` once `gap_limit` unused indexes follow the last used one.
+pub fn next_scan_batch(
+    next_index: u32,
+    last_used_index: Option<u32>,
+    gap_limit: u32,
+) -> Option<Range<u32>> {
+    let scan_end = match last_used_index {
+        Some(index) => index.checked_add(1)?,
+        None => 0,
+    }
+    .checked_add(gap_limit)?;
+    if next_index >= scan_end {
+        return None;
+    }
+    let batch_end = next_index
+        .checked_add(SCAN_BATCH_SIZE)
+        .map_or(scan_end, |batch_end| batch_end.min(scan_end));
+    Some(next_index..batch_end)
+}
+
+/// The key at `keychain`/`index` under an account key from [`mnemonic_account_key`].
+pub fn derive_external_key(
+    account_key: &ExtendedPrivateKey<SecretKey>,
+    keychain: Keychain,
+    index: u32,
+    prefix: Prefix,
+) -> WalletResult<ExternalKey> {
+    let path_string = format!("m/{}/{}", keychain as u32, index);
+    let derivation_path =
+        DerivationPath::from_str(&path_string).map_err(|e| CryptoError::Bip32Derivation {
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })?;
+    let private_key = account_key
+        .clone()
+        .derive_path(&derivation_path)
+        .map_err(|e| CryptoError::Bip32Derivation {
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })?;
+    ExternalKey::new(
+        Zeroizing::new(private_key.private_key().secret_bytes()),
+        prefix,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                          abandon abandon abandon about";
+
+    fn key_one() -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[31] = 1;
+        key
+    }
+
+    #[test]
+    fn parses_hex_and_wif_private_keys() {
+        let hex_key = format!("{}01", "00".repeat(31));
+        assert_eq!(*parse_private_key(&hex_key).unwrap(), key_one());
+        // Compressed and uncompressed WIF encodings of the same key.
+        let compressed = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        let uncompressed = "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf";
+        assert_eq!(*parse_private_key(compressed).unwrap(), key_one());
+        assert_eq!(*parse_private_key(uncompressed).unwrap(), key_one());
+    }
+
+    #[test]
+    fn rejects_malformed_private_keys() {
+        // Bad checksum.
+        assert!(parse_private_key("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWm").is_err());
+        // Zero is not a valid secp256k1 key.
+        assert!(parse_private_key(&"00".repeat(32)).is_err());
+        assert!(parse_private_key("not a key").is_err());
+    }
+
+    #[test]
+    fn external_key_address_is_schnorr_p2pk() {
+        let key = ExternalKey::new(Zeroizing::new(key_one()), Prefix::Mainnet).unwrap();
+        assert_eq!(key.address.version, Version::PubKey);
+        // x-only public key of the generator point.
+        assert_eq!(
+            hex::encode(key.address.payload.as_slice()),
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert!(!format!("{key:?}").contains("secret"));
+    }
+
+    #[test]
+    fn gap_limit_defaults_when_zero_and_is_bounded() {
+        assert_eq!(resolve_gap_limit(0).unwrap(), DEFAULT_GAP_LIMIT);
+        assert_eq!(resolve_gap_limit(5).unwrap(), 5);
+        assert_eq!(resolve_gap_limit(MAX_GAP_LIMIT).unwrap(), MAX_GAP_LIMIT);
+        assert!(resolve_gap_limit(MAX_GAP_LIMIT + 1).is_err());
+        assert!(resolve_gap_limit(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn scan_batches_stop_a_gap_limit_past_the_last_used_index() {
+        assert_eq!(next_scan_batch(0, None, 20), Some(0..20));
+        assert_eq!(next_scan_batch(20, None, 20), None);
+        assert_eq!(next_scan_batch(20, Some(7), 20), Some(20..28));
+        assert_eq!(next_scan_batch(28, Some(7), 20), None);
+    }
+
+    #[test]
+    fn scan_batches_are_bounded_in_size() {
+        assert_eq!(
+            next_scan_batch(0, None, MAX_GAP_LIMIT),
+            Some(0..SCAN_BATCH_SIZE)
+        );
+        assert_eq!(
+            next_scan_batch(900, None, MAX_GAP_LIMIT),
+            Some(900..MAX_GAP_LIMIT)
+        );
+    }
+
+    #[test]
+    fn scan_batches_do_not_overflow_at_the_end_of_the_index_space() {
+        assert_eq!(next_scan_batch(u32::MAX, Some(u32::MAX), 20), None);
+        assert_eq!(next_scan_batch(0, Some(u32::MAX - 10), 20), None);
+        assert_eq!(
+            next_scan_batch(u32::MAX - 50, Some(u32::MAX - 60), 20),
+            Some(u32::MAX - 50..u32::MAX - 39)
+        );
+    }
+
+    #[test]
+    fn mnemonic_defaults_to_the_wallet_account_path() {
+        let default_path = mnemonic_account_key(PHRASE, "", "").unwrap();
+        let explicit_path = mnemonic_account_key(PHRASE, "", "m/44'/111111'/0'").unwrap();
+        let derive = |account_key, keychain, index| {
+            derive_external_key(account_key, keychain, index, Prefix::Mainnet)
+                .unwrap()
+                .address
+        };
+        assert_eq!(
+            derive(&default_path, Keychain::External, 0),
+            derive(&explicit_path, Keychain::External, 0)
+        );
+        assert_ne!(
+            derive(&default_path, Keychain::External, 0),
+            derive(&default_path, Keychain::Internal, 0)
+        );
+        assert!(mnemonic_account_key("abandon abandon", "", "").is_err());
+        assert!(mnemonic_account_key(PHRASE, "", "not/a/path").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use common::error_location::ErrorLocation;
+use common::errors::{StorageError, WalletResult};
+use common::json_store::{read_json_file, unix_millis_now, write_json_file};
+use common::model::WalletOutpoint;
+use kaspa_consensus_core::tx::TransactionId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const FROZEN_UTXOS_FILE_KIND: &str = "frozen_utxos.json";
+
+/// Why and when an outpoint was frozen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenUtxo {
+    pub reason: String,
+    pub frozen_at_unix_millis: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FrozenUtxoJson {
+    transaction_id: String,
+    index: u32,
+    reason: String,
+    frozen_at_unix_millis: u64,
+}
+
+/// Outpoints the operator excluded from automatic coin selection, persisted next to the keys
+/// file so freezes survive restarts.
+#[derive(Debug)]
+pub struct FrozenUtxoStore {
+    file_path: Option<String>,
+    frozen: HashMap<WalletOutpoint, FrozenUtxo>,
+}
+
+impl FrozenUtxoStore {
+    pub fn load(file_path: &str) -> WalletResult<Self> {
+        let entries: Vec<FrozenUtxoJson> =
+            read_json_file(file_path, FROZEN_UTXOS_FILE_KIND)?.unwrap_or_default();
+        let mut frozen = HashMap::with_capacity(entries.len());
+        for entry in entries {
+            let transaction_id = TransactionId::from_str(&entry.transaction_id).map_err(|e| {
+                StorageError::Deserialize {
+                    kind: FROZEN_UTXOS_FILE_KIND,
+                    reason: format!("invalid transaction id {}: {e}", entry.transaction_id),
+                    location: ErrorLocation::capture(),
+                }
+            })?;
+            frozen.insert(
+                WalletOutpoint::new(transaction_id, entry.index),
+                FrozenUtxo {
+                    reason: entry.reason,
+                    frozen_at_unix_millis: entry.frozen_at_unix_millis,
+                },
+            );
+        }
+        Ok(Self {
+            file_path: Some(file_path.to_string()),
+            frozen,
+        })
+    }
+
+    /// A store that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            file_path: None,
+            frozen: HashMap::new(),
+        }
+    }
+
+    pub fn is_frozen(&self, outpoint: &WalletOutpoint) -> bool {
+        self.frozen.contains_key(outpoint)
+    }
+
+    pub fn get(&self, outpoint: &WalletOutpoint) -> Option<&FrozenUtxo> {
+        self.frozen.get(outpoint)
+    }
+
+    /// All frozen outpoints, oldest freeze first.
+    pub fn list(&self) -> Vec<(WalletOutpoint, FrozenUtxo)> {
+        let mut frozen: Vec<_> = self
+            .frozen
+            .iter()
+            .map(|(outpoint, frozen_utxo)| (outpoint.clone(), frozen_utxo.clone()))
+            .collect();
+        frozen.sort_by(|(a_outpoint, a), (b_outpoint, b)| {
+            a.frozen_at_unix_millis
+                .cmp(&b.frozen_at_unix_millis)
+                .then_with(|| a_outpoint.transaction_id.cmp(&b_outpoint.transaction_id))
+                .then_with(|| a_outpoint.index.cmp(&b_outpoint.index))
+        });
+        frozen
+    }
+
+    /// Freeze `outpoints`. Re-freezing an outpoint replaces its reason but keeps the time it
+    /// was first frozen.
+    pub fn freeze(&mut self, outpoints: &[WalletOutpoint], reason: &str) -> WalletResult<()> {
+        let now = unix_millis_now();
+        for outpoint in outpoints {
+            self.frozen
+                .entry(outpoint.clone())
+                .and_modify(|frozen_utxo| frozen_utxo.reason = reason.to_string())
+                .or_insert_with(|| FrozenUtxo {
+                    reason: reason.to_string(),
+                    frozen_at_unix_millis: now,
+                });
+        }
+        self.save()
+    }
+
+    /// Unfreeze `outpoints`, returning those that were frozen.
+    pub fn unfreeze(&mut self, outpoints: &[WalletOutpoint]) -> WalletResult<Vec<WalletOutpoint>> {
+        let unfrozen: Vec<WalletOutpoint> = outpoints
+            .iter()
+            .filter(|outpoint| self.frozen.remove(outpoint).is_some())
+            .cloned()
+            .collect();
+        if !unfrozen.is_empty() {
+            self.save()?;
+        }
+        Ok(unfrozen)
+    }
+
+    fn save(&self) -> WalletResult<()> {
+        let Some(file_path) = &self.file_path else {
+            return Ok(());
+        };
+        let entries: Vec<FrozenUtxoJson> = self
+            .list()
+            .into_iter()
+            .map(|(outpoint, frozen_utxo)| FrozenUtxoJson {
+                transaction_id: outpoint.transaction_id.to_string(),
+                index: outpoint.index,
+                reason: frozen_utxo.reason,
+                frozen_at_unix_millis: frozen_utxo.frozen_at_unix_millis,
+            })
+            .collect();
+        write_json_file(file_path, FROZEN_UTXOS_FILE_KIND, &entries)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outpoint(byte: u8, index: u32) -> WalletOutpoint {
+        WalletOutpoint::new(kaspa_hashes::Hash::from_bytes([byte; 32]), index)
+    }
+
+    #[test]
+    fn freeze_persists_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frozen_utxos.json");
+        let path = path.to_str().unwrap();
+
+        let mut store = FrozenUtxoStore::load(path).unwrap();
+        store
+            .freeze(&[outpoint(1, 0), outpoint(2, 3)], "compliance review")
+            .unwrap();
+
+        let reloaded = FrozenUtxoStore::load(path).unwrap();
+        assert!(reloaded.is_frozen(&outpoint(1, 0)));
+        assert!(reloaded.is_frozen(&outpoint(2, 3)));
+        assert!(!reloaded.is_frozen(&outpoint(2, 0)));
+        assert_eq!(
+            reloaded.get(&outpoint(2, 3)).unwrap().reason,
+            "compliance review"
+        );
+    }
+
+    #[test]
+    fn refreeze_keeps_original_time_and_updates_reason() {
+        let mut store = FrozenUtxoStore::in_memory();
+        store.freeze(&[outpoint(1, 0)], "first").unwrap();
+        let frozen_at = store.get(&outpoint(1, 0)).unwrap().frozen_at_unix_millis;
+
+        store.freeze(&[outpoint(1, 0)], "second").unwrap();
+        let frozen_utxo = store.get(&outpoint(1, 0)).unwrap();
+        assert_eq!(frozen_utxo.reason, "second");
+        assert_eq!(frozen_utxo.frozen_at_unix_millis, frozen_at);
+    }
+
+    #[test]
+    fn unfreeze_returns_only_frozen_outpoints() {
+        let mut store = FrozenUtxoStore::in_memory();
+        store.freeze(&[outpoint(1, 0)], "").unwrap();
+
+        let unfrozen = store.unfreeze(&[outpoint(1, 0), outpoint(2, 0)]).unwrap();
+        assert_eq!(unfrozen, vec![outpoint(1, 0)]);
+        assert!(!store.is_frozen(&outpoint(1, 0)));
+    }
+}
@@ -0,0 +1,209 @@
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::json_store::{read_json_file, unix_millis_now, write_json_file};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const IDEMPOTENCY_KEYS_FILE_KIND: &str = "idempotency_keys.json";
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    // Identifies the request body the key was first used with.
+    fingerprint: String,
+    transaction_ids: Vec<String>,
+    created_at_unix_millis: u64,
+}
+
+/// Results of Send and Broadcast requests by client-supplied idempotency key, persisted next to
+/// the keys file so a retry after a daemon restart still finds the original transactions.
+#[derive(Debug)]
+pub struct IdempotencyStore {
+    file_path: Option<String>,
+    retention: Duration,
+    records: HashMap<String, IdempotencyRecord>,
+}
+
+impl IdempotencyStore {
+    pub fn load(file_path: &str, retention: Duration) -> WalletResult<Self> {
+        let records = read_json_file(file_path, IDEMPOTENCY_KEYS_FILE_KIND)?.unwrap_or_default();
+        let mut store = Self {
+            file_path: Some(file_path.to_string()),
+            retention,
+            records,
+        };
+        store.prune_expired(unix_millis_now());
+        Ok(store)
+    }
+
+    /// A store that is never written to disk.
+    pub fn in_memory(retention: Duration) -> Self {
+        Self {
+            file_path: None,
+            retention,
+            records: HashMap::new(),
+        }
+    }
+
+    /// The transaction ids recorded for `key`, if it was used within the retention window.
+    /// Fails if the key was used with a different request body.
+    pub fn lookup(&self, key: &str, fingerprint: &str) -> WalletResult<Option<Vec<String>>> {
+        self.lookup_at(key, fingerprint, unix_millis_now())
+    }
+
+    fn lookup_at(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now_unix_millis: u64,
+    ) -> WalletResult<Option<Vec<String>>> {
+        validate_idempotency_key(key)?;
+        let Some(record) = self
+            .records
+            .get(key)
+            .filter(|record| !self.is_expired(record, now_unix_millis))
+        else {
+            return Ok(None);
+        };
+        if record.fingerprint != fingerprint {
+            return Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("idempotency key {key} was already used with a different request"),
+                location: ErrorLocation::capture(),
+            }));
+        }
+        Ok(Some(record.transaction_ids.clone()))
+    }
+
+    pub fn record(
+        &mut self,
+        key: &str,
+        fingerprint: &str,
+        transaction_ids: &[String],
+    ) -> WalletResult<()> {
+        self.record_at(key, fingerprint, transaction_ids, unix_millis_now())
+    }
+
+    fn record_at(
+        &mut self,
+        key: &str,
+        fingerprint: &str,
+        transaction_ids: &[String],
+        now_unix_millis: u64,
+    ) -> WalletResult<()> {
+        validate_idempotency_key(key)?;
+        self.prune_expired(now_unix_millis);
+        self.records.insert(
+            key.to_string(),
+            IdempotencyRecord {
+                fingerprint: fingerprint.to_string(),
+                transaction_ids: transaction_ids.to_vec(),
+                created_at_unix_millis: now_unix_millis,
+            },
+        );
+        self.save()
+    }
+
+    fn is_expired(&self, record: &IdempotencyRecord, now_unix_millis: u64) -> bool {
+        now_unix_millis.saturating_sub(record.created_at_unix_millis)
+            >= self.retention.as_millis() as u64
+    }
+
+    fn prune_expired(&mut self, now_unix_millis: u64) {
+        let retention_millis = self.retention.as_millis() as u64;
+        self.records.retain(|_, record| {
+            now_unix_millis.saturating_sub(record.created_at_unix_millis) < retention_millis
+        });
+    }
+
+    fn save(&self) -> WalletResult<()> {
+        let Some(file_path) = &self.file_path else {
+            return Ok(());
+        };
+        write_json_file(file_path, IDEMPOTENCY_KEYS_FILE_KIND, &self.records)?;
+        Ok(())
+    }
+}
+
+fn validate_idempotency_key(key: &str) -> WalletResult<()> {
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(WalletError::from(UserInputError::InvalidArgument {
+            reason: format!(
+                "idempotency key must be at most {MAX_IDEMPOTENCY_KEY_LEN} bytes, got {}",
+                key.len()
+            ),
+            location: ErrorLocation::capture(),
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RETENTION: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn repeated_key_returns_original_transaction_ids() {
+        let mut store = IdempotencyStore::in_memory(RETENTION);
+        let transaction_ids = vec!["a".to_string(), "b".to_string()];
+        store
+            .record_at("key", "fingerprint", &transaction_ids, 1_000)
+            .unwrap();
+
+        let res = store.lookup_at("key", "fingerprint", 2_000).unwrap();
+        assert_eq!(res, Some(transaction_ids));
+        assert_eq!(
+            store.lookup_at("other", "fingerprint", 2_000).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn reused_key_with_different_request_is_rejected() {
+        let mut store = IdempotencyStore::in_memory(RETENTION);
+        store
+            .record_at("key", "fingerprint", &["a".to_string()], 1_000)
+            .unwrap();
+
+        let err = store
+            .lookup_at("key", "other fingerprint", 2_000)
+            .unwrap_err();
+        assert_eq!(err.kind_name(), "InvalidArgument", "got: {err}");
+    }
+
+    #[test]
+    fn key_is_forgotten_after_retention() {
+        let mut store = IdempotencyStore::in_memory(RETENTION);
+        store
+            .record_at("key", "fingerprint", &["a".to_string()], 1_000)
+            .unwrap();
+
+        let after_retention = 1_000 + RETENTION.as_millis() as u64;
+        assert_eq!(
+            store
+                .lookup_at("key", "other fingerprint", after_retention)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn records_persist_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("idempotency_keys.json");
+        let path = path.to_str().unwrap();
+
+        let mut store = IdempotencyStore::load(path, RETENTION).unwrap();
+        store
+            .record("key", "fingerprint", &["a".to_string()])
+            .unwrap();
+
+        let reloaded = IdempotencyStore::load(path, RETENTION).unwrap();
+        assert_eq!(
+            reloaded.lookup("key", "fingerprint").unwrap(),
+            Some(vec!["a".to_string()])
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::address_manager::AddressManager;
+use crate::invoices::{Invoice, InvoiceEvent, InvoicePayment, InvoiceStore};
+use crate::utxo_manager::UtxoManager;
+use common::errors::WalletResult;
+use common::json_store::unix_millis_now;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+/// Follows the UTXOs paid to invoice addresses and moves invoices between states, recording an
+/// event for each change.
+pub struct InvoiceTracker {
+    address_manager: Arc<Mutex<AddressManager>>,
+    utxo_manager: Arc<Mutex<UtxoManager>>,
+
+    poll_interval_millis: u64,
+    // Never held while taking another lock.
+    store: Mutex<InvoiceStore>,
+}
+
+impl InvoiceTracker {
+    pub fn new(
+        address_manager: Arc<Mutex<AddressManager>>,
+        utxo_manager: Arc<Mutex<UtxoManager>>,
+        store: InvoiceStore,
+        poll_interval_millis: u64,
+    ) -> Self {
+        Self {
+            address_manager,
+            utxo_manager,
+            poll_interval_millis,
+            store: Mutex::new(store),
+        }
+    }
+
+    pub fn start(invoice_tracker: Arc<InvoiceTracker>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(core::time::Duration::from_millis(
+                invoice_tracker.poll_interval_millis,
+            ));
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = invoice_tracker.refresh().await {
+                    warn!("Invoice tracking poll failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Record a new invoice for `address`, which must be a freshly issued wallet address.
+    pub async fn create(
+        &self,
+        address: String,
+        amount: u64,
+        memo: String,
+        expires_at_unix_millis: Option<u64>,
+    ) -> WalletResult<Invoice> {
+        self.store.lock().await.create(
+            address,
+            amount,
+            memo,
+            unix_millis_now(),
+            expires_at_unix_millis,
+        )
+    }
+
+    pub async fn invoice(&self, invoice_id: u64) -> WalletResult<Invoice> {
+        self.store.lock().await.get(invoice_id).cloned()
+    }
+
+    /// All invoices, oldest first.
+    pub async fn invoices(&self) -> Vec<Invoice> {
+        self.store.lock().await.list().cloned().collect()
+    }
+
+    pub async fn events_after(&self, after_sequence: u64, limit: usize) -> Vec<InvoiceEvent> {
+        self.store.lock().await.events_after(after_sequence, limit)
+    }
+
+    /// Bring every invoice up to date with the wallet's UTXO set and the clock.
+    pub async fn refresh(&self) -> WalletResult<()> {
+        let invoice_addresses: Vec<String> = {
+            let store = self.store.lock().await;
+            store
+                .list()
+                .map(|invoice| invoice.address.clone())
+                .collect()
+        };
+        if invoice_addresses.is_empty() {
+            return Ok(());
+        }
+
+        let mut invoice_address_by_wallet_address = HashMap::new();
+        {
+            let address_manager = self.address_manager.lock().await;
+            for address in invoice_addresses {
+                if let Some(wallet_address) =
+                    address_manager.wallet_address_from_string(&address).await
+                {
+                    invoice_address_by_wallet_address.insert(wallet_address, address);
+                }
+            }
+        }
+
+        let mut payments_by_address: HashMap<String, Vec<InvoicePayment>> = HashMap::new();
+        {
+            let utxo_manager = self.utxo_manager.lock().await;
+            for utxo in utxo_manager.utxos_sorted_by_amount() {
+                // Outputs of the wallet's own unaccepted transactions are not payments yet.
+                if utxo.utxo_entry.is_unconfirmed {
+                    continue;
+                }
+                if let Some(address) = invoice_address_by_wallet_address.get(&utxo.address) {
+                    payments_by_address
+                        .entry(address.clone())
+                        .or_default()
+                        .push(InvoicePayment {
+                            transaction_id: utxo.outpoint.transaction_id.to_string(),
+                            index: utxo.outpoint.index,
+                            amount: utxo.utxo_entry.amount,
+                        });
+                }
+            }
+        }
+
+        let events = self
+            .store
+            .lock()
+            .await
+            .update(&payments_by_address, unix_millis_now())?;
+        for event in events {
+            info!(
+                invoice_id = event.invoice_id,
+                sequence = event.sequence,
+                received = event.received,
+                "Invoice state changed from {:?} to {:?}",
+                event.previous_state,
+                event.state
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::json_store::{read_json_file, write_json_file};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+const INVOICES_FILE_KIND: &str = "invoices.json";
+
+/// Invoice events kept for polling; older ones are dropped first.
+const MAX_RETAINED_EVENTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvoiceState {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+    Overpaid,
+    Expired,
+}
+
+impl InvoiceState {
+    /// The state an invoice moves to from `self` once `received` sompi have been paid towards
+    /// `amount`. Payments seen in the same poll as the expiry still count. An invoice that
+    /// expired short of its amount stays expired whatever arrives later, and a paid one never
+    /// expires.
+    pub fn next(
+        self,
+        amount: u64,
+        received: u64,
+        expires_at_unix_millis: Option<u64>,
+        now_unix_millis: u64,
+    ) -> Self {
+        if self == Self::Expired {
+            return Self::Expired;
+        }
+        if received > amount {
+            return Self::Overpaid;
+        }
+        if received == amount {
+            return Self::Paid;
+        }
+        if expires_at_unix_millis.is_some_and(|expires_at| now_unix_millis >= expires_at) {
+            return Self::Expired;
+        }
+        if received > 0 {
+            Self::PartiallyPaid
+        } else {
+            Self::Unpaid
+        }
+    }
+}
+
+/// A UTXO paid to an invoice's address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvoicePayment {
+    pub transaction_id: String,
+    pub index: u32,
+    pub amount: u64,
+}
+
+/// An expected payment to a fresh external address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invoice {
+    pub id: u64,
+    pub address: String,
+    pub amount: u64,
+    pub memo: String,
+    pub created_at_unix_millis: u64,
+    pub expires_at_unix_millis: Option<u64>,
+    pub state: InvoiceState,
+    /// Every UTXO ever seen on the address, so spending one later does not unpay the invoice.
+    pub payments: Vec<InvoicePayment>,
+}
+
+impl Invoice {
+    pub fn received(&self) -> u64 {
+        self.payments.iter().map(|payment| payment.amount).sum()
+    }
+}
+
+/// A change of an invoice's state. Sequences increase by one per event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvoiceEvent {
+    pub sequence: u64,
+    pub invoice_id: u64,
+    pub previous_state: InvoiceState,
+    pub state: InvoiceState,
+    pub received: u64,
+    pub at_unix_millis: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct InvoicesJson {
+    next_invoice_id: u64,
+    next_event_sequence: u64,
+    invoices: Vec<Invoice>,
+    events: Vec<InvoiceEvent>,
+}
+
+/// Invoices and their state-change events, persisted next to the keys file.
+#[derive(Debug)]
+pub struct InvoiceStore {
+    file_path: Option<String>,
+    next_invoice_id: u64,
+    next_event_sequence: u64,
+    invoices: BTreeMap<u64, Invoice>,
+    events: VecDeque<InvoiceEvent>,
+}
+
+impl InvoiceStore {
+    pub fn load(file_path: &str) -> WalletResult<Self> {
+        let invoices: InvoicesJson =
+            read_json_file(file_path, INVOICES_FILE_KIND)?.unwrap_or_default();
+        Ok(Self {
+            file_path: Some(file_path.to_string()),
+            next_invoice_id: invoices.next_invoice_id.max(1),
+            next_event_sequence: invoices.next_event_sequence.max(1),
+            invoices: invoices
+                .invoices
+                .into_iter()
+                .map(|invoice| (invoice.id, invoice))
+                .collect(),
+            events: invoices.events.into(),
+        })
+    }
+
+    /// A store that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            file_path: None,
+            next_invoice_id: 1,
+            next_event_sequence: 1,
+            invoices: BTreeMap::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn get(&self, invoice_id: u64) -> WalletResult<&Invoice> {
+        self.invoices.get(&invoice_id).ok_or_else(|| {
+            WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("no invoice with id {invoice_id}"),
+                location: ErrorLocation::capture(),
+            })
+        })
+    }
+
+    /// All invoices, oldest first.
+    pub fn list(&self) -> impl Iterator<Item = &Invoice> {
+        self.invoices.values()
+    }
+
+    /// Up to `limit` events with a sequence above `after_sequence`, oldest first.
+    pub fn events_after(&self, after_sequence: u64, limit: usize) -> Vec<InvoiceEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.sequence > after_sequence)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn create(
+        &mut self,
+        address: String,
+        amount: u64,
+        memo: String,
+        created_at_unix_millis: u64,
+        expires_at_unix_millis: Option<u64>,
+    ) -> WalletResult<Invoice> {
+        let invoice = Invoice {
+            id: self.next_invoice_id,
+            address,
+            amount,
+            memo,
+            created_at_unix_millis,
+            expires_at_unix_millis,
+            state: InvoiceState::Unpaid,
+            payments: vec![],
+        };
+        self.next_invoice_id += 1;
+        self.invoices.insert(invoice.id, invoice.clone());
+        self.save()?;
+        Ok(invoice)
+    }
+
+    /// Record the UTXOs now on each invoice address and move invoices to their new states,
+    /// returning an event per state change.
+    pub fn update(
+        &mut self,
+        payments_by_address: &HashMap<String, Vec<InvoicePayment>>,
+        now_unix_millis: u64,
+    ) -> WalletResult<Vec<InvoiceEvent>> {
+        let mut changed = false;
+        let mut new_events = vec![];
+        for invoice in self.invoices.values_mut() {
+            for payment in payments_by_address
+                .get(&invoice.address)
+                .into_iter()
+                .flatten()
+            {
+                let is_new = !invoice.payments.iter().any(|known| {
+                    known.transaction_id == payment.transaction_id && known.index == payment.index
+                });
+                if is_new {
+                    invoice.payments.push(payment.clone());
+                    changed = true;
+                }
+            }
+
+            let received = invoice.received();
+            let state = invoice.state.next(
+                invoice.amount,
+                received,
+                invoice.expires_at_unix_millis,
+                now_unix_millis,
+            );
+            if state != invoice.state {
+                new_events.push(InvoiceEvent {
+                    sequence: self.next_event_sequence,
+                    invoice_id: invoice.id,
+                    previous_state: invoice.state,
+                    state,
+                    received,
+                    at_unix_millis: now_unix_millis,
+                });
+                self.next_event_sequence += 1;
+                invoice.state = state;
+                changed = true;
+            }
+        }
+
+        self.events.extend(new_events.iter().cloned());
+        while self.events.len() > MAX_RETAINED_EVENTS {
+            self.events.pop_front();
+        }
+        if changed {
+            self.save()?;
+        }
+        Ok(new_events)
+    }
+
+    fn save(&self) -> WalletResult<()> {
+        let Some(file_path) = &self.file_path else {
+            return Ok(());
+        };
+        let invoices = InvoicesJson {
+            next_invoice_id: self.next_invoice_id,
+            next_event_sequence: self.next_event_sequence,
+            invoices: self.invoices.values().cloned().collect(),
+            events: self.events.iter().cloned().collect(),
+        };
+        write_json_file(file_path, INVOICES_FILE_KIND, &invoices)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "kaspa:invoice";
+
+    fn payment(byte: u8, amount: u64) -> InvoicePayment {
+        InvoicePayment {
+            transaction_id: hex::encode([byte; 32]),
+            index: 0,
+            amount,
+        }
+    }
+
+    fn payments(payments: Vec<InvoicePayment>) -> HashMap<String, Vec<InvoicePayment>> {
+        HashMap::from([(ADDRESS.to_string(), payments)])
+    }
+
+    #[test]
+    fn state_follows_received_amount_and_expiry() {
+        use InvoiceState::*;
+        let expires_at = Some(1_000);
+        assert_eq!(Unpaid.next(100, 0, expires_at, 0), Unpaid);
+        assert_eq!(Unpaid.next(100, 40, expires_at, 0), PartiallyPaid);
+        assert_eq!(PartiallyPaid.next(100, 100, expires_at, 0), Paid);
+        assert_eq!(Paid.next(100, 101, expires_at, 0), Overpaid);
+        assert_eq!(PartiallyPaid.next(100, 40, expires_at, 1_000), Expired);
+        assert_eq!(Unpaid.next(100, 0, None, u64::MAX), Unpaid);
+        // Paid invoices do not expire; expired ones are not revived by late payments.
+        assert_eq!(Paid.next(100, 100, expires_at, 2_000), Paid);
+        assert_eq!(Expired.next(100, 100, expires_at, 2_000), Expired);
+        // A full payment seen in the poll that also notices the expiry still counts.
+        assert_eq!(Unpaid.next(100, 100, expires_at, 2_000), Paid);
+    }
+
+    #[test]
+    fn update_emits_an_event_per_state_change() {
+        let mut store = InvoiceStore::in_memory();
+        let invoice = store
+            .create(ADDRESS.to_string(), 100, String::new(), 0, None)
+            .unwrap();
+
+        let events = store.update(&payments(vec![payment(1, 40)]), 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].previous_state, InvoiceState::Unpaid);
+        assert_eq!(events[0].state, InvoiceState::PartiallyPaid);
+        assert_eq!(events[0].received, 40);
+
+        // Nothing new: no event.
+        assert!(
+            store
+                .update(&payments(vec![payment(1, 40)]), 20)
+                .unwrap()
+                .is_empty()
+        );
+
+        // The first UTXO was spent meanwhile; it still counts towards the invoice.
+        let events = store.update(&payments(vec![payment(2, 60)]), 30).unwrap();
+        assert_eq!(events[0].state, InvoiceState::Paid);
+        assert_eq!(events[0].sequence, 2);
+        assert_eq!(store.get(invoice.id).unwrap().received(), 100);
+
+        assert_eq!(store.events_after(0, usize::MAX).len(), 2);
+        assert_eq!(store.events_after(1, usize::MAX)[0].sequence, 2);
+        assert!(store.get(invoice.id + 1).is_err());
+    }
+
+    #[test]
+    fn invoices_persist_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invoices.json");
+        let path = path.to_str().unwrap();
+
+        let mut store = InvoiceStore::load(path).unwrap();
+        let invoice = store
+            .create(ADDRESS.to_string(), 100, "order 42".to_string(), 0, Some(5))
+            .unwrap();
+        store.update(&HashMap::new(), 5).unwrap();
+
+        let mut reloaded = InvoiceStore::load(path).unwrap();
+        let reloaded_invoice = reloaded.get(invoice.id).unwrap();
+        assert_eq!(reloaded_invoice.memo, "order 42");
+        assert_eq!(reloaded_invoice.state, InvoiceState::Expired);
+        assert_eq!(reloaded.events_after(0, usize::MAX).len(), 1);
+        let next = reloaded
+            .create(ADDRESS.to_string(), 1, String::new(), 0, None)
+            .unwrap();
+        assert_eq!(next.id, invoice.id + 1);
+    }
+}
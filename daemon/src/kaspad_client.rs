@@ -1,7 +1,10 @@
 use common::error_location::ErrorLocation;
 use common::errors::{RpcError, WalletError, WalletResult};
+use common::status_classify::{classify_kaspad_rpc_error, is_mempool_entry_not_found};
 use kaspa_consensus_core::network::NetworkId;
+use kaspa_consensus_core::tx::TransactionId;
 use kaspa_grpc_client::GrpcClient;
+use kaspa_wallet_core::rpc::RpcApi;
 use tracing::info;
 
 pub async fn connect(server: &Option<String>, network_id: &NetworkId) -> WalletResult<GrpcClient> {
@@ -26,3 +29,23 @@ pub async fn connect(server: &Option<String>, network_id: &NetworkId) -> WalletR
 
     Ok(client)
 }
+
+/// Whether kaspad holds `transaction_id` in its mempool. Only an explicit not-found answer is
+/// `false`; a failed call is an error, since callers act on absence by releasing or resubmitting
+/// the transaction's inputs.
+pub async fn is_in_mempool(
+    kaspa_client: &GrpcClient,
+    transaction_id: TransactionId,
+) -> WalletResult<bool> {
+    match kaspa_client
+        .get_mempool_entry(transaction_id, true, false)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) if is_mempool_entry_not_found(&e, transaction_id) => Ok(false),
+        Err(e) => Err(WalletError::from(classify_kaspad_rpc_error(
+            "get_mempool_entry",
+            e,
+        ))),
+    }
+}
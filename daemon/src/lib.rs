@@ -1,11 +1,24 @@
+pub mod address_book;
 pub mod address_manager;
 pub mod args;
+pub mod coin_selection;
+pub mod confirmation_tracker;
 pub mod daemon;
+pub mod dust_policy;
+pub mod external_keys;
+pub mod frozen_utxos;
+pub mod idempotency;
+pub mod invoice_tracker;
+pub mod invoices;
 pub mod kaspad_client;
 pub mod log;
+pub mod rebroadcast_manager;
 pub mod service;
+pub mod subnetworks;
 pub mod sync_manager;
 pub mod transaction_generator;
+pub mod used_addresses;
 pub mod utxo_manager;
+pub mod utxo_reservations;
 
 pub use daemon::Daemon;
@@ -23,22 +23,40 @@ async fn main() {
 
     let daemon = Daemon::new(args.clone());
 
-    let (sync_manager_handle, server_handle) = match daemon.start().await {
+    let handles = match daemon.start().await {
         Err(e) => {
             error!("{}", e);
             return;
         }
-        Ok((sync_manager_handle, server_handle)) => (sync_manager_handle, server_handle),
+        Ok(handles) => handles,
     };
 
     select! {
-        result = sync_manager_handle => {
+        result = handles.sync_manager => {
             if let Err(e) = result {
                 panic!("Error from sync manager: {}", e);
             }
             info!("Sync manager has finished");
         }
-        result = server_handle => {
+        result = handles.rebroadcast_manager => {
+            if let Err(e) = result {
+                panic!("Error from rebroadcast manager: {}", e);
+            }
+            info!("Rebroadcast manager has finished");
+        }
+        result = handles.confirmation_tracker => {
+            if let Err(e) = result {
+                panic!("Error from confirmation tracker: {}", e);
+            }
+            info!("Confirmation tracker has finished");
+        }
+        result = handles.invoice_tracker => {
+            if let Err(e) = result {
+                panic!("Error from invoice tracker: {}", e);
+            }
+            info!("Invoice tracker has finished");
+        }
+        result = handles.server => {
             if let Err(e) = result {
                 panic!("Error from server: {}", e);
             }
@@ -0,0 +1,336 @@
+use crate::address_manager::AddressManager;
+use crate::kaspad_client::is_in_mempool;
+use crate::utxo_manager::{TrackedMempoolTransaction, UtxoManager};
+use common::error_location::ErrorLocation;
+use common::errors::{RpcError, WalletResult};
+use common::model::WalletOutpoint;
+use common::status_classify::classify_submit_rpc_error;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_wallet_core::rpc::RpcApi;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+/// Rebroadcast attempts after which a failing transaction is reported as persistently failing.
+const PERSISTENT_FAILURE_ATTEMPTS: u32 = 5;
+
+/// Periodically resubmits tracked wallet transactions that kaspad no longer has in its mempool
+/// (e.g. after a kaspad restart or an eviction) and that have not been accepted.
+pub struct RebroadcastManager {
+    kaspa_client: Arc<GrpcClient>,
+    address_manager: Arc<Mutex<AddressManager>>,
+    utxo_manager: Arc<Mutex<UtxoManager>>,
+
+    rebroadcast_interval_millis: u64,
+}
+
+impl RebroadcastManager {
+    pub fn new(
+        kaspa_client: Arc<GrpcClient>,
+        address_manager: Arc<Mutex<AddressManager>>,
+        utxo_manager: Arc<Mutex<UtxoManager>>,
+        rebroadcast_interval_millis: u64,
+    ) -> Self {
+        Self {
+            kaspa_client,
+            address_manager,
+            utxo_manager,
+            rebroadcast_interval_millis,
+        }
+    }
+
+    pub fn start(rebroadcast_manager: Arc<RebroadcastManager>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if rebroadcast_manager.rebroadcast_interval_millis == 0 {
+                info!("Rebroadcast of dropped wallet transactions is disabled");
+                return;
+            }
+            rebroadcast_manager.rebroadcast_loop().await;
+        })
+    }
+
+    async fn rebroadcast_loop(&self) {
+        let mut interval = interval(core::time::Duration::from_millis(
+            self.rebroadcast_interval_millis,
+        ));
+        // The first tick completes immediately; nothing has been submitted yet.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+
+            // A failed pass (e.g. kaspad briefly unreachable) is retried on the next tick rather
+            // than taking the daemon down.
+            if let Err(e) = self.rebroadcast().await {
+                warn!("Rebroadcast pass failed: {}", e);
+            }
+        }
+    }
+
+    async fn rebroadcast(&self) -> WalletResult<()> {
+        // The RPCs below run without the UTXO manager lock so that sends, balance queries and
+        // syncs are not blocked for the whole pass; it is only re-taken to record attempts.
+        let tracked_transactions =
+            dependency_order(self.utxo_manager.lock().await.mempool_transactions());
+        if tracked_transactions.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "Checking {} tracked transactions for rebroadcast",
+            tracked_transactions.len()
+        );
+
+        let tracked_ids: HashSet<TransactionId> = tracked_transactions
+            .iter()
+            .map(TrackedMempoolTransaction::id)
+            .collect();
+        let unspent_outpoints = self.unspent_outpoints(&tracked_transactions).await?;
+
+        // Transactions known to be in kaspad's mempool, either already or after resubmission.
+        // A child is only resubmitted if each parent is here or already confirmed.
+        let mut available: HashSet<TransactionId> = HashSet::new();
+        for tracked_transaction in &tracked_transactions {
+            let transaction_id = tracked_transaction.id();
+            // A failed lookup aborts the pass: treating it as missing would resubmit every
+            // tracked transaction whenever kaspad is unreachable.
+            if !is_rebroadcast_candidate(tracked_transaction)
+                || is_in_mempool(&self.kaspa_client, transaction_id).await?
+            {
+                available.insert(transaction_id);
+                continue;
+            }
+
+            let tx = &tracked_transaction.transaction.transaction.inner().tx;
+            let inputs_available = tx.inputs.iter().all(|input| {
+                let outpoint = input.previous_outpoint;
+                if tracked_ids.contains(&outpoint.transaction_id) {
+                    available.contains(&outpoint.transaction_id)
+                } else {
+                    unspent_outpoints.contains(&outpoint.into())
+                }
+            });
+            if !inputs_available {
+                // Accepted, double-spent or waiting on a parent that could not be resubmitted;
+                // the next sync settles which.
+                debug!(
+                    tx_id = %transaction_id,
+                    "not rebroadcasting: inputs are no longer available"
+                );
+                continue;
+            }
+
+            // Accepted, replaced or abandoned since the snapshot: resubmitting it now would spend
+            // inputs the wallet may already have released.
+            if self
+                .utxo_manager
+                .lock()
+                .await
+                .mempool_transaction(&transaction_id)
+                .is_none()
+            {
+                continue;
+            }
+
+            match self.kaspa_client.submit_transaction(tx.into(), false).await {
+                Ok(_) => {
+                    info!(
+                        tx_id = %transaction_id,
+                        attempts = tracked_transaction.rebroadcast_attempts + 1,
+                        "rebroadcast wallet transaction missing from kaspad's mempool"
+                    );
+                    self.utxo_manager
+                        .lock()
+                        .await
+                        .record_rebroadcast_attempt(&transaction_id, None);
+                    available.insert(transaction_id);
+                }
+                Err(rpc_err) => {
+                    let classified = classify_submit_rpc_error(transaction_id, rpc_err);
+                    let attempts = tracked_transaction.rebroadcast_attempts + 1;
+                    if attempts >= PERSISTENT_FAILURE_ATTEMPTS {
+                        error!(
+                            tx_id = %transaction_id,
+                            attempts,
+                            error_kind = classified.kind_name(),
+                            "rebroadcast is persistently failing: {}",
+                            classified.user_message()
+                        );
+                    } else {
+                        warn!(
+                            tx_id = %transaction_id,
+                            attempts,
+                            error_kind = classified.kind_name(),
+                            "rebroadcast failed: {}",
+                            classified.user_message()
+                        );
+                    }
+                    self.utxo_manager.lock().await.record_rebroadcast_attempt(
+                        &transaction_id,
+                        Some(classified.user_message()),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The consensus-unspent outpoints among the inputs of `tracked_transactions` that do not
+    /// come from another tracked transaction.
+    async fn unspent_outpoints(
+        &self,
+        tracked_transactions: &[TrackedMempoolTransaction],
+    ) -> WalletResult<HashSet<WalletOutpoint>> {
+        let mut addresses = HashMap::new();
+        {
+            let address_manager = self.address_manager.lock().await;
+            for tracked_transaction in tracked_transactions {
+                for wallet_address in &tracked_transaction.transaction.address_by_input_index {
+                    if addresses.contains_key(wallet_address) {
+                        continue;
+                    }
+                    let address = address_manager
+                        .kaspa_address_from_wallet_address(wallet_address, true)
+                        .await?;
+                    addresses.insert(wallet_address.clone(), address);
+                }
+            }
+        }
+        if addresses.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let utxo_entries = self
+            .kaspa_client
+            .get_utxos_by_addresses(addresses.into_values().collect())
+            .await
+            .map_err(|e| RpcError::Transport {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            })?;
+
+        Ok(utxo_entries
+            .into_iter()
+            .map(|entry| entry.outpoint.into())
+            .collect())
+    }
+}
+
+/// Whether the last sync found `tracked_transaction` missing from kaspad's mempool, including
+/// after kaspad had held it. Others are known to be in the mempool and are not looked up.
+pub(crate) fn is_rebroadcast_candidate(tracked_transaction: &TrackedMempoolTransaction) -> bool {
+    tracked_transaction.missing_since_daa_score.is_some()
+}
+
+/// Order `transactions` so that every transaction comes after the tracked transactions whose
+/// outputs it spends. Relative order is otherwise preserved.
+pub(crate) fn dependency_order(
+    transactions: Vec<TrackedMempoolTransaction>,
+) -> Vec<TrackedMempoolTransaction> {
+    let ids: HashSet<TransactionId> = transactions
+        .iter()
+        .map(TrackedMempoolTransaction::id)
+        .collect();
+
+    let mut ordered = Vec::with_capacity(transactions.len());
+    let mut emitted: HashSet<TransactionId> = HashSet::new();
+    let mut remaining = transactions;
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) =
+            remaining.into_iter().partition(|tracked_transaction| {
+                tracked_transaction
+                    .transaction
+                    .transaction
+                    .inner()
+                    .tx
+                    .inputs
+                    .iter()
+                    .map(|input| input.previous_outpoint.transaction_id)
+                    .all(|parent| !ids.contains(&parent) || emitted.contains(&parent))
+            });
+        if ready.is_empty() {
+            // Unreachable for valid transactions (a cycle would need a hash collision); keep
+            // the remaining order rather than looping forever.
+            ordered.extend(blocked);
+            break;
+        }
+        emitted.extend(ready.iter().map(TrackedMempoolTransaction::id));
+        ordered.extend(ready);
+        remaining = blocked;
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::model::{Keychain, WalletAddress, WalletSignableTransaction};
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{
+        SignableTransaction, Transaction, TransactionInput, TransactionOutpoint,
+    };
+
+    fn tracked(parent: TransactionId, nonce: u64) -> TrackedMempoolTransaction {
+        let tx = Transaction::new(
+            0,
+            vec![TransactionInput::new(
+                TransactionOutpoint::new(parent, 0),
+                vec![],
+                0,
+                1,
+            )],
+            vec![],
+            nonce,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        TrackedMempoolTransaction {
+            transaction: WalletSignableTransaction::new_from_unsigned(
+                SignableTransaction::new(tx),
+                vec![],
+                vec![WalletAddress::new(0, 0, Keychain::External)],
+                vec![],
+            ),
+            submitted_daa_score: 0,
+            rebroadcast_attempts: 0,
+            last_rebroadcast_error: None,
+            missing_since_daa_score: None,
+        }
+    }
+
+    #[test]
+    fn dependency_order_puts_parents_before_children() {
+        let external = kaspa_hashes::Hash::from_bytes([1u8; 32]);
+        let parent = tracked(external, 0);
+        let child = tracked(parent.id(), 1);
+        let grandchild = tracked(child.id(), 2);
+
+        let ordered = dependency_order(vec![grandchild.clone(), child.clone(), parent.clone()]);
+        let ordered_ids: Vec<TransactionId> = ordered.iter().map(|t| t.id()).collect();
+        assert_eq!(ordered_ids, vec![parent.id(), child.id(), grandchild.id()]);
+    }
+
+    #[test]
+    fn transactions_evicted_after_being_seen_are_rebroadcast_candidates() {
+        let mut transaction = tracked(kaspa_hashes::Hash::from_bytes([1u8; 32]), 0);
+        assert!(!is_rebroadcast_candidate(&transaction));
+
+        // A sync that no longer finds it in kaspad's mempool marks it missing.
+        transaction.missing_since_daa_score = Some(1_000);
+        assert!(is_rebroadcast_candidate(&transaction));
+    }
+
+    #[test]
+    fn dependency_order_preserves_order_of_independent_transactions() {
+        let first = tracked(kaspa_hashes::Hash::from_bytes([1u8; 32]), 0);
+        let second = tracked(kaspa_hashes::Hash::from_bytes([2u8; 32]), 0);
+
+        let ordered = dependency_order(vec![first.clone(), second.clone()]);
+        let ordered_ids: Vec<TransactionId> = ordered.iter().map(|t| t.id()).collect();
+        assert_eq!(ordered_ids, vec![first.id(), second.id()]);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::kaspad_client::is_in_mempool;
+use crate::service::common::parse_transaction_id;
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{TransactionError, UserInputError, WalletError, WalletResult};
+use proto::kaswallet_proto::{AbandonTransactionRequest, AbandonTransactionResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn abandon_transaction(
+        &self,
+        request: AbandonTransactionRequest,
+    ) -> WalletResult<AbandonTransactionResponse> {
+        let transaction_id = parse_transaction_id(&request.transaction_id)?;
+
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        if utxo_manager.mempool_transaction(&transaction_id).is_none() {
+            return Err(WalletError::from(TransactionError::TransactionNotFound {
+                tx_id: transaction_id,
+                location: ErrorLocation::capture(),
+            }));
+        }
+        // Releasing the inputs of a transaction kaspad still holds would only make the next
+        // spend of them fail as a double spend.
+        if is_in_mempool(&self.kaspa_client, transaction_id).await? {
+            return Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: format!(
+                    "transaction {transaction_id} is still in kaspad's mempool and cannot be abandoned"
+                ),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        let abandoned_transaction_ids = utxo_manager.abandon_mempool_transaction(&transaction_id);
+        drop(utxo_manager);
+        for abandoned_transaction_id in &abandoned_transaction_ids {
+            self.confirmation_tracker
+                .mark_rejected(abandoned_transaction_id, "abandoned".to_string())
+                .await;
+        }
+        info!(
+            tx_id = %transaction_id,
+            abandoned = ?abandoned_transaction_ids,
+            "pending transaction abandoned"
+        );
+
+        Ok(AbandonTransactionResponse {
+            abandoned_transaction_ids: abandoned_transaction_ids
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use crate::service::common::parse_transaction_id;
+use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::transaction_fee;
+use common::error_location::ErrorLocation;
+use common::errors::{TransactionError, WalletError, WalletResult};
+use common::model::WalletUtxo;
+use kaspa_wallet_core::rpc::RpcApi;
+use proto::kaswallet_proto::{AccelerateRequest, AccelerateResponse};
+use secrecy::SecretString;
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn accelerate(
+        &self,
+        request: AccelerateRequest,
+    ) -> WalletResult<AccelerateResponse> {
+        self.check_is_synced().await?;
+        let parent_transaction_id = parse_transaction_id(&request.transaction_id)?;
+
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        // Deliberately spend the mempool-receiving outputs `is_utxo_unspendable` refuses to
+        // select: the child is only useful while the parent is still pending. Frozen or reserved
+        // outputs stay untouched.
+        let parent_outputs: Vec<WalletUtxo> = utxo_manager
+            .utxos_sorted_by_amount()
+            .into_iter()
+            .filter(|utxo| {
+                utxo.outpoint.transaction_id == parent_transaction_id
+                    && utxo.utxo_entry.is_unconfirmed
+                    && !utxo_manager.is_utxo_held_back(&utxo.outpoint)
+            })
+            .collect();
+        if parent_outputs.is_empty() {
+            return Err(WalletError::from(TransactionError::TransactionNotFound {
+                tx_id: parent_transaction_id,
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        // The wallet's view may be one sync behind kaspad: make sure the parent is still in the
+        // mempool, otherwise the child would be rejected as an orphan.
+        let parent_entry = self
+            .kaspa_client
+            .get_mempool_entry(parent_transaction_id, false, false)
+            .await
+            .map_err(|e| {
+                WalletError::from(TransactionError::BuildFailed {
+                    reason: format!(
+                        "parent transaction {parent_transaction_id} is not in kaspad's mempool: {e}"
+                    ),
+                    location: ErrorLocation::capture(),
+                })
+            })?;
+        if parent_entry.is_orphan {
+            return Err(WalletError::from(TransactionError::Orphan {
+                tx_id: parent_transaction_id,
+                location: ErrorLocation::capture(),
+            }));
+        }
+        let parent_mass = parent_entry
+            .transaction
+            .verbose_data
+            .as_ref()
+            .map(|verbose_data| verbose_data.compute_mass)
+            .filter(|compute_mass| *compute_mass > 0)
+            .unwrap_or(parent_entry.transaction.mass);
+
+        let unsigned_transaction = {
+            let transaction_generator = self.transaction_generator.lock().await;
+            transaction_generator
+                .create_child_pays_for_parent_transaction(
+                    &parent_outputs,
+                    parent_entry.fee,
+                    parent_mass,
+                    request.target_fee_rate,
+                )
+                .await?
+        };
+
+        let password = SecretString::from(request.password);
+        let signed_transactions = self
+            .sign_transactions(vec![unsigned_transaction], &password)
+            .await?;
+        let transaction_ids = self
+            .submit_transactions(&mut utxo_manager, &signed_transactions)
+            .await?;
+
+        let fee = transaction_fee(signed_transactions[0].transaction.inner());
+        info!(
+            parent_tx_id = %parent_transaction_id,
+            tx_id = %signed_transactions[0].transaction.inner().id(),
+            fee_sompi = fee,
+            "parent accelerated with child-pays-for-parent"
+        );
+
+        Ok(AccelerateResponse {
+            transaction_id: transaction_ids.into_iter().next().unwrap_or_default(),
+            fee,
+            signed_transaction: signed_transactions.into_iter().next().map(Into::into),
+        })
+    }
+}
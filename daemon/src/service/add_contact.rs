@@ -0,0 +1,39 @@
+use crate::service::common::parse_address;
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use proto::kaswallet_proto::{AddContactRequest, AddContactResponse, Contact as ProtoContact};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn add_contact(
+        &self,
+        request: AddContactRequest,
+    ) -> WalletResult<AddContactResponse> {
+        let address = parse_address(&request.address)?;
+        let prefix = self.address_manager.lock().await.prefix();
+        if address.prefix != prefix {
+            return Err(WalletError::from(UserInputError::InvalidAddress {
+                input: request.address,
+                reason: format!("Address is not on the {prefix} network"),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        let contact = self.address_book.lock().await.add_contact(
+            &request.alias,
+            &address.to_string(),
+            &request.note,
+        )?;
+        info!("Added contact {} for {}", request.alias, contact.address);
+
+        Ok(AddContactResponse {
+            contact: Some(ProtoContact {
+                alias: request.alias,
+                address: contact.address,
+                note: contact.note,
+                created_at_unix_millis: contact.created_at_unix_millis,
+            }),
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use crate::service::kaswallet_service::KasWalletService;
 use common::errors::WalletResult;
 use common::model::WalletSignableTransaction;
+use itertools::Itertools;
 use proto::kaswallet_proto::{BroadcastRequest, BroadcastResponse};
+use tracing::info;
 
 impl KasWalletService {
     pub(crate) async fn broadcast(
@@ -14,11 +16,55 @@ impl KasWalletService {
             .map(WalletSignableTransaction::try_from)
             .collect::<WalletResult<Vec<_>>>()?;
 
-        let mut utxo_manager = self.utxo_manager.lock().await;
-        let transaction_ids = self
-            .submit_transactions(&mut utxo_manager, &signed_transactions)
-            .await?;
+        // Transaction ids do not cover signature scripts, so re-signing the same transactions
+        // still counts as the same request.
+        let fingerprint = signed_transactions
+            .iter()
+            .map(|transaction| transaction.transaction.inner().id())
+            .join(",");
+        let (transaction_ids, idempotent_replay) = {
+            let mut utxo_manager = self.utxo_manager.lock().await;
+            match self
+                .idempotent_transaction_ids(&request.idempotency_key, &fingerprint)
+                .await?
+            {
+                Some(transaction_ids) => {
+                    info!(
+                        idempotency_key = %request.idempotency_key,
+                        "Broadcast request repeats an earlier one; returning its transactions"
+                    );
+                    (transaction_ids, true)
+                }
+                None => {
+                    let transaction_ids = self
+                        .submit_transactions_idempotently(
+                            &mut utxo_manager,
+                            &signed_transactions,
+                            &request.idempotency_key,
+                            &fingerprint,
+                        )
+                        .await?;
+                    (transaction_ids, false)
+                }
+            }
+        };
 
-        Ok(BroadcastResponse { transaction_ids })
+        let statuses = match request.wait_for_confirmations {
+            Some(depth) => {
+                self.wait_for_transaction_confirmations(
+                    &transaction_ids,
+                    depth,
+                    request.wait_timeout_millis,
+                )
+                .await?
+            }
+            None => vec![],
+        };
+
+        Ok(BroadcastResponse {
+            transaction_ids,
+            statuses,
+            idempotent_replay,
+        })
     }
 }
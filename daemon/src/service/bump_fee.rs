@@ -0,0 +1,68 @@
+use crate::service::common::parse_transaction_id;
+use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::transaction_fee;
+use common::error_location::ErrorLocation;
+use common::errors::{TransactionError, WalletError, WalletResult};
+use proto::kaswallet_proto::{BumpFeeRequest, BumpFeeResponse};
+use secrecy::SecretString;
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn bump_fee(&self, request: BumpFeeRequest) -> WalletResult<BumpFeeResponse> {
+        self.check_is_synced().await?;
+        let transaction_id = parse_transaction_id(&request.transaction_id)?;
+
+        // Hold the UTXO manager from lookup through submit, so a sync cannot drop or re-apply the
+        // original transaction while its replacement is being built.
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        let original_transaction = utxo_manager
+            .mempool_transaction(&transaction_id)
+            .ok_or_else(|| {
+                WalletError::from(TransactionError::TransactionNotFound {
+                    tx_id: transaction_id,
+                    location: ErrorLocation::capture(),
+                })
+            })?;
+        if utxo_manager.has_mempool_descendants(&transaction_id) {
+            // Replacing the parent would orphan the wallet's own children.
+            return Err(WalletError::from(TransactionError::BuildFailed {
+                reason: format!(
+                    "transaction {transaction_id} has unconfirmed wallet transactions spending its outputs"
+                ),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        let unsigned_transaction = {
+            let transaction_generator = self.transaction_generator.lock().await;
+            transaction_generator
+                .create_fee_bump_transaction(&original_transaction, request.fee_policy)
+                .await?
+        };
+
+        let password = SecretString::from(request.password);
+        let replacement = self
+            .sign_transactions(vec![unsigned_transaction], &password)
+            .await?
+            .remove(0);
+
+        let replacement_transaction_id = self
+            .submit_replacement_transaction(&mut utxo_manager, &transaction_id, &replacement)
+            .await?;
+
+        let fee = transaction_fee(replacement.transaction.inner());
+        info!(
+            replaced_tx_id = %transaction_id,
+            tx_id = %replacement_transaction_id,
+            fee_sompi = fee,
+            "fee bumped"
+        );
+
+        Ok(BumpFeeResponse {
+            transaction_id: replacement_transaction_id,
+            replaced_transaction_id: request.transaction_id,
+            fee,
+            signed_transaction: Some(replacement.into()),
+        })
+    }
+}
@@ -1,14 +1,62 @@
 use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::{fee_bucket_for_priority, transaction_fee};
 use crate::utxo_manager::UtxoManager;
 use common::error_location::ErrorLocation;
-use common::errors::{RpcError, SyncError, TransactionError, WalletError, WalletResult};
-use common::model::WalletSignableTransaction;
+use common::errors::{
+    RpcError, SyncError, TransactionError, UserInputError, WalletError, WalletResult,
+};
 use common::model::WalletSigned;
+use common::model::{WalletSignableTransaction, WalletUtxo};
 use common::status_classify::classify_submit_rpc_error;
+use kaspa_addresses::{Address, Version};
+use kaspa_bip32::secp256k1::XOnlyPublicKey;
+use kaspa_consensus_core::tx::TransactionId;
 use kaspa_wallet_core::rpc::RpcApi;
+use proto::kaswallet_proto::{FeePriority, TransactionDescription};
+use std::str::FromStr;
 use tokio::sync::MutexGuard;
 use tracing::{error, info, warn};
 
+/// Parse a hex transaction id supplied over the wire.
+pub(crate) fn parse_transaction_id(input: &str) -> WalletResult<TransactionId> {
+    TransactionId::from_str(input).map_err(|_| {
+        WalletError::from(UserInputError::InvalidTransactionId {
+            input: input.to_string(),
+            location: ErrorLocation::capture(),
+        })
+    })
+}
+
+/// Parse an address supplied over the wire.
+pub(crate) fn parse_address(input: &str) -> WalletResult<Address> {
+    Address::try_from(input.trim()).map_err(|e| {
+        WalletError::from(UserInputError::InvalidAddress {
+            input: input.to_string(),
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })
+    })
+}
+
+/// The schnorr public key a P2PK address pays to. Other address versions carry no key that can
+/// sign a message.
+pub(crate) fn address_schnorr_public_key(address: &Address) -> WalletResult<XOnlyPublicKey> {
+    if address.version != Version::PubKey {
+        return Err(WalletError::from(UserInputError::InvalidAddress {
+            input: address.to_string(),
+            reason: "only schnorr P2PK addresses can sign messages".to_string(),
+            location: ErrorLocation::capture(),
+        }));
+    }
+    XOnlyPublicKey::from_slice(address.payload.as_slice()).map_err(|e| {
+        WalletError::from(UserInputError::InvalidAddress {
+            input: address.to_string(),
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        })
+    })
+}
+
 impl KasWalletService {
     pub(crate) async fn get_virtual_daa_score(&self) -> WalletResult<u64> {
         let block_dag_info =
@@ -23,6 +71,63 @@ impl KasWalletService {
         Ok(block_dag_info.virtual_daa_score)
     }
 
+    /// The fee rate fee-relative dust thresholds are measured at: the first normal bucket, or the
+    /// priority bucket when kaspad reports no normal buckets.
+    pub(crate) async fn dust_reference_fee_rate(&self) -> WalletResult<f64> {
+        let fee_estimate =
+            self.kaspa_client
+                .get_fee_estimate()
+                .await
+                .map_err(|e| RpcError::Transport {
+                    reason: e.to_string(),
+                    location: ErrorLocation::capture(),
+                })?;
+        Ok(fee_bucket_for_priority(&fee_estimate, FeePriority::Normal).feerate)
+    }
+
+    /// Whether `utxo` is dust under the daemon's dust policy, at `fee_rate` for fee-relative
+    /// thresholds.
+    pub(crate) async fn is_utxo_dust(
+        &self,
+        utxo: &WalletUtxo,
+        fee_rate: f64,
+    ) -> WalletResult<bool> {
+        let amount = utxo.utxo_entry.amount;
+        if !self.dust_policy.is_fee_relative() {
+            return Ok(self.dust_policy.is_dust(amount, 0));
+        }
+        let transaction_generator = self.transaction_generator.lock().await;
+        let mass = transaction_generator
+            .estimate_mass(
+                &vec![utxo.clone()],
+                amount,
+                None,
+                &[],
+                self.allowed_subnetworks.default_subnetwork_id(),
+            )
+            .await?;
+
+        let fee = ((mass as f64) * fee_rate).ceil() as u64;
+
+        Ok(self.dust_policy.is_dust(amount, fee))
+    }
+
+    /// Replace a contact alias in `to_address` with the contact's address. Anything else,
+    /// including an unknown alias, is left for the transaction generator to validate.
+    pub(crate) async fn resolve_contact_alias(
+        &self,
+        transaction_description: &mut TransactionDescription,
+    ) {
+        // Addresses always carry a prefix; aliases never contain a ':'.
+        if transaction_description.to_address.contains(':') {
+            return;
+        }
+        let address_book = self.address_book.lock().await;
+        if let Some(contact) = address_book.contact(&transaction_description.to_address) {
+            transaction_description.to_address = contact.address.clone();
+        }
+    }
+
     pub(crate) async fn check_is_synced(&self) -> WalletResult<()> {
         if !self.sync_manager.is_synced().await {
             // Wallet has not yet completed initial UTXO sync — a transient
@@ -37,17 +142,102 @@ impl KasWalletService {
         }
     }
 
+    /// The transaction ids recorded for `idempotency_key`, if a request with it already
+    /// succeeded. An empty key disables the lookup.
+    pub(crate) async fn idempotent_transaction_ids(
+        &self,
+        idempotency_key: &str,
+        fingerprint: &str,
+    ) -> WalletResult<Option<Vec<String>>> {
+        if idempotency_key.is_empty() {
+            return Ok(None);
+        }
+        self.idempotency_store
+            .lock()
+            .await
+            .lookup(idempotency_key, fingerprint)
+    }
+
+    /// Remember the transactions submitted for a request under `idempotency_key`.
+    async fn record_idempotency_key(
+        &self,
+        idempotency_key: &str,
+        fingerprint: &str,
+        transaction_ids: &[String],
+    ) -> WalletResult<()> {
+        if idempotency_key.is_empty() {
+            return Ok(());
+        }
+        self.idempotency_store
+            .lock()
+            .await
+            .record(idempotency_key, fingerprint, transaction_ids)
+    }
+
+    /// Submit `signed_transactions` and record them under `idempotency_key`. If a submit fails
+    /// partway, the transactions already submitted are recorded before the error is returned,
+    /// so a retry under the same key does not pay them again.
+    pub(crate) async fn submit_transactions_idempotently(
+        &self,
+        utxo_manager: &mut MutexGuard<'_, UtxoManager>,
+        signed_transactions: &Vec<WalletSignableTransaction>,
+        idempotency_key: &str,
+        fingerprint: &str,
+    ) -> WalletResult<Vec<String>> {
+        let mut transaction_ids = vec![];
+        let submitted = self
+            .submit_transactions_into(utxo_manager, signed_transactions, &mut transaction_ids)
+            .await;
+        if !transaction_ids.is_empty() {
+            if submitted.is_err() {
+                warn!(
+                    idempotency_key,
+                    transaction_ids = ?transaction_ids,
+                    "recording the transactions submitted before a failed submit"
+                );
+            }
+            // Logged rather than returned: the transactions are already submitted, and an error
+            // would invite the retry the key is meant to make safe.
+            if let Err(e) = self
+                .record_idempotency_key(idempotency_key, fingerprint, &transaction_ids)
+                .await
+            {
+                error!(
+                    idempotency_key,
+                    transaction_ids = ?transaction_ids,
+                    error_kind = e.kind_name(),
+                    "failed to record idempotency key: {}",
+                    e.user_message()
+                );
+            }
+        }
+        submitted.map(|()| transaction_ids)
+    }
+
     pub(crate) async fn submit_transactions(
         &self,
         utxo_manager: &mut MutexGuard<'_, UtxoManager>,
         signed_transactions: &Vec<WalletSignableTransaction>,
     ) -> WalletResult<Vec<String>> {
+        let mut transaction_ids = vec![];
+        self.submit_transactions_into(utxo_manager, signed_transactions, &mut transaction_ids)
+            .await?;
+        Ok(transaction_ids)
+    }
+
+    /// Submit `signed_transactions` in order, pushing the id of each accepted one onto
+    /// `transaction_ids` and stopping at the first failure.
+    async fn submit_transactions_into(
+        &self,
+        utxo_manager: &mut MutexGuard<'_, UtxoManager>,
+        signed_transactions: &Vec<WalletSignableTransaction>,
+        transaction_ids: &mut Vec<String>,
+    ) -> WalletResult<()> {
         // Bind the guard so it lives for the body, not the statement —
         // `let _ = ...` would drop the MutexGuard immediately and remove
         // the intended serialization across concurrent broadcast/send.
         let _guard = self.submit_transaction_mutex.lock().await;
 
-        let mut transaction_ids = vec![];
         for signed_transaction in signed_transactions {
             // Encode the "must be Fully signed" precondition on the match
             // itself so the type system enforces it. A future reorder
@@ -72,12 +262,7 @@ impl KasWalletService {
             let input_count = tx.tx.inputs.len();
             let output_count = tx.tx.outputs.len();
             let mass = tx.tx.storage_mass();
-            let fee_sompi: u64 = tx
-                .entries
-                .iter()
-                .map(|e| e.as_ref().map(|e| e.amount).unwrap_or(0))
-                .sum::<u64>()
-                .saturating_sub(tx.tx.outputs.iter().map(|o| o.value).sum::<u64>());
+            let fee_sompi = transaction_fee(tx);
             // Capture lane / consensus-version on the tx itself (not from
             // the daemon's configured lane) so the log line truthfully
             // describes what was sent to kaspad even if the two diverge.
@@ -113,6 +298,7 @@ impl KasWalletService {
                     utxo_manager
                         .add_mempool_transaction(signed_transaction)
                         .await;
+                    self.confirmation_tracker.track(tx_id).await;
                 }
                 Err(rpc_err) => {
                     // The kaspa-rpc-core client gives us a typed `RpcError`,
@@ -171,6 +357,63 @@ impl KasWalletService {
             }
         }
 
-        Ok(transaction_ids)
+        Ok(())
+    }
+
+    /// Submit `replacement` through kaspad's replace-by-fee path and swap it for
+    /// `replaced_transaction_id` in the wallet's mempool tracking.
+    pub(crate) async fn submit_replacement_transaction(
+        &self,
+        utxo_manager: &mut MutexGuard<'_, UtxoManager>,
+        replaced_transaction_id: &TransactionId,
+        replacement: &WalletSignableTransaction,
+    ) -> WalletResult<String> {
+        let _guard = self.submit_transaction_mutex.lock().await;
+
+        let tx = match &replacement.transaction {
+            WalletSigned::Fully(tx) => tx,
+            WalletSigned::Partially(_) => {
+                return Err(WalletError::from(TransactionError::NotFullySigned {
+                    location: ErrorLocation::capture(),
+                }));
+            }
+        };
+        self.ensure_subnetwork_id_matches(&tx.tx.subnetwork_id)?;
+
+        let tx_id = tx.tx.id();
+        match self
+            .kaspa_client
+            .submit_transaction_replacement((&tx.tx).into())
+            .await
+        {
+            Ok(response) => {
+                info!(
+                    tx_id = %tx_id,
+                    replaced_tx_id = %replaced_transaction_id,
+                    subnetwork_id = %tx.tx.subnetwork_id,
+                    tx_version = tx.tx.version,
+                    "replacement tx submitted"
+                );
+                utxo_manager
+                    .replace_mempool_transaction(replaced_transaction_id, replacement)
+                    .await;
+                self.confirmation_tracker.track(tx_id).await;
+                self.confirmation_tracker
+                    .mark_rejected(replaced_transaction_id, format!("replaced by {tx_id}"))
+                    .await;
+                Ok(response.transaction_id.to_string())
+            }
+            Err(rpc_err) => {
+                let classified = classify_submit_rpc_error(tx_id, rpc_err);
+                error!(
+                    tx_id = %tx_id,
+                    replaced_tx_id = %replaced_transaction_id,
+                    error_kind = classified.kind_name(),
+                    error_loc = %classified.location(),
+                    "replacement tx submit failed"
+                );
+                Err(WalletError::from(classified))
+            }
+        }
     }
 }
@@ -0,0 +1,56 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{ConsolidateRequest, ConsolidateResponse};
+use secrecy::SecretString;
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn consolidate(
+        &self,
+        request: ConsolidateRequest,
+    ) -> WalletResult<ConsolidateResponse> {
+        self.check_is_synced().await?;
+
+        // Held through submit so a sync or a concurrent send cannot pick the same UTXOs.
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        let unsigned_transactions = {
+            let transaction_generator = self.transaction_generator.lock().await;
+            transaction_generator
+                .create_consolidation_transactions(
+                    &utxo_manager,
+                    request.from_addresses,
+                    request.min_input_value,
+                    request.max_input_value,
+                    request.target_utxo_count.max(1),
+                    request.fee_policy,
+                )
+                .await?
+        };
+
+        if !request.broadcast || unsigned_transactions.is_empty() {
+            // Leased like CreateUnsignedTransactions output, until broadcast through Broadcast.
+            utxo_manager.reserve_transaction_inputs(&unsigned_transactions);
+            return Ok(ConsolidateResponse {
+                transactions: unsigned_transactions.into_iter().map(Into::into).collect(),
+                transaction_ids: vec![],
+            });
+        }
+
+        let password = SecretString::from(request.password);
+        let signed_transactions = self
+            .sign_transactions(unsigned_transactions, &password)
+            .await?;
+        let transaction_ids = self
+            .submit_transactions(&mut utxo_manager, &signed_transactions)
+            .await?;
+        info!(
+            transaction_count = transaction_ids.len(),
+            "consolidation transactions submitted"
+        );
+
+        Ok(ConsolidateResponse {
+            transactions: signed_transactions.into_iter().map(Into::into).collect(),
+            transaction_ids,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::service::get_invoice::invoice_to_proto;
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::json_store::unix_millis_now;
+use proto::kaswallet_proto::{CreateInvoiceRequest, CreateInvoiceResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn create_invoice(
+        &self,
+        request: CreateInvoiceRequest,
+    ) -> WalletResult<CreateInvoiceResponse> {
+        self.check_is_synced().await?;
+
+        if request.amount == 0 {
+            return Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: "invoice amount must be positive".to_string(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+        let expires_at_unix_millis = (request.expiry_seconds > 0)
+            .then(|| unix_millis_now().saturating_add(request.expiry_seconds.saturating_mul(1000)));
+
+        // Every invoice gets an address of its own, so anything paid to it belongs to the invoice.
+        let (address, wallet_address) = {
+            let address_manager = self.address_manager.lock().await;
+            address_manager.new_address().await?
+        };
+        self.sync_manager
+            .collect_issued_addresses(wallet_address.index)
+            .await?;
+
+        let invoice = self
+            .invoice_tracker
+            .create(
+                address,
+                request.amount,
+                request.memo,
+                expires_at_unix_millis,
+            )
+            .await?;
+        info!(
+            "Created invoice {} for {} sompi to {}",
+            invoice.id, invoice.amount, invoice.address
+        );
+
+        Ok(CreateInvoiceResponse {
+            invoice: Some(invoice_to_proto(&invoice)?),
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::FeeLimits;
 use crate::utxo_manager::UtxoManager;
 use common::error_location::ErrorLocation;
 use common::errors::{UserInputError, WalletError, WalletResult};
@@ -21,27 +22,34 @@ impl KasWalletService {
         }
         let transaction_description = request.transaction_description.unwrap();
         let unsigned_transactions: Vec<WalletSignableTransaction>;
+        let fee_limits: FeeLimits;
         {
-            let utxo_manager = self.utxo_manager.lock().await;
-            unsigned_transactions = self
+            let mut utxo_manager = self.utxo_manager.lock().await;
+            (unsigned_transactions, fee_limits) = self
                 .create_unsigned_transactions_from_description(
                     transaction_description,
                     &utxo_manager,
                 )
                 .await?;
+            // Reserved under the same lock as the selection, so a concurrent caller cannot pick
+            // the same inputs in between.
+            utxo_manager.reserve_transaction_inputs(&unsigned_transactions);
         }
 
         Ok(CreateUnsignedTransactionsResponse {
             unsigned_transactions: unsigned_transactions.into_iter().map(Into::into).collect(),
+            applied_fee: Some(fee_limits.into()),
         })
     }
 
     pub(crate) async fn create_unsigned_transactions_from_description(
         &self,
-        transaction_description: TransactionDescription,
+        mut transaction_description: TransactionDescription,
         utxo_manager: &MutexGuard<'_, UtxoManager>,
-    ) -> WalletResult<Vec<WalletSignableTransaction>> {
+    ) -> WalletResult<(Vec<WalletSignableTransaction>, FeeLimits)> {
         self.check_is_synced().await?;
+        self.resolve_contact_alias(&mut transaction_description)
+            .await;
 
         let mut transaction_generator = self.transaction_generator.lock().await;
         transaction_generator
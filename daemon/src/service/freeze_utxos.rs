@@ -0,0 +1,60 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{TransactionError, UserInputError, WalletError, WalletResult};
+use common::model::WalletOutpoint;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+use proto::kaswallet_proto::{FreezeUtxosRequest, FreezeUtxosResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn freeze_utxos(
+        &self,
+        request: FreezeUtxosRequest,
+    ) -> WalletResult<FreezeUtxosResponse> {
+        if request.outpoints.is_empty() {
+            return Err(WalletError::from(UserInputError::MissingField {
+                field: "outpoints",
+                location: ErrorLocation::capture(),
+            }));
+        }
+        let outpoints = request
+            .outpoints
+            .into_iter()
+            .map(WalletOutpoint::try_from)
+            .collect::<WalletResult<Vec<_>>>()?;
+
+        let (frozen_utxos, utxos_by_outpoint) = {
+            let mut utxo_manager = self.utxo_manager.lock().await;
+            let utxos_by_outpoint = utxo_manager.utxos_by_outpoint();
+            // Refuse unknown outpoints so a typo does not silently leave the intended UTXO
+            // spendable.
+            if let Some(outpoint) = outpoints
+                .iter()
+                .find(|outpoint| !utxos_by_outpoint.contains_key(outpoint))
+            {
+                return Err(WalletError::from(TransactionError::UtxoNotFound {
+                    outpoint: TransactionOutpoint::new(outpoint.transaction_id, outpoint.index),
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            utxo_manager.freeze_utxos(&outpoints, &request.reason)?;
+
+            let frozen_utxos: Vec<_> = outpoints
+                .iter()
+                .filter_map(|outpoint| {
+                    utxo_manager
+                        .frozen_utxo(outpoint)
+                        .map(|frozen_utxo| (outpoint.clone(), frozen_utxo.clone()))
+                })
+                .collect();
+            (frozen_utxos, utxos_by_outpoint)
+        };
+        info!("Froze {} UTXOs: {}", frozen_utxos.len(), request.reason);
+
+        Ok(FreezeUtxosResponse {
+            frozen_utxos: self
+                .frozen_utxos_to_proto(frozen_utxos, &utxos_by_outpoint)
+                .await?,
+        })
+    }
+}
@@ -1,30 +1,174 @@
 use crate::service::kaswallet_service::KasWalletService;
-use common::errors::WalletResult;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
 use common::model::{Keychain, WalletAddress};
-use proto::kaswallet_proto::GetAddressesRequest;
+use proto::kaswallet_proto::{
+    AddressInfo, GetAddressesRequest, GetAddressesResponse, Keychain as ProtoKeychain,
+    KeychainFilter, UsageFilter,
+};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 use std::sync::atomic::Ordering::Relaxed;
 
+/// Page tokens name the next address to list as `<keychain>/<index>`.
+fn page_token(keychain: &Keychain, index: u32) -> String {
+    format!("{}/{}", keychain.clone() as u32, index)
+}
+
+fn parse_page_token(page_token: &str) -> WalletResult<Option<(Keychain, u32)>> {
+    if page_token.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || {
+        WalletError::from(UserInputError::InvalidArgument {
+            reason: format!("invalid page_token {page_token}"),
+            location: ErrorLocation::capture(),
+        })
+    };
+    let (keychain, index) = page_token.split_once('/').ok_or_else(invalid)?;
+    let keychain = match keychain {
+        "0" => Keychain::External,
+        "1" => Keychain::Internal,
+        _ => return Err(invalid()),
+    };
+    let index = index.parse().map_err(|_| invalid())?;
+    Ok(Some((keychain, index)))
+}
+
 impl KasWalletService {
     pub(crate) async fn get_addresses(
         &self,
-        _request: GetAddressesRequest,
-    ) -> WalletResult<Vec<String>> {
+        request: GetAddressesRequest,
+    ) -> WalletResult<GetAddressesResponse> {
         self.check_is_synced().await?;
 
-        let mut addresses = vec![];
-        let address_manager = self.address_manager.lock().await;
-        for i in 1..=self.keys.last_used_external_index.load(Relaxed) {
-            let wallet_address = WalletAddress {
-                index: i,
-                cosigner_index: self.keys.cosigner_index,
-                keychain: Keychain::External,
+        let usage = request.usage();
+        let page_size = if request.page_size == 0 {
+            usize::MAX
+        } else {
+            request.page_size as usize
+        };
+        let start = parse_page_token(&request.page_token)?;
+
+        // External index 0 is never handed out by NewAddress; internal index 0 is the shared
+        // change address.
+        let external = (
+            Keychain::External,
+            1..=self.keys.last_used_external_index.load(Relaxed),
+        );
+        let internal = (
+            Keychain::Internal,
+            0..=self.keys.last_used_internal_index.load(Relaxed),
+        );
+        let keychains: Vec<(Keychain, RangeInclusive<u32>)> = match request.keychain() {
+            KeychainFilter::External => vec![external],
+            KeychainFilter::Internal => vec![internal],
+            KeychainFilter::All => vec![external, internal],
+        };
+
+        let mut utxo_stats: HashMap<WalletAddress, (u64, u32)> = HashMap::new();
+        {
+            let utxo_manager = self.utxo_manager.lock().await;
+            for utxo in utxo_manager.utxos_sorted_by_amount() {
+                let (balance, utxo_count) = utxo_stats.entry(utxo.address).or_default();
+                *balance += utxo.utxo_entry.amount;
+                *utxo_count += 1;
+            }
+        }
+
+        let labels = self.address_book.lock().await.labels().clone();
+        let used_addresses: HashSet<WalletAddress> =
+            self.address_manager.lock().await.used_addresses().await;
+
+        // Pick the page without holding the address manager, then derive only its addresses.
+        let mut page = vec![];
+        let mut next_page_token = String::new();
+        'keychains: for (keychain, indexes) in keychains {
+            let first_index = match &start {
+                Some((start_keychain, start_index)) => {
+                    match (start_keychain.clone() as u32).cmp(&(keychain.clone() as u32)) {
+                        Ordering::Greater => continue,
+                        Ordering::Equal => (*start_index).max(*indexes.start()),
+                        Ordering::Less => *indexes.start(),
+                    }
+                }
+                None => *indexes.start(),
+            };
+            for index in first_index..=*indexes.end() {
+                if page.len() == page_size {
+                    next_page_token = page_token(&keychain, index);
+                    break 'keychains;
+                }
+                let wallet_address =
+                    WalletAddress::new(index, self.keys.cosigner_index, keychain.clone());
+                let (balance, utxo_count) =
+                    utxo_stats.get(&wallet_address).copied().unwrap_or_default();
+                // Holding UTXOs covers outputs the sync has not reported a balance for yet.
+                let is_used = utxo_count > 0 || used_addresses.contains(&wallet_address);
+                let is_wanted = match usage {
+                    UsageFilter::All => true,
+                    UsageFilter::Used => is_used,
+                    UsageFilter::Unused => !is_used,
+                };
+                if is_wanted {
+                    page.push((wallet_address, is_used, balance, utxo_count));
+                }
+            }
+        }
+
+        let mut address_infos = Vec::with_capacity(page.len());
+        for (wallet_address, is_used, balance, utxo_count) in page {
+            let (address, derivation_path) = {
+                let address_manager = self.address_manager.lock().await;
+                (
+                    address_manager
+                        .kaspa_address_from_wallet_address(&wallet_address, true)
+                        .await?
+                        .to_string(),
+                    address_manager
+                        .calculate_full_address_path(&wallet_address)?
+                        .to_string(),
+                )
             };
-            let address = address_manager
-                .kaspa_address_from_wallet_address(&wallet_address, true)
-                .await?;
-            addresses.push(address.to_string());
+            let label = labels.get(&address).cloned().unwrap_or_default();
+            address_infos.push(AddressInfo {
+                address,
+                keychain: ProtoKeychain::from(wallet_address.keychain) as i32,
+                index: wallet_address.index,
+                derivation_path,
+                is_used,
+                balance,
+                utxo_count,
+                label,
+            });
         }
 
-        Ok(addresses)
+        Ok(GetAddressesResponse {
+            address: address_infos
+                .iter()
+                .map(|address_info| address_info.address.clone())
+                .collect(),
+            address_infos,
+            next_page_token,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_token_roundtrips() {
+        let token = page_token(&Keychain::Internal, 42);
+        assert_eq!(
+            parse_page_token(&token).unwrap(),
+            Some((Keychain::Internal, 42))
+        );
+        assert_eq!(parse_page_token("").unwrap(), None);
+        assert!(parse_page_token("2/1").is_err());
+        assert!(parse_page_token("0/x").is_err());
+        assert!(parse_page_token("0").is_err());
     }
 }
@@ -13,6 +13,11 @@ impl KasWalletService {
         self.check_is_synced().await?;
 
         let virtual_daa_score = self.get_virtual_daa_score().await?;
+        let dust_fee_rate = if self.dust_policy.hide_from_balance {
+            Some(self.dust_reference_fee_rate().await?)
+        } else {
+            None
+        };
         let mut balances_map = HashMap::new();
 
         let utxos_sorted_by_amount: Vec<WalletUtxo>;
@@ -27,16 +32,35 @@ impl KasWalletService {
                 let balances = balances_map
                     .entry(entry.address.clone())
                     .or_insert_with(BalancesEntry::new);
-                if utxo_manager.is_utxo_unspendable(&entry, virtual_daa_score) {
+                let is_hidden_dust = match dust_fee_rate {
+                    Some(dust_fee_rate) => self.is_utxo_dust(&entry, dust_fee_rate).await?,
+                    None => false,
+                };
+                if utxo_manager.is_utxo_frozen(&entry.outpoint) {
+                    balances.add_frozen(amount);
+                } else if is_hidden_dust {
+                    balances.add_dust(amount);
+                } else if utxo_manager.is_utxo_unspendable(&entry, virtual_daa_score) {
                     balances.add_pending(amount);
                 } else {
                     balances.add_available(amount);
                 }
             }
+            for covenant_bound_utxo in utxo_manager.covenant_bound_utxos() {
+                balances_map
+                    .entry(covenant_bound_utxo.utxo.address.clone())
+                    .or_insert_with(BalancesEntry::new)
+                    .add_covenant_bound(covenant_bound_utxo.utxo.utxo_entry.amount);
+            }
         }
         let mut address_balances = vec![];
         let mut total_balances = BalancesEntry::new();
 
+        let labels = if request.include_balance_per_address {
+            self.address_book.lock().await.labels().clone()
+        } else {
+            Default::default()
+        };
         let address_manager = self.address_manager.lock().await;
         for (wallet_address, balances) in &balances_map {
             let address = address_manager
@@ -44,10 +68,15 @@ impl KasWalletService {
                 .await?;
 
             if request.include_balance_per_address {
+                let address = address.to_string();
                 address_balances.push(AddressBalances {
-                    address: address.to_string(),
+                    label: labels.get(&address).cloned().unwrap_or_default(),
+                    address,
                     available: balances.available,
                     pending: balances.pending,
+                    frozen: balances.frozen,
+                    covenant_bound: balances.covenant_bound,
+                    dust: balances.dust,
                 });
             }
             total_balances.add(balances);
@@ -63,6 +92,9 @@ impl KasWalletService {
             available: total_balances.available,
             pending: total_balances.pending,
             address_balances,
+            frozen: total_balances.frozen,
+            covenant_bound: total_balances.covenant_bound,
+            dust: total_balances.dust,
         })
     }
 }
@@ -70,6 +102,9 @@ impl KasWalletService {
 struct BalancesEntry {
     pub available: u64,
     pub pending: u64,
+    pub frozen: u64,
+    pub covenant_bound: u64,
+    pub dust: u64,
 }
 
 impl BalancesEntry {
@@ -77,12 +112,18 @@ impl BalancesEntry {
         Self {
             available: 0,
             pending: 0,
+            frozen: 0,
+            covenant_bound: 0,
+            dust: 0,
         }
     }
 
     pub fn add(&mut self, other: &Self) {
         self.add_available(other.available);
         self.add_pending(other.pending);
+        self.add_frozen(other.frozen);
+        self.add_covenant_bound(other.covenant_bound);
+        self.add_dust(other.dust);
     }
     pub fn add_available(&mut self, amount: u64) {
         self.available += amount;
@@ -90,4 +131,13 @@ impl BalancesEntry {
     pub fn add_pending(&mut self, amount: u64) {
         self.pending += amount;
     }
+    pub fn add_frozen(&mut self, amount: u64) {
+        self.frozen += amount;
+    }
+    pub fn add_covenant_bound(&mut self, amount: u64) {
+        self.covenant_bound += amount;
+    }
+    pub fn add_dust(&mut self, amount: u64) {
+        self.dust += amount;
+    }
 }
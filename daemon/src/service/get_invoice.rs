@@ -0,0 +1,54 @@
+use crate::invoices::{Invoice, InvoiceState};
+use crate::service::common::parse_address;
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use common::payment_uri::PaymentUri;
+use proto::kaswallet_proto::{
+    GetInvoiceRequest, GetInvoiceResponse, Invoice as ProtoInvoice,
+    InvoiceState as ProtoInvoiceState,
+};
+
+impl From<InvoiceState> for ProtoInvoiceState {
+    fn from(state: InvoiceState) -> Self {
+        match state {
+            InvoiceState::Unpaid => ProtoInvoiceState::Unpaid,
+            InvoiceState::PartiallyPaid => ProtoInvoiceState::PartiallyPaid,
+            InvoiceState::Paid => ProtoInvoiceState::Paid,
+            InvoiceState::Overpaid => ProtoInvoiceState::Overpaid,
+            InvoiceState::Expired => ProtoInvoiceState::Expired,
+        }
+    }
+}
+
+pub(crate) fn invoice_to_proto(invoice: &Invoice) -> WalletResult<ProtoInvoice> {
+    let payment_uri = PaymentUri {
+        amount: Some(invoice.amount),
+        message: (!invoice.memo.is_empty()).then(|| invoice.memo.clone()),
+        ..PaymentUri::new(parse_address(&invoice.address)?)
+    };
+    Ok(ProtoInvoice {
+        invoice_id: invoice.id,
+        address: invoice.address.clone(),
+        amount: invoice.amount,
+        memo: invoice.memo.clone(),
+        created_at_unix_millis: invoice.created_at_unix_millis,
+        expires_at_unix_millis: invoice.expires_at_unix_millis.unwrap_or_default(),
+        state: ProtoInvoiceState::from(invoice.state) as i32,
+        received: invoice.received(),
+        payment_uri: payment_uri.to_string(),
+    })
+}
+
+impl KasWalletService {
+    pub(crate) async fn get_invoice(
+        &self,
+        request: GetInvoiceRequest,
+    ) -> WalletResult<GetInvoiceResponse> {
+        self.invoice_tracker.refresh().await?;
+        let invoice = self.invoice_tracker.invoice(request.invoice_id).await?;
+
+        Ok(GetInvoiceResponse {
+            invoice: Some(invoice_to_proto(&invoice)?),
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use proto::kaswallet_proto::{GetMaxSendableRequest, GetMaxSendableResponse};
+
+impl KasWalletService {
+    pub(crate) async fn get_max_sendable(
+        &self,
+        request: GetMaxSendableRequest,
+    ) -> WalletResult<GetMaxSendableResponse> {
+        let Some(mut transaction_description) = request.transaction_description else {
+            return Err(WalletError::from(UserInputError::MissingField {
+                field: "transaction_description",
+                location: ErrorLocation::capture(),
+            }));
+        };
+        self.check_is_synced().await?;
+        self.resolve_contact_alias(&mut transaction_description)
+            .await;
+
+        let utxo_manager = self.utxo_manager.lock().await;
+        let mut transaction_generator = self.transaction_generator.lock().await;
+        let max_sendable = transaction_generator
+            .max_sendable(&utxo_manager, transaction_description)
+            .await?;
+
+        Ok(GetMaxSendableResponse {
+            amount: max_sendable.amount,
+            fee: max_sendable.fee,
+            transaction_count: max_sendable.transaction_count as u32,
+            applied_fee: Some(max_sendable.fee_limits.into()),
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::confirmation_tracker::{ConfirmationState, TransactionConfirmation};
+use crate::service::common::parse_transaction_id;
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use kaspa_consensus_core::tx::TransactionId;
+use proto::kaswallet_proto::{
+    GetTransactionStatusRequest, GetTransactionStatusResponse, TransactionState, TransactionStatus,
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+impl KasWalletService {
+    pub(crate) async fn get_transaction_status(
+        &self,
+        request: GetTransactionStatusRequest,
+    ) -> WalletResult<GetTransactionStatusResponse> {
+        let transaction_ids = request
+            .transaction_ids
+            .iter()
+            .map(|transaction_id| parse_transaction_id(transaction_id))
+            .collect::<WalletResult<Vec<_>>>()?;
+
+        let confirmations = self.confirmation_tracker.statuses(&transaction_ids).await;
+        Ok(GetTransactionStatusResponse {
+            statuses: to_transaction_statuses(&transaction_ids, confirmations),
+        })
+    }
+
+    /// Block until the given transactions reach `depth` confirmations or are rejected, bounded
+    /// by `timeout_millis` (0 selects the default). Must not be called with the UTXO manager
+    /// locked: acceptance is only observed while syncs keep running.
+    pub(crate) async fn wait_for_transaction_confirmations(
+        &self,
+        transaction_ids: &[String],
+        depth: u64,
+        timeout_millis: u64,
+    ) -> WalletResult<Vec<TransactionStatus>> {
+        let transaction_ids = transaction_ids
+            .iter()
+            .map(|transaction_id| parse_transaction_id(transaction_id))
+            .collect::<WalletResult<Vec<_>>>()?;
+        let timeout = match timeout_millis {
+            0 => DEFAULT_WAIT_TIMEOUT,
+            timeout_millis => Duration::from_millis(timeout_millis),
+        };
+
+        let confirmations = self
+            .confirmation_tracker
+            .wait_for_confirmations(&transaction_ids, depth, Instant::now() + timeout)
+            .await;
+        Ok(to_transaction_statuses(&transaction_ids, confirmations))
+    }
+}
+
+fn to_transaction_statuses(
+    transaction_ids: &[TransactionId],
+    confirmations: Vec<Option<TransactionConfirmation>>,
+) -> Vec<TransactionStatus> {
+    transaction_ids
+        .iter()
+        .zip(confirmations)
+        .map(|(transaction_id, confirmation)| {
+            let mut status = TransactionStatus {
+                transaction_id: transaction_id.to_string(),
+                ..Default::default()
+            };
+            let Some(confirmation) = confirmation else {
+                status.set_state(TransactionState::Unknown);
+                return status;
+            };
+            status.confirmations = confirmation.confirmations;
+            match confirmation.state {
+                ConfirmationState::Pending => status.set_state(TransactionState::Pending),
+                ConfirmationState::Accepted {
+                    accepting_block_hash,
+                    accepting_daa_score,
+                } => {
+                    status.set_state(TransactionState::Accepted);
+                    status.accepting_block_hash = accepting_block_hash.to_string();
+                    status.accepting_daa_score = accepting_daa_score;
+                }
+                ConfirmationState::Rejected { reason } => {
+                    status.set_state(TransactionState::Rejected);
+                    status.rejection_reason = reason;
+                }
+            }
+            status
+        })
+        .collect()
+}
@@ -1,37 +1,34 @@
 use crate::address_manager::AddressSet;
+use crate::service::common::parse_address;
 use crate::service::kaswallet_service::KasWalletService;
 use common::error_location::ErrorLocation;
-use common::errors::{RpcError, UserInputError, WalletError, WalletResult};
+use common::errors::{UserInputError, WalletError, WalletResult};
 use common::model::WalletUtxo;
-use kaspa_addresses::Address;
-use kaspa_wallet_core::rpc::RpcApi;
 use proto::kaswallet_proto::{
     AddressToUtxos, GetUtxosRequest, GetUtxosResponse, Utxo as ProtoUtxo,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 impl KasWalletService {
     pub(crate) async fn get_utxos(
         &self,
         request: GetUtxosRequest,
     ) -> WalletResult<GetUtxosResponse> {
-        for address in &request.addresses {
-            Address::try_from(address.as_str()).map_err(|e| UserInputError::InvalidAddress {
-                input: address.clone(),
-                reason: e.to_string(),
-                location: ErrorLocation::capture(),
-            })?;
-        }
+        let requested_addresses = request
+            .addresses
+            .iter()
+            .map(|address| parse_address(address).map(|address| address.to_string()))
+            .collect::<WalletResult<Vec<String>>>()?;
 
         let address_set: AddressSet;
         {
             let address_manager = self.address_manager.lock().await;
             address_set = address_manager.address_set().await;
         }
-        let address_strings: Vec<String> = if request.addresses.is_empty() {
+        let address_strings: Vec<String> = if requested_addresses.is_empty() {
             address_set.keys().cloned().collect()
         } else {
-            for address in &request.addresses {
+            for address in &requested_addresses {
                 if !address_set.contains_key(address) {
                     return Err(WalletError::from(UserInputError::InvalidAddress {
                         input: address.clone(),
@@ -40,25 +37,20 @@ impl KasWalletService {
                     }));
                 }
             }
-            request.addresses
+            requested_addresses
         };
 
-        let fee_estimate =
-            self.kaspa_client
-                .get_fee_estimate()
-                .await
-                .map_err(|e| RpcError::Transport {
-                    reason: e.to_string(),
-                    location: ErrorLocation::capture(),
-                })?;
-
-        let fee_rate = fee_estimate.normal_buckets[0].feerate;
+        let fee_rate = self.dust_reference_fee_rate().await?;
 
         let virtual_daa_score = self.get_virtual_daa_score().await?;
 
-        let utxos = {
+        let (frozen_utxos, utxos, covenant_bound_utxos) = {
             let utxo_manager = self.utxo_manager.lock().await;
-            utxo_manager.utxos_sorted_by_amount()
+            let (frozen_utxos, utxos): (Vec<WalletUtxo>, Vec<WalletUtxo>) = utxo_manager
+                .utxos_sorted_by_amount()
+                .into_iter()
+                .partition(|utxo| utxo_manager.is_utxo_frozen(&utxo.outpoint));
+            (frozen_utxos, utxos, utxo_manager.covenant_bound_utxos())
         };
         let filtered_bucketed_utxos = self
             .filter_utxos_and_bucket_by_address(
@@ -70,16 +62,50 @@ impl KasWalletService {
                 request.include_dust,
             )
             .await?;
+        // Frozen UTXOs are reported whatever their pending or dust status: they are listed so
+        // the operator can see what is held back.
+        let frozen_bucketed_utxos = self
+            .filter_utxos_and_bucket_by_address(
+                &frozen_utxos,
+                fee_rate,
+                virtual_daa_score,
+                &address_strings,
+                true,
+                true,
+            )
+            .await?;
+        // Like frozen UTXOs, covenant-bound ones are always listed, on their own.
+        let mut covenant_bound_bucketed_utxos = HashMap::new();
+        for covenant_bound_utxo in covenant_bound_utxos {
+            let is_pending = {
+                let utxo_manager = self.utxo_manager.lock().await;
+                utxo_manager.is_utxo_unspendable(&covenant_bound_utxo.utxo, virtual_daa_score)
+            };
+            let address = {
+                let address_manager = self.address_manager.lock().await;
+                address_manager
+                    .kaspa_address_from_wallet_address(&covenant_bound_utxo.utxo.address, true)
+                    .await?
+                    .address_to_string()
+            };
+            if !address_strings.is_empty() && !address_strings.contains(&address) {
+                continue;
+            }
+            covenant_bound_bucketed_utxos
+                .entry(address)
+                .or_insert_with(Vec::new)
+                .push(covenant_bound_utxo.into_proto(is_pending));
+        }
 
-        let addresses_to_utxos = filtered_bucketed_utxos
-            .iter()
-            .map(|(address_string, utxos)| AddressToUtxos {
-                address: address_string.clone(),
-                utxos: utxos.clone(),
-            })
-            .collect();
-
-        Ok(GetUtxosResponse { addresses_to_utxos })
+        let labels = self.address_book.lock().await.labels().clone();
+        Ok(GetUtxosResponse {
+            addresses_to_utxos: to_addresses_to_utxos(filtered_bucketed_utxos, &labels),
+            frozen_addresses_to_utxos: to_addresses_to_utxos(frozen_bucketed_utxos, &labels),
+            covenant_bound_addresses_to_utxos: to_addresses_to_utxos(
+                covenant_bound_bucketed_utxos,
+                &labels,
+            ),
+        })
     }
 
     async fn filter_utxos_and_bucket_by_address(
@@ -126,15 +152,18 @@ impl KasWalletService {
 
         Ok(filtered_bucketed_utxos)
     }
+}
 
-    async fn is_utxo_dust(&self, utxo: &WalletUtxo, fee_rate: f64) -> WalletResult<bool> {
-        let transaction_generator = self.transaction_generator.lock().await;
-        let mass = transaction_generator
-            .estimate_mass(&vec![utxo.clone()], utxo.utxo_entry.amount, &[])
-            .await?;
-
-        let fee = ((mass as f64) * fee_rate).ceil() as u64;
-
-        Ok(fee >= utxo.utxo_entry.amount)
-    }
+fn to_addresses_to_utxos(
+    bucketed_utxos: HashMap<String, Vec<ProtoUtxo>>,
+    labels: &BTreeMap<String, String>,
+) -> Vec<AddressToUtxos> {
+    bucketed_utxos
+        .into_iter()
+        .map(|(address, utxos)| AddressToUtxos {
+            label: labels.get(&address).cloned().unwrap_or_default(),
+            address,
+            utxos,
+        })
+        .collect()
 }
@@ -1,25 +1,43 @@
+use crate::address_book::AddressBookStore;
 use crate::address_manager::AddressManager;
+use crate::confirmation_tracker::ConfirmationTracker;
+use crate::dust_policy::DustPolicy;
+use crate::idempotency::IdempotencyStore;
+use crate::invoice_tracker::InvoiceTracker;
+use crate::subnetworks::AllowedSubnetworks;
 use crate::sync_manager::SyncManager;
 use crate::transaction_generator::TransactionGenerator;
 use crate::utxo_manager::UtxoManager;
-use common::error_location::ErrorLocation;
-use common::errors::{UserInputError, WalletError, WalletResult};
+use common::errors::WalletResult;
 use common::keys::Keys;
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_grpc_client::GrpcClient;
 use proto::kaswallet_proto::wallet_server::Wallet;
 use proto::kaswallet_proto::{
-    BroadcastRequest, BroadcastResponse, CreateUnsignedTransactionsRequest,
-    CreateUnsignedTransactionsResponse, GetAddressesRequest, GetAddressesResponse,
-    GetBalanceRequest, GetBalanceResponse, GetUtxosRequest, GetUtxosResponse, GetVersionRequest,
-    GetVersionResponse, NewAddressRequest, NewAddressResponse, SendRequest, SendResponse,
-    SignRequest, SignResponse,
+    AbandonTransactionRequest, AbandonTransactionResponse, AccelerateRequest, AccelerateResponse,
+    AddContactRequest, AddContactResponse, BroadcastRequest, BroadcastResponse, BumpFeeRequest,
+    BumpFeeResponse, ConsolidateRequest, ConsolidateResponse, CreateInvoiceRequest,
+    CreateInvoiceResponse, CreateUnsignedTransactionsRequest, CreateUnsignedTransactionsResponse,
+    FreezeUtxosRequest, FreezeUtxosResponse, GetAddressesRequest, GetAddressesResponse,
+    GetBalanceRequest, GetBalanceResponse, GetInvoiceRequest, GetInvoiceResponse,
+    GetMaxSendableRequest, GetMaxSendableResponse, GetTransactionStatusRequest,
+    GetTransactionStatusResponse, GetUtxosRequest, GetUtxosResponse, GetVersionRequest,
+    GetVersionResponse, ListAddressLabelsRequest, ListAddressLabelsResponse, ListContactsRequest,
+    ListContactsResponse, ListFrozenUtxosRequest, ListFrozenUtxosResponse,
+    ListInvoiceEventsRequest, ListInvoiceEventsResponse, ListInvoicesRequest, ListInvoicesResponse,
+    ListPendingTransactionsRequest, ListPendingTransactionsResponse, NewAddressRequest,
+    NewAddressResponse, ReleaseReservationRequest, ReleaseReservationResponse,
+    RemoveContactRequest, RemoveContactResponse, SendRequest, SendResponse, SetAddressLabelRequest,
+    SetAddressLabelResponse, SignMessageRequest, SignMessageResponse, SignRequest, SignResponse,
+    SweepDustRequest, SweepDustResponse, SweepExternalRequest, SweepExternalResponse,
+    UnfreezeUtxosRequest, UnfreezeUtxosResponse, ValidateAddressRequest, ValidateAddressResponse,
+    VerifyMessageRequest, VerifyMessageResponse,
 };
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::Mutex;
 use tonic::{Request, Response, Status};
-use tracing::{instrument, warn};
+use tracing::instrument;
 
 static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -34,16 +52,24 @@ pub struct KasWalletService {
     pub(crate) utxo_manager: Arc<Mutex<UtxoManager>>,
     pub(crate) transaction_generator: Arc<Mutex<TransactionGenerator>>,
     pub(crate) sync_manager: Arc<SyncManager>,
+    pub(crate) confirmation_tracker: Arc<ConfirmationTracker>,
+    // Locked after the UTXO manager when both are needed.
+    pub(crate) idempotency_store: Mutex<IdempotencyStore>,
+    // Locked after the UTXO manager when both are needed, and never held while taking another lock.
+    pub(crate) address_book: Mutex<AddressBookStore>,
+    pub(crate) invoice_tracker: Arc<InvoiceTracker>,
     pub(crate) submit_transaction_mutex: Mutex<()>,
-    // Operator-configured lane id. Wire-supplied transactions (Sign,
-    // Broadcast) whose `subnetwork_id` does not match this value are
-    // rejected at the service boundary so a daemon configured for a
-    // specific lane (e.g. IGRA `97b10000…`) cannot be coerced into
-    // signing or relaying transactions targeting any other lane.
-    pub(crate) configured_subnetwork_id: SubnetworkId,
+    // Operator-configured lanes. Wire-supplied transactions (Sign,
+    // Broadcast) whose `subnetwork_id` is not one of them are rejected at
+    // the service boundary so a daemon configured for specific lanes
+    // (e.g. IGRA `97b10000…`) cannot be coerced into signing or relaying
+    // transactions targeting any other lane.
+    pub(crate) allowed_subnetworks: AllowedSubnetworks,
+    pub(crate) dust_policy: DustPolicy,
 }
 
 impl KasWalletService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         kaspa_client: Arc<GrpcClient>,
         keys: Arc<Keys>,
@@ -51,7 +77,12 @@ impl KasWalletService {
         utxo_manager: Arc<Mutex<UtxoManager>>,
         transaction_generator: Arc<Mutex<TransactionGenerator>>,
         sync_manager: Arc<SyncManager>,
-        configured_subnetwork_id: SubnetworkId,
+        confirmation_tracker: Arc<ConfirmationTracker>,
+        idempotency_store: IdempotencyStore,
+        address_book: AddressBookStore,
+        invoice_tracker: Arc<InvoiceTracker>,
+        allowed_subnetworks: AllowedSubnetworks,
+        dust_policy: DustPolicy,
     ) -> Self {
         Self {
             kaspa_client,
@@ -60,52 +91,27 @@ impl KasWalletService {
             utxo_manager,
             transaction_generator,
             sync_manager,
+            confirmation_tracker,
+            idempotency_store: Mutex::new(idempotency_store),
+            address_book: Mutex::new(address_book),
+            invoice_tracker,
             submit_transaction_mutex: Mutex::new(()),
-            configured_subnetwork_id,
+            allowed_subnetworks,
+            dust_policy,
         }
     }
 
-    /// Reject a wire-supplied transaction whose subnetwork id does not match
-    /// the daemon's configured lane. Returns `Ok(())` when the daemon is in
-    /// native mode (no lane restriction) or the ids match.
+    /// Reject a wire-supplied transaction whose subnetwork id is not one of
+    /// the daemon's configured lanes. Returns `Ok(())` when the daemon is in
+    /// native mode (no lane restriction) or the id is allowed.
     pub(crate) fn ensure_subnetwork_id_matches(
         &self,
         tx_subnetwork_id: &SubnetworkId,
     ) -> WalletResult<()> {
-        ensure_subnetwork_id_matches(&self.configured_subnetwork_id, tx_subnetwork_id)
+        self.allowed_subnetworks.ensure_allowed(tx_subnetwork_id)
     }
 }
 
-/// Pure lane-gate predicate, extracted for unit testing without having to
-/// stand up an `Arc<GrpcClient>` and the rest of the service graph.
-///
-/// We intentionally allow any tx through a native-configured daemon
-/// (`configured.is_native()`), so the generic kaspa wallet behavior is
-/// preserved — only lane-bound daemons gate by subnetwork id.
-pub(crate) fn ensure_subnetwork_id_matches(
-    configured: &SubnetworkId,
-    tx_subnetwork_id: &SubnetworkId,
-) -> WalletResult<()> {
-    if configured.is_native() || tx_subnetwork_id == configured {
-        return Ok(());
-    }
-    // Surface lane-mismatch attempts: silent rejections leave on-call with
-    // no signal that a probe or misroute happened. Emitted at warn so
-    // log aggregators flag it without paging.
-    warn!(
-        configured = %configured,
-        attempted = %tx_subnetwork_id,
-        "rejecting wire-supplied tx with mismatched subnetwork id"
-    );
-    Err(WalletError::from(UserInputError::InvalidArgument {
-        reason: format!(
-            "transaction subnetwork_id {tx_subnetwork_id} does not match daemon's \
-             configured subnetwork_id {configured}",
-        ),
-        location: ErrorLocation::capture(),
-    }))
-}
-
 #[tonic::async_trait]
 impl Wallet for KasWalletService {
     #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
@@ -113,12 +119,12 @@ impl Wallet for KasWalletService {
         &self,
         request: Request<GetAddressesRequest>,
     ) -> Result<Response<GetAddressesResponse>, Status> {
-        let addresses = self
+        let response = self
             .get_addresses(request.into_inner())
             .await
             .map_err(Status::from)?;
 
-        Ok(Response::new(GetAddressesResponse { address: addresses }))
+        Ok(Response::new(response))
     }
 
     #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
@@ -134,6 +140,162 @@ impl Wallet for KasWalletService {
         Ok(Response::new(response))
     }
 
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn validate_address(
+        &self,
+        request: Request<ValidateAddressRequest>,
+    ) -> Result<Response<ValidateAddressResponse>, Status> {
+        let response = self
+            .validate_address(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn set_address_label(
+        &self,
+        request: Request<SetAddressLabelRequest>,
+    ) -> Result<Response<SetAddressLabelResponse>, Status> {
+        let response = self
+            .set_address_label(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_address_labels(
+        &self,
+        request: Request<ListAddressLabelsRequest>,
+    ) -> Result<Response<ListAddressLabelsResponse>, Status> {
+        let response = self
+            .list_address_labels(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn add_contact(
+        &self,
+        request: Request<AddContactRequest>,
+    ) -> Result<Response<AddContactResponse>, Status> {
+        let response = self
+            .add_contact(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn remove_contact(
+        &self,
+        request: Request<RemoveContactRequest>,
+    ) -> Result<Response<RemoveContactResponse>, Status> {
+        let response = self
+            .remove_contact(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_contacts(
+        &self,
+        request: Request<ListContactsRequest>,
+    ) -> Result<Response<ListContactsResponse>, Status> {
+        let response = self
+            .list_contacts(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn sign_message(
+        &self,
+        request: Request<SignMessageRequest>,
+    ) -> Result<Response<SignMessageResponse>, Status> {
+        let response = self
+            .sign_message(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn verify_message(
+        &self,
+        request: Request<VerifyMessageRequest>,
+    ) -> Result<Response<VerifyMessageResponse>, Status> {
+        let response = self
+            .verify_message(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn create_invoice(
+        &self,
+        request: Request<CreateInvoiceRequest>,
+    ) -> Result<Response<CreateInvoiceResponse>, Status> {
+        let response = self
+            .create_invoice(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn get_invoice(
+        &self,
+        request: Request<GetInvoiceRequest>,
+    ) -> Result<Response<GetInvoiceResponse>, Status> {
+        let response = self
+            .get_invoice(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_invoices(
+        &self,
+        request: Request<ListInvoicesRequest>,
+    ) -> Result<Response<ListInvoicesResponse>, Status> {
+        let response = self
+            .list_invoices(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_invoice_events(
+        &self,
+        request: Request<ListInvoiceEventsRequest>,
+    ) -> Result<Response<ListInvoiceEventsResponse>, Status> {
+        let response = self
+            .list_invoice_events(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
     #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
     async fn get_balance(
         &self,
@@ -164,7 +326,7 @@ impl Wallet for KasWalletService {
         skip(self, request),
         fields(
             request_id = next_request_id(),
-            subnetwork_id = %self.configured_subnetwork_id,
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
         ),
         err(Display)
     )]
@@ -184,7 +346,7 @@ impl Wallet for KasWalletService {
         skip(self, request),
         fields(
             request_id = next_request_id(),
-            subnetwork_id = %self.configured_subnetwork_id,
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
         ),
         err(Display)
     )]
@@ -201,7 +363,7 @@ impl Wallet for KasWalletService {
         skip(self, request),
         fields(
             request_id = next_request_id(),
-            subnetwork_id = %self.configured_subnetwork_id,
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
         ),
         err(Display)
     )]
@@ -221,7 +383,7 @@ impl Wallet for KasWalletService {
         skip(self, request),
         fields(
             request_id = next_request_id(),
-            subnetwork_id = %self.configured_subnetwork_id,
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
             amount_sompi = tracing::field::Empty,
         ),
         err(Display)
@@ -242,6 +404,210 @@ impl Wallet for KasWalletService {
         Ok(Response::new(response))
     }
 
+    #[instrument(
+        skip(self, request),
+        fields(
+            request_id = next_request_id(),
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
+        ),
+        err(Display)
+    )]
+    async fn bump_fee(
+        &self,
+        request: Request<BumpFeeRequest>,
+    ) -> Result<Response<BumpFeeResponse>, Status> {
+        let response = self
+            .bump_fee(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(
+        skip(self, request),
+        fields(
+            request_id = next_request_id(),
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
+        ),
+        err(Display)
+    )]
+    async fn accelerate(
+        &self,
+        request: Request<AccelerateRequest>,
+    ) -> Result<Response<AccelerateResponse>, Status> {
+        let response = self
+            .accelerate(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_pending_transactions(
+        &self,
+        request: Request<ListPendingTransactionsRequest>,
+    ) -> Result<Response<ListPendingTransactionsResponse>, Status> {
+        let response = self
+            .list_pending_transactions(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn abandon_transaction(
+        &self,
+        request: Request<AbandonTransactionRequest>,
+    ) -> Result<Response<AbandonTransactionResponse>, Status> {
+        let response = self
+            .abandon_transaction(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(
+        skip(self, request),
+        fields(
+            request_id = next_request_id(),
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
+        ),
+        err(Display)
+    )]
+    async fn consolidate(
+        &self,
+        request: Request<ConsolidateRequest>,
+    ) -> Result<Response<ConsolidateResponse>, Status> {
+        let response = self
+            .consolidate(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(
+        skip(self, request),
+        fields(
+            request_id = next_request_id(),
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
+        ),
+        err(Display)
+    )]
+    async fn sweep_dust(
+        &self,
+        request: Request<SweepDustRequest>,
+    ) -> Result<Response<SweepDustResponse>, Status> {
+        let response = self
+            .sweep_dust(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(
+        skip(self, request),
+        fields(
+            request_id = next_request_id(),
+            allowed_subnetwork_ids = %self.allowed_subnetworks,
+        ),
+        err(Display)
+    )]
+    async fn sweep_external(
+        &self,
+        request: Request<SweepExternalRequest>,
+    ) -> Result<Response<SweepExternalResponse>, Status> {
+        let response = self
+            .sweep_external(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn freeze_utxos(
+        &self,
+        request: Request<FreezeUtxosRequest>,
+    ) -> Result<Response<FreezeUtxosResponse>, Status> {
+        let response = self
+            .freeze_utxos(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn unfreeze_utxos(
+        &self,
+        request: Request<UnfreezeUtxosRequest>,
+    ) -> Result<Response<UnfreezeUtxosResponse>, Status> {
+        let response = self
+            .unfreeze_utxos(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn list_frozen_utxos(
+        &self,
+        request: Request<ListFrozenUtxosRequest>,
+    ) -> Result<Response<ListFrozenUtxosResponse>, Status> {
+        let response = self
+            .list_frozen_utxos(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn release_reservation(
+        &self,
+        request: Request<ReleaseReservationRequest>,
+    ) -> Result<Response<ReleaseReservationResponse>, Status> {
+        let response = self
+            .release_reservation(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn get_max_sendable(
+        &self,
+        request: Request<GetMaxSendableRequest>,
+    ) -> Result<Response<GetMaxSendableResponse>, Status> {
+        let response = self
+            .get_max_sendable(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
+    async fn get_transaction_status(
+        &self,
+        request: Request<GetTransactionStatusRequest>,
+    ) -> Result<Response<GetTransactionStatusResponse>, Status> {
+        let response = self
+            .get_transaction_status(request.into_inner())
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(response))
+    }
+
     #[instrument(skip(self, request), fields(request_id = next_request_id()), err(Display))]
     async fn get_version(
         &self,
@@ -273,21 +639,33 @@ mod tests {
         SubnetworkId::from_bytes(bytes)
     }
 
+    fn lanes(configured: SubnetworkId) -> AllowedSubnetworks {
+        AllowedSubnetworks::new(configured, &[])
+    }
+
     #[test]
     fn native_configured_allows_any_tx() {
-        ensure_subnetwork_id_matches(&SUBNETWORK_ID_NATIVE, &SUBNETWORK_ID_NATIVE).unwrap();
-        ensure_subnetwork_id_matches(&SUBNETWORK_ID_NATIVE, &igra_lane()).unwrap();
-        ensure_subnetwork_id_matches(&SUBNETWORK_ID_NATIVE, &other_lane()).unwrap();
+        lanes(SUBNETWORK_ID_NATIVE)
+            .ensure_allowed(&SUBNETWORK_ID_NATIVE)
+            .unwrap();
+        lanes(SUBNETWORK_ID_NATIVE)
+            .ensure_allowed(&igra_lane())
+            .unwrap();
+        lanes(SUBNETWORK_ID_NATIVE)
+            .ensure_allowed(&other_lane())
+            .unwrap();
     }
 
     #[test]
     fn matching_lane_passes() {
-        ensure_subnetwork_id_matches(&igra_lane(), &igra_lane()).unwrap();
+        lanes(igra_lane()).ensure_allowed(&igra_lane()).unwrap();
     }
 
     #[test]
     fn mismatched_lane_is_rejected() {
-        let err = ensure_subnetwork_id_matches(&igra_lane(), &other_lane()).unwrap_err();
+        let err = lanes(igra_lane())
+            .ensure_allowed(&other_lane())
+            .unwrap_err();
         let msg = err.to_string();
         // The message must name both ids so on-call can see which lane the
         // tx tried to use vs. which the daemon expected.
@@ -299,7 +677,9 @@ mod tests {
     fn lane_daemon_rejects_native_tx() {
         // Critical: a daemon configured for a non-native lane MUST NOT
         // sign or broadcast a native (subnetwork=0×20) transaction.
-        let err = ensure_subnetwork_id_matches(&igra_lane(), &SUBNETWORK_ID_NATIVE).unwrap_err();
+        let err = lanes(igra_lane())
+            .ensure_allowed(&SUBNETWORK_ID_NATIVE)
+            .unwrap_err();
         assert!(err.to_string().contains("does not match"), "got: {err}");
     }
 }
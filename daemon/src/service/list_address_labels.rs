@@ -0,0 +1,23 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{AddressLabel, ListAddressLabelsRequest, ListAddressLabelsResponse};
+
+impl KasWalletService {
+    pub(crate) async fn list_address_labels(
+        &self,
+        _request: ListAddressLabelsRequest,
+    ) -> WalletResult<ListAddressLabelsResponse> {
+        let address_book = self.address_book.lock().await;
+
+        Ok(ListAddressLabelsResponse {
+            labels: address_book
+                .labels()
+                .iter()
+                .map(|(address, label)| AddressLabel {
+                    address: address.clone(),
+                    label: label.clone(),
+                })
+                .collect(),
+        })
+    }
+}
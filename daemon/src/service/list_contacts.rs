@@ -0,0 +1,25 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{Contact as ProtoContact, ListContactsRequest, ListContactsResponse};
+
+impl KasWalletService {
+    pub(crate) async fn list_contacts(
+        &self,
+        _request: ListContactsRequest,
+    ) -> WalletResult<ListContactsResponse> {
+        let address_book = self.address_book.lock().await;
+
+        Ok(ListContactsResponse {
+            contacts: address_book
+                .contacts()
+                .iter()
+                .map(|(alias, contact)| ProtoContact {
+                    alias: alias.clone(),
+                    address: contact.address.clone(),
+                    note: contact.note.clone(),
+                    created_at_unix_millis: contact.created_at_unix_millis,
+                })
+                .collect(),
+        })
+    }
+}
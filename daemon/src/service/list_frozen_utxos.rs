@@ -0,0 +1,57 @@
+use crate::frozen_utxos::FrozenUtxo;
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use common::model::{WalletOutpoint, WalletUtxo};
+use proto::kaswallet_proto::{
+    FrozenUtxo as ProtoFrozenUtxo, ListFrozenUtxosRequest, ListFrozenUtxosResponse,
+};
+use std::collections::HashMap;
+
+impl KasWalletService {
+    pub(crate) async fn list_frozen_utxos(
+        &self,
+        _request: ListFrozenUtxosRequest,
+    ) -> WalletResult<ListFrozenUtxosResponse> {
+        let (frozen_utxos, utxos_by_outpoint) = {
+            let utxo_manager = self.utxo_manager.lock().await;
+            (
+                utxo_manager.frozen_utxos(),
+                utxo_manager.utxos_by_outpoint(),
+            )
+        };
+
+        Ok(ListFrozenUtxosResponse {
+            frozen_utxos: self
+                .frozen_utxos_to_proto(frozen_utxos, &utxos_by_outpoint)
+                .await?,
+        })
+    }
+
+    /// Must not be called with the UTXO manager locked.
+    pub(crate) async fn frozen_utxos_to_proto(
+        &self,
+        frozen_utxos: Vec<(WalletOutpoint, FrozenUtxo)>,
+        utxos_by_outpoint: &HashMap<WalletOutpoint, WalletUtxo>,
+    ) -> WalletResult<Vec<ProtoFrozenUtxo>> {
+        let address_manager = self.address_manager.lock().await;
+        let mut proto_frozen_utxos = Vec::with_capacity(frozen_utxos.len());
+        for (outpoint, frozen_utxo) in frozen_utxos {
+            let mut proto_frozen_utxo = ProtoFrozenUtxo {
+                reason: frozen_utxo.reason,
+                frozen_at_unix_millis: frozen_utxo.frozen_at_unix_millis,
+                ..Default::default()
+            };
+            if let Some(utxo) = utxos_by_outpoint.get(&outpoint) {
+                proto_frozen_utxo.is_in_wallet = true;
+                proto_frozen_utxo.address = address_manager
+                    .kaspa_address_from_wallet_address(&utxo.address, true)
+                    .await?
+                    .address_to_string();
+                proto_frozen_utxo.amount = utxo.utxo_entry.amount;
+            }
+            proto_frozen_utxo.outpoint = Some(outpoint.into());
+            proto_frozen_utxos.push(proto_frozen_utxo);
+        }
+        Ok(proto_frozen_utxos)
+    }
+}
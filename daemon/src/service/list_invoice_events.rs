@@ -0,0 +1,35 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{
+    InvoiceEvent as ProtoInvoiceEvent, InvoiceState as ProtoInvoiceState, ListInvoiceEventsRequest,
+    ListInvoiceEventsResponse,
+};
+
+impl KasWalletService {
+    pub(crate) async fn list_invoice_events(
+        &self,
+        request: ListInvoiceEventsRequest,
+    ) -> WalletResult<ListInvoiceEventsResponse> {
+        self.invoice_tracker.refresh().await?;
+
+        let limit = match request.limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let events = self
+            .invoice_tracker
+            .events_after(request.after_sequence, limit)
+            .await
+            .into_iter()
+            .map(|event| ProtoInvoiceEvent {
+                sequence: event.sequence,
+                invoice_id: event.invoice_id,
+                previous_state: ProtoInvoiceState::from(event.previous_state) as i32,
+                state: ProtoInvoiceState::from(event.state) as i32,
+                received: event.received,
+                at_unix_millis: event.at_unix_millis,
+            })
+            .collect();
+        Ok(ListInvoiceEventsResponse { events })
+    }
+}
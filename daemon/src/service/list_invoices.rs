@@ -0,0 +1,22 @@
+use crate::service::get_invoice::invoice_to_proto;
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{ListInvoicesRequest, ListInvoicesResponse};
+
+impl KasWalletService {
+    pub(crate) async fn list_invoices(
+        &self,
+        request: ListInvoicesRequest,
+    ) -> WalletResult<ListInvoicesResponse> {
+        self.invoice_tracker.refresh().await?;
+
+        let mut invoices = vec![];
+        for invoice in self.invoice_tracker.invoices().await {
+            let invoice = invoice_to_proto(&invoice)?;
+            if request.states.is_empty() || request.states.contains(&invoice.state) {
+                invoices.push(invoice);
+            }
+        }
+        Ok(ListInvoicesResponse { invoices })
+    }
+}
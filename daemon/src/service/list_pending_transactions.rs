@@ -0,0 +1,36 @@
+use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::transaction_fee;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{
+    ListPendingTransactionsRequest, ListPendingTransactionsResponse, PendingTransaction,
+};
+
+impl KasWalletService {
+    pub(crate) async fn list_pending_transactions(
+        &self,
+        _request: ListPendingTransactionsRequest,
+    ) -> WalletResult<ListPendingTransactionsResponse> {
+        let tracked_transactions = {
+            let utxo_manager = self.utxo_manager.lock().await;
+            utxo_manager.mempool_transactions()
+        };
+
+        let pending_transactions = tracked_transactions
+            .into_iter()
+            .map(|tracked_transaction| PendingTransaction {
+                transaction_id: tracked_transaction.id().to_string(),
+                submitted_daa_score: tracked_transaction.submitted_daa_score,
+                fee: transaction_fee(tracked_transaction.transaction.transaction.inner()),
+                rebroadcast_attempts: tracked_transaction.rebroadcast_attempts,
+                last_rebroadcast_error: tracked_transaction
+                    .last_rebroadcast_error
+                    .unwrap_or_default(),
+                transaction: Some(tracked_transaction.transaction.into()),
+            })
+            .collect();
+
+        Ok(ListPendingTransactionsResponse {
+            pending_transactions,
+        })
+    }
+}
@@ -1,10 +1,35 @@
+mod abandon_transaction;
+mod accelerate;
+mod add_contact;
 mod broadcast;
+mod bump_fee;
 mod common;
+mod consolidate;
+mod create_invoice;
 mod create_unsigned_transaction;
+mod freeze_utxos;
 mod get_addresses;
 mod get_balance;
+mod get_invoice;
+mod get_max_sendable;
+mod get_transaction_status;
 mod get_utxos;
 pub mod kaswallet_service;
+mod list_address_labels;
+mod list_contacts;
+mod list_frozen_utxos;
+mod list_invoice_events;
+mod list_invoices;
+mod list_pending_transactions;
 mod new_address;
+mod release_reservation;
+mod remove_contact;
 mod send;
+mod set_address_label;
 mod sign;
+mod sign_message;
+mod sweep_dust;
+mod sweep_external;
+mod unfreeze_utxos;
+mod validate_address;
+mod verify_message;
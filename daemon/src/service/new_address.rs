@@ -1,20 +1,48 @@
 use crate::service::kaswallet_service::KasWalletService;
-use common::errors::WalletResult;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
 use proto::kaswallet_proto::{NewAddressRequest, NewAddressResponse};
 
+/// Upper bound on the addresses a single NewAddress request may issue.
+const MAX_NEW_ADDRESSES_PER_REQUEST: u32 = 10_000;
+
 impl KasWalletService {
     pub(crate) async fn new_address(
         &self,
-        _request: NewAddressRequest,
+        request: NewAddressRequest,
     ) -> WalletResult<NewAddressResponse> {
         self.check_is_synced().await?;
 
-        let address_manager = self.address_manager.lock().await;
+        let count = request.count.max(1);
+        if count > MAX_NEW_ADDRESSES_PER_REQUEST {
+            return Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: format!(
+                    "count must be at most {MAX_NEW_ADDRESSES_PER_REQUEST}, got {count}"
+                ),
+                location: ErrorLocation::capture(),
+            }));
+        }
 
-        let (address_string, _) = address_manager.new_address().await?;
+        let new_addresses = {
+            let address_manager = self.address_manager.lock().await;
+            address_manager.new_addresses(count).await?
+        };
+        // Usually a no-op: the sync loop scans well past the last used index.
+        let last_issued_index = new_addresses
+            .last()
+            .map(|(_, wallet_address)| wallet_address.index)
+            .unwrap_or_default();
+        self.sync_manager
+            .collect_issued_addresses(last_issued_index)
+            .await?;
 
+        let addresses: Vec<String> = new_addresses
+            .into_iter()
+            .map(|(address_string, _)| address_string)
+            .collect();
         Ok(NewAddressResponse {
-            address: address_string,
+            address: addresses[0].clone(),
+            addresses,
         })
     }
 }
@@ -0,0 +1,35 @@
+use crate::service::common::parse_transaction_id;
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{ReleaseReservationRequest, ReleaseReservationResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn release_reservation(
+        &self,
+        request: ReleaseReservationRequest,
+    ) -> WalletResult<ReleaseReservationResponse> {
+        let transaction_ids = request
+            .transaction_ids
+            .iter()
+            .map(|transaction_id| parse_transaction_id(transaction_id))
+            .collect::<WalletResult<Vec<_>>>()?;
+
+        let released_outpoints: Vec<_> = {
+            let mut utxo_manager = self.utxo_manager.lock().await;
+            transaction_ids
+                .iter()
+                .flat_map(|transaction_id| utxo_manager.release_reservation(transaction_id))
+                .collect()
+        };
+        info!(
+            "Released reservations of {} transactions covering {} UTXOs",
+            transaction_ids.len(),
+            released_outpoints.len()
+        );
+
+        Ok(ReleaseReservationResponse {
+            released_outpoints: released_outpoints.into_iter().map(Into::into).collect(),
+        })
+    }
+}
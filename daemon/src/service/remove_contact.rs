@@ -0,0 +1,24 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use proto::kaswallet_proto::{RemoveContactRequest, RemoveContactResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn remove_contact(
+        &self,
+        request: RemoveContactRequest,
+    ) -> WalletResult<RemoveContactResponse> {
+        let removed = self
+            .address_book
+            .lock()
+            .await
+            .remove_contact(&request.alias)?;
+        if let Some(contact) = &removed {
+            info!("Removed contact {} for {}", request.alias, contact.address);
+        }
+
+        Ok(RemoveContactResponse {
+            removed: removed.is_some(),
+        })
+    }
+}
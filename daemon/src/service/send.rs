@@ -1,6 +1,7 @@
 use crate::service::kaswallet_service::KasWalletService;
 use common::error_location::ErrorLocation;
 use common::errors::{UserInputError, WalletError, WalletResult};
+use prost::Message;
 use proto::kaswallet_proto::{SendRequest, SendResponse};
 use secrecy::SecretString;
 use std::time::Instant;
@@ -27,9 +28,41 @@ impl KasWalletService {
             transaction_description
         );
 
+        // The password is deliberately not part of the fingerprint: a retry that fixes a typo
+        // in it is the same payment.
+        let fingerprint = hex::encode(transaction_description.encode_to_vec());
+        if let Some(transaction_ids) = self
+            .idempotent_transaction_ids(&request.idempotency_key, &fingerprint)
+            .await?
+        {
+            drop(utxo_manager);
+            info!(
+                idempotency_key = %request.idempotency_key,
+                "Send request repeats an earlier one; returning its transactions"
+            );
+            let statuses = match request.wait_for_confirmations {
+                Some(depth) => {
+                    self.wait_for_transaction_confirmations(
+                        &transaction_ids,
+                        depth,
+                        request.wait_timeout_millis,
+                    )
+                    .await?
+                }
+                None => vec![],
+            };
+            return Ok(SendResponse {
+                transaction_ids,
+                signed_transactions: vec![],
+                statuses,
+                idempotent_replay: true,
+                applied_fee: None,
+            });
+        }
+
         debug!("Creating unsigned transactions...");
 
-        let unsigned_transactions = self
+        let (unsigned_transactions, fee_limits) = self
             .create_unsigned_transactions_from_description(transaction_description, &utxo_manager)
             .await?;
         debug!("Created {} transactions", unsigned_transactions.len());
@@ -43,9 +76,28 @@ impl KasWalletService {
 
         debug!("Submitting transactions...");
         let transaction_ids = self
-            .submit_transactions(&mut utxo_manager, &signed_transactions)
+            .submit_transactions_idempotently(
+                &mut utxo_manager,
+                &signed_transactions,
+                &request.idempotency_key,
+                &fingerprint,
+            )
             .await?;
         debug!("Transactions submitted: {:?}", transaction_ids);
+        // Release the UTXO manager before waiting, so syncs can observe the acceptance.
+        drop(utxo_manager);
+
+        let statuses = match request.wait_for_confirmations {
+            Some(depth) => {
+                self.wait_for_transaction_confirmations(
+                    &transaction_ids,
+                    depth,
+                    request.wait_timeout_millis,
+                )
+                .await?
+            }
+            None => vec![],
+        };
 
         info!(
             "Total time to serve send request: {:?}",
@@ -54,6 +106,9 @@ impl KasWalletService {
         Ok(SendResponse {
             transaction_ids,
             signed_transactions: signed_transactions.into_iter().map(Into::into).collect(),
+            statuses,
+            idempotent_replay: false,
+            applied_fee: Some(fee_limits.into()),
         })
     }
 }
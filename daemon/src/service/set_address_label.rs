@@ -0,0 +1,33 @@
+use crate::service::common::parse_address;
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use proto::kaswallet_proto::{SetAddressLabelRequest, SetAddressLabelResponse};
+
+impl KasWalletService {
+    pub(crate) async fn set_address_label(
+        &self,
+        request: SetAddressLabelRequest,
+    ) -> WalletResult<SetAddressLabelResponse> {
+        let address = parse_address(&request.address)?.to_string();
+        // Only the wallet's own addresses are labelled; external ones go in contacts.
+        let is_mine = {
+            let address_manager = self.address_manager.lock().await;
+            address_manager.address_set().await.contains_key(&address)
+        };
+        if !is_mine {
+            return Err(WalletError::from(UserInputError::InvalidAddress {
+                input: address,
+                reason: "Address not found in wallet".into(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        self.address_book
+            .lock()
+            .await
+            .set_label(&address, &request.label)?;
+
+        Ok(SetAddressLabelResponse {})
+    }
+}
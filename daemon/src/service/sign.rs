@@ -4,7 +4,7 @@ use common::errors::{CryptoError, TransactionError, WalletError, WalletResult};
 use common::keys::master_key_path;
 use common::model::WalletSignableTransaction;
 use itertools::Itertools;
-use kaspa_bip32::{ExtendedPrivateKey, Mnemonic, SecretKey, secp256k1};
+use kaspa_bip32::{DerivationPath, ExtendedPrivateKey, Mnemonic, SecretKey, secp256k1};
 use kaspa_consensus_core::hashing::sighash::{
     SigHashReusedValuesUnsync, calc_schnorr_signature_hash,
 };
@@ -77,6 +77,32 @@ impl KasWalletService {
         Ok(signed_transactions)
     }
 
+    /// Sign `unsigned_transactions` with raw private keys held outside the wallet. Fails unless
+    /// every input is spendable by one of `private_keys`.
+    pub(crate) fn sign_transactions_with_private_keys(
+        unsigned_transactions: Vec<WalletSignableTransaction>,
+        private_keys: &[[u8; 32]],
+    ) -> WalletResult<Vec<WalletSignableTransaction>> {
+        let mut signed_transactions = vec![];
+        for unsigned_transaction in unsigned_transactions {
+            let signed_transaction =
+                sign_with_multiple(unsigned_transaction.transaction.into_inner(), private_keys);
+            Self::sanity_check_verify(&signed_transaction)?;
+            if let Partially(_) = signed_transaction {
+                return Err(WalletError::from(TransactionError::NotFullySigned {
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            signed_transactions.push(WalletSignableTransaction::new(
+                signed_transaction.into(),
+                unsigned_transaction.derivation_paths,
+                unsigned_transaction.address_by_input_index,
+                unsigned_transaction.address_by_output_index,
+            ));
+        }
+        Ok(signed_transactions)
+    }
+
     pub(crate) fn sign_transaction(
         &self,
         unsigned_transaction: WalletSignableTransaction,
@@ -84,16 +110,7 @@ impl KasWalletService {
     ) -> WalletResult<Signed> {
         let mut private_keys = vec![];
         for derivation_path in &unsigned_transaction.derivation_paths {
-            for extended_private_key in extended_private_keys.iter() {
-                let private_key = extended_private_key
-                    .clone()
-                    .derive_path(derivation_path)
-                    .map_err(|e| CryptoError::Bip32Derivation {
-                        reason: e.to_string(),
-                        location: ErrorLocation::capture(),
-                    })?;
-                private_keys.push(private_key.private_key().secret_bytes());
-            }
+            private_keys.extend(derive_private_keys(extended_private_keys, derivation_path)?);
         }
 
         let signable_transaction = unsigned_transaction.transaction;
@@ -129,7 +146,7 @@ impl KasWalletService {
         Ok(())
     }
 
-    fn mnemonics_to_private_keys(
+    pub(crate) fn mnemonics_to_private_keys(
         mnemonics: &[Mnemonic],
     ) -> WalletResult<Vec<ExtendedPrivateKey<SecretKey>>> {
         let is_multisig = mnemonics.len() > 1;
@@ -140,6 +157,27 @@ impl KasWalletService {
     }
 }
 
+/// The private key of each of `extended_private_keys` at `derivation_path`, relative to the
+/// master key.
+pub(crate) fn derive_private_keys(
+    extended_private_keys: &[ExtendedPrivateKey<SecretKey>],
+    derivation_path: &DerivationPath,
+) -> WalletResult<Vec<[u8; 32]>> {
+    extended_private_keys
+        .iter()
+        .map(|extended_private_key| {
+            let private_key = extended_private_key
+                .clone()
+                .derive_path(derivation_path)
+                .map_err(|e| CryptoError::Bip32Derivation {
+                    reason: e.to_string(),
+                    location: ErrorLocation::capture(),
+                })?;
+            Ok(private_key.private_key().secret_bytes())
+        })
+        .collect()
+}
+
 // Public helper function to convert a single mnemonic to master private key
 pub fn mnemonic_to_private_key(
     mnemonic: &Mnemonic,
@@ -0,0 +1,125 @@
+use crate::service::common::{address_schnorr_public_key, parse_address};
+use crate::service::kaswallet_service::KasWalletService;
+use crate::service::sign::derive_private_keys;
+use common::error_location::ErrorLocation;
+use common::errors::{CryptoError, UserInputError, WalletError, WalletResult};
+use kaspa_addresses::Address;
+use kaspa_wallet_core::message::{
+    PersonalMessage, SignMessageOptions, sign_message, verify_message,
+};
+use proto::kaswallet_proto::{SignMessageRequest, SignMessageResponse};
+use secrecy::SecretString;
+use zeroize::Zeroizing;
+
+impl KasWalletService {
+    pub(crate) async fn sign_message(
+        &self,
+        request: SignMessageRequest,
+    ) -> WalletResult<SignMessageResponse> {
+        let address = parse_address(&request.address)?;
+        // Fail before asking for the password if the address cannot sign messages.
+        address_schnorr_public_key(&address)?;
+        let derivation_path = {
+            let address_manager = self.address_manager.lock().await;
+            let wallet_address = address_manager
+                .wallet_address_from_string(&address.to_string())
+                .await
+                .ok_or_else(|| {
+                    WalletError::from(UserInputError::InvalidAddress {
+                        input: address.to_string(),
+                        reason: "Address not found in wallet".into(),
+                        location: ErrorLocation::capture(),
+                    })
+                })?;
+            address_manager.calculate_address_path(&wallet_address)?
+        };
+
+        let password = SecretString::from(request.password);
+        let mnemonics = self.keys.decrypt_mnemonics(&password)?;
+        let extended_private_keys = Self::mnemonics_to_private_keys(&mnemonics)?;
+        let private_keys = Zeroizing::new(derive_private_keys(
+            &extended_private_keys,
+            &derivation_path,
+        )?);
+        let private_key = private_keys.first().ok_or_else(|| {
+            WalletError::from(CryptoError::MessageSigningFailed {
+                reason: "the wallet holds no private key".to_string(),
+                location: ErrorLocation::capture(),
+            })
+        })?;
+
+        Ok(SignMessageResponse {
+            signature: sign_personal_message(&request.message, private_key, &address)?,
+        })
+    }
+}
+
+/// Sign `message` with `private_key`, the key of `address`, returning the hex signature.
+fn sign_personal_message(
+    message: &str,
+    private_key: &[u8; 32],
+    address: &Address,
+) -> WalletResult<String> {
+    let public_key = address_schnorr_public_key(address)?;
+    let message = PersonalMessage(message);
+    let options = SignMessageOptions { no_aux_rand: false };
+    let signature = sign_message(&message, private_key, &options).map_err(|e| {
+        CryptoError::MessageSigningFailed {
+            reason: e.to_string(),
+            location: ErrorLocation::capture(),
+        }
+    })?;
+    // Same sanity check as for transactions: never hand out a signature that does not verify.
+    verify_message(&message, &signature, &public_key).map_err(|e| {
+        CryptoError::MessageSigningFailed {
+            reason: format!("signature does not verify against {address}: {e}"),
+            location: ErrorLocation::capture(),
+        }
+    })?;
+    Ok(hex::encode(signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::verify_message::verify_personal_message;
+    use kaspa_addresses::{Prefix, Version};
+    use kaspa_bip32::secp256k1::{Keypair, Secp256k1};
+
+    fn key_and_address(secret: u8) -> ([u8; 32], Address) {
+        let private_key = [secret; 32];
+        let keypair = Keypair::from_seckey_slice(&Secp256k1::new(), &private_key).unwrap();
+        let (public_key, _) = keypair.x_only_public_key();
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &public_key.serialize());
+        (private_key, address)
+    }
+
+    #[test]
+    fn signed_message_verifies_against_the_signing_address() {
+        let (private_key, address) = key_and_address(7);
+        let signature = sign_personal_message("hello kaspa", &private_key, &address).unwrap();
+        assert!(verify_personal_message("hello kaspa", &signature, &address).unwrap());
+    }
+
+    #[test]
+    fn tampered_message_does_not_verify() {
+        let (private_key, address) = key_and_address(7);
+        let signature = sign_personal_message("hello kaspa", &private_key, &address).unwrap();
+        assert!(!verify_personal_message("hello kaspa!", &signature, &address).unwrap());
+    }
+
+    #[test]
+    fn signature_does_not_verify_against_another_address() {
+        let (private_key, address) = key_and_address(7);
+        let (_, other_address) = key_and_address(8);
+        let signature = sign_personal_message("hello kaspa", &private_key, &address).unwrap();
+        assert!(!verify_personal_message("hello kaspa", &signature, &other_address).unwrap());
+    }
+
+    #[test]
+    fn signing_with_a_key_that_is_not_the_addresss_fails() {
+        let (_, address) = key_and_address(7);
+        let (other_private_key, _) = key_and_address(8);
+        assert!(sign_personal_message("hello kaspa", &other_private_key, &address).is_err());
+    }
+}
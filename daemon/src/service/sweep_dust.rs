@@ -0,0 +1,97 @@
+use crate::service::kaswallet_service::KasWalletService;
+use crate::transaction_generator::DustSweep;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use common::model::WalletAddress;
+use proto::kaswallet_proto::{SweepDustRequest, SweepDustResponse};
+use secrecy::SecretString;
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn sweep_dust(
+        &self,
+        request: SweepDustRequest,
+    ) -> WalletResult<SweepDustResponse> {
+        self.check_is_synced().await?;
+
+        let virtual_daa_score = self.get_virtual_daa_score().await?;
+        let dust_fee_rate = self.dust_reference_fee_rate().await?;
+        let from_addresses: Vec<WalletAddress> = {
+            let address_manager = self.address_manager.lock().await;
+            let address_set = address_manager.address_set().await;
+            let mut from_addresses = vec![];
+            for address_string in request.from_addresses {
+                let wallet_address = address_set.get(&address_string).ok_or_else(|| {
+                    WalletError::from(UserInputError::InvalidAddress {
+                        input: address_string.clone(),
+                        reason: "From address is not in address set".to_string(),
+                        location: ErrorLocation::capture(),
+                    })
+                })?;
+                from_addresses.push(wallet_address.clone());
+            }
+            from_addresses
+        };
+
+        // Held through submit so a sync or a concurrent send cannot pick the same UTXOs.
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        let mut dust_utxos = vec![];
+        for utxo in utxo_manager.utxos_sorted_by_amount() {
+            if utxo_manager.is_utxo_unspendable(&utxo, virtual_daa_score)
+                || utxo_manager.is_utxo_held_back(&utxo.outpoint)
+                || (!from_addresses.is_empty() && !from_addresses.contains(&utxo.address))
+            {
+                continue;
+            }
+            if self.is_utxo_dust(&utxo, dust_fee_rate).await? {
+                dust_utxos.push(utxo);
+            }
+        }
+        let dust_utxo_count = dust_utxos.len() as u32;
+
+        let DustSweep {
+            transactions,
+            dust_value,
+            fee,
+            is_economic,
+            fee_limits,
+        } = {
+            let transaction_generator = self.transaction_generator.lock().await;
+            transaction_generator
+                .create_dust_sweep_transactions(
+                    &utxo_manager,
+                    dust_utxos,
+                    request.fee_policy,
+                    request.dry_run,
+                )
+                .await?
+        };
+
+        let transaction_ids = if request.dry_run || transactions.is_empty() {
+            vec![]
+        } else {
+            let password = SecretString::from(request.password);
+            let signed_transactions = self.sign_transactions(transactions, &password).await?;
+            let transaction_ids = self
+                .submit_transactions(&mut utxo_manager, &signed_transactions)
+                .await?;
+            info!(
+                dust_utxo_count,
+                dust_value,
+                fee,
+                transaction_count = transaction_ids.len(),
+                "dust sweep transactions submitted"
+            );
+            transaction_ids
+        };
+
+        Ok(SweepDustResponse {
+            dust_utxo_count,
+            dust_value,
+            fee,
+            is_economic,
+            applied_fee: Some(fee_limits.into()),
+            transaction_ids,
+        })
+    }
+}
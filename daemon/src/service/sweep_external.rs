@@ -0,0 +1,220 @@
+use crate::external_keys::{
+    ExternalKey, ExternalUtxo, derive_external_key, mnemonic_account_key, next_scan_batch,
+    parse_private_key, resolve_gap_limit,
+};
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{RpcError, UserInputError, WalletError, WalletResult};
+use common::model::{Keychain, WalletUtxoEntry};
+use kaspa_addresses::{Address, Prefix};
+use kaspa_wallet_core::rpc::RpcApi;
+use proto::kaswallet_proto::{
+    ExternalMnemonic, SweepExternalRequest, SweepExternalResponse, sweep_external_request,
+};
+use tracing::info;
+use zeroize::Zeroizing;
+
+impl KasWalletService {
+    pub(crate) async fn sweep_external(
+        &self,
+        request: SweepExternalRequest,
+    ) -> WalletResult<SweepExternalResponse> {
+        self.check_is_synced().await?;
+
+        let virtual_daa_score = self.get_virtual_daa_score().await?;
+        let coinbase_maturity = self.utxo_manager.lock().await.coinbase_maturity();
+        let (peeked_address, _) = self.address_manager.lock().await.peek_new_address().await?;
+        let prefix = peeked_address.prefix;
+
+        let (keys, utxos) = match request.key {
+            Some(sweep_external_request::Key::PrivateKey(private_key)) => {
+                let private_key = Zeroizing::new(private_key);
+                let key = ExternalKey::new(parse_private_key(&private_key)?, prefix)?;
+                let utxos = self
+                    .external_utxos(&[&key.address], virtual_daa_score, coinbase_maturity)
+                    .await?;
+                (vec![key], utxos)
+            }
+            Some(sweep_external_request::Key::Mnemonic(mnemonic)) => {
+                self.discover_mnemonic_utxos(mnemonic, prefix, virtual_daa_score, coinbase_maturity)
+                    .await?
+            }
+            None => {
+                return Err(WalletError::from(UserInputError::MissingField {
+                    field: "key",
+                    location: ErrorLocation::capture(),
+                }));
+            }
+        };
+
+        let mut from_addresses: Vec<String> = vec![];
+        for utxo in &utxos {
+            let address = utxo.address.to_string();
+            if !from_addresses.contains(&address) {
+                from_addresses.push(address);
+            }
+        }
+        let utxo_count = utxos.len() as u32;
+        let swept_value: u64 = utxos.iter().map(|utxo| utxo.utxo_entry.amount).sum();
+        if utxos.is_empty() {
+            return Ok(SweepExternalResponse {
+                to_address: peeked_address.to_string(),
+                from_addresses,
+                utxo_count,
+                swept_value,
+                fee: 0,
+                applied_fee: None,
+                transaction_ids: vec![],
+            });
+        }
+
+        let to_address = if request.dry_run {
+            peeked_address
+        } else {
+            let (to_address, wallet_address) = {
+                let address_manager = self.address_manager.lock().await;
+                let (_, wallet_address) = address_manager.new_address().await?;
+                let to_address = address_manager
+                    .kaspa_address_from_wallet_address(&wallet_address, true)
+                    .await?;
+                (to_address, wallet_address)
+            };
+            self.sync_manager
+                .collect_issued_addresses(wallet_address.index)
+                .await?;
+            to_address
+        };
+
+        // Held through submit so the wallet's view picks up the swept outputs atomically.
+        let mut utxo_manager = self.utxo_manager.lock().await;
+        let (transactions, fee_limits) = {
+            let transaction_generator = self.transaction_generator.lock().await;
+            transaction_generator
+                .create_external_sweep_transactions(&utxos, to_address.clone(), request.fee_policy)
+                .await?
+        };
+        let swept_to_wallet: u64 = transactions
+            .iter()
+            .flat_map(|transaction| transaction.transaction.inner().tx.outputs.iter())
+            .map(|output| output.value)
+            .sum();
+        let fee = swept_value.saturating_sub(swept_to_wallet);
+
+        let transaction_ids = if request.dry_run {
+            vec![]
+        } else {
+            let private_keys: Zeroizing<Vec<[u8; 32]>> =
+                Zeroizing::new(keys.iter().map(|key| *key.secret_bytes()).collect());
+            let signed_transactions =
+                Self::sign_transactions_with_private_keys(transactions, &private_keys)?;
+            let transaction_ids = self
+                .submit_transactions(&mut utxo_manager, &signed_transactions)
+                .await?;
+            info!(
+                from_address_count = from_addresses.len(),
+                utxo_count,
+                swept_value,
+                fee,
+                to_address = %to_address,
+                transaction_count = transaction_ids.len(),
+                "external sweep transactions submitted"
+            );
+            transaction_ids
+        };
+
+        Ok(SweepExternalResponse {
+            to_address: to_address.to_string(),
+            from_addresses,
+            utxo_count,
+            swept_value,
+            fee,
+            applied_fee: Some(fee_limits.into()),
+            transaction_ids,
+        })
+    }
+
+    /// Scan both keychains of `mnemonic` until `gap_limit` consecutive addresses hold no UTXOs,
+    /// returning the keys that hold any and their spendable UTXOs.
+    async fn discover_mnemonic_utxos(
+        &self,
+        mnemonic: ExternalMnemonic,
+        prefix: Prefix,
+        virtual_daa_score: u64,
+        coinbase_maturity: u64,
+    ) -> WalletResult<(Vec<ExternalKey>, Vec<ExternalUtxo>)> {
+        let gap_limit = resolve_gap_limit(mnemonic.gap_limit)?;
+        let phrase = Zeroizing::new(mnemonic.phrase);
+        let passphrase = Zeroizing::new(mnemonic.passphrase);
+        let account_key = mnemonic_account_key(&phrase, &passphrase, &mnemonic.derivation_path)?;
+
+        let mut used_keys = vec![];
+        let mut utxos = vec![];
+        for keychain in [Keychain::External, Keychain::Internal] {
+            let mut last_used_index: Option<u32> = None;
+            let mut next_index = 0;
+            while let Some(batch) = next_scan_batch(next_index, last_used_index, gap_limit) {
+                let keys = batch
+                    .clone()
+                    .map(|index| derive_external_key(&account_key, keychain.clone(), index, prefix))
+                    .collect::<WalletResult<Vec<_>>>()?;
+                let addresses: Vec<&Address> = keys.iter().map(|key| &key.address).collect();
+                let batch_utxos = self
+                    .external_utxos(&addresses, virtual_daa_score, coinbase_maturity)
+                    .await?;
+                for (index, key) in batch.clone().zip(keys) {
+                    if batch_utxos.iter().any(|utxo| utxo.address == key.address) {
+                        last_used_index = Some(index);
+                        used_keys.push(key);
+                    }
+                }
+                utxos.extend(batch_utxos);
+                next_index = batch.end;
+            }
+        }
+        Ok((used_keys, utxos))
+    }
+
+    /// Spendable UTXOs of `addresses` according to kaspad. Covenant-bound and immature coinbase
+    /// UTXOs are left behind.
+    async fn external_utxos(
+        &self,
+        addresses: &[&Address],
+        virtual_daa_score: u64,
+        coinbase_maturity: u64,
+    ) -> WalletResult<Vec<ExternalUtxo>> {
+        let rpc_utxo_entries = self
+            .kaspa_client
+            .get_utxos_by_addresses(addresses.iter().map(|&address| address.clone()).collect())
+            .await
+            .map_err(|e| RpcError::Transport {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            })?;
+
+        let mut utxos = vec![];
+        for rpc_utxo_entry in rpc_utxo_entries {
+            let entry = &rpc_utxo_entry.utxo_entry;
+            if entry.covenant_id.is_some()
+                || (entry.is_coinbase
+                    && entry.block_daa_score + coinbase_maturity > virtual_daa_score)
+            {
+                continue;
+            }
+            let Some(address) = rpc_utxo_entry.address else {
+                continue;
+            };
+            utxos.push(ExternalUtxo {
+                outpoint: rpc_utxo_entry.outpoint.into(),
+                utxo_entry: WalletUtxoEntry::new(
+                    entry.amount,
+                    entry.script_public_key.clone(),
+                    entry.block_daa_score,
+                    entry.is_coinbase,
+                    false,
+                ),
+                address,
+            });
+        }
+        Ok(utxos)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use common::model::WalletOutpoint;
+use proto::kaswallet_proto::{UnfreezeUtxosRequest, UnfreezeUtxosResponse};
+use tracing::info;
+
+impl KasWalletService {
+    pub(crate) async fn unfreeze_utxos(
+        &self,
+        request: UnfreezeUtxosRequest,
+    ) -> WalletResult<UnfreezeUtxosResponse> {
+        let outpoints = request
+            .outpoints
+            .into_iter()
+            .map(WalletOutpoint::try_from)
+            .collect::<WalletResult<Vec<_>>>()?;
+
+        let unfrozen_outpoints = {
+            let mut utxo_manager = self.utxo_manager.lock().await;
+            utxo_manager.unfreeze_utxos(&outpoints)?
+        };
+        info!("Unfroze {} UTXOs", unfrozen_outpoints.len());
+
+        Ok(UnfreezeUtxosResponse {
+            unfrozen_outpoints: unfrozen_outpoints.into_iter().map(Into::into).collect(),
+        })
+    }
+}
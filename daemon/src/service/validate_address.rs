@@ -0,0 +1,133 @@
+use crate::service::kaswallet_service::KasWalletService;
+use common::errors::WalletResult;
+use kaspa_addresses::{Address, Prefix};
+use proto::kaswallet_proto::{AddressVersion, ValidateAddressRequest, ValidateAddressResponse};
+
+impl KasWalletService {
+    pub(crate) async fn validate_address(
+        &self,
+        request: ValidateAddressRequest,
+    ) -> WalletResult<ValidateAddressResponse> {
+        self.check_is_synced().await?;
+
+        let address_manager = self.address_manager.lock().await;
+        let (response, address) =
+            validate_address_string(&request.address, address_manager.prefix());
+        let Some(address) = address else {
+            return Ok(response);
+        };
+
+        let wallet_address = address_manager
+            .wallet_address_from_string(&address.to_string())
+            .await;
+        let derivation_path = match &wallet_address {
+            Some(wallet_address) => address_manager
+                .calculate_full_address_path(wallet_address)?
+                .to_string(),
+            None => String::new(),
+        };
+
+        Ok(ValidateAddressResponse {
+            is_mine: wallet_address.is_some(),
+            wallet_address: wallet_address.map(Into::into),
+            derivation_path,
+            ..response
+        })
+    }
+}
+
+/// The part of a `ValidateAddressResponse` that does not depend on the wallet's keys, for `input`
+/// checked against a daemon serving `network_prefix`. Returns the parsed address if it is valid.
+fn validate_address_string(
+    input: &str,
+    network_prefix: Prefix,
+) -> (ValidateAddressResponse, Option<Address>) {
+    match Address::try_from(input.trim()) {
+        Ok(address) => (
+            ValidateAddressResponse {
+                is_valid: true,
+                is_network_match: address.prefix == network_prefix,
+                prefix: address.prefix.to_string(),
+                version: AddressVersion::from(address.version) as i32,
+                network_prefix: network_prefix.to_string(),
+                ..Default::default()
+            },
+            Some(address),
+        ),
+        Err(e) => (
+            ValidateAddressResponse {
+                is_valid: false,
+                invalid_reason: e.to_string(),
+                network_prefix: network_prefix.to_string(),
+                ..Default::default()
+            },
+            None,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+
+    fn address_string(prefix: Prefix, version: Version) -> String {
+        Address::new(prefix, version, &[7u8; 32]).to_string()
+    }
+
+    #[test]
+    fn valid_p2pk_address_on_the_daemons_network() {
+        let (response, address) = validate_address_string(
+            &address_string(Prefix::Mainnet, Version::PubKey),
+            Prefix::Mainnet,
+        );
+        assert!(address.is_some());
+        assert!(response.is_valid);
+        assert!(response.is_network_match);
+        assert_eq!(response.prefix, "kaspa");
+        assert_eq!(response.network_prefix, "kaspa");
+        assert_eq!(response.version, AddressVersion::PubKey as i32);
+        assert!(response.invalid_reason.is_empty());
+    }
+
+    #[test]
+    fn valid_p2sh_address_on_the_daemons_network() {
+        let (response, _) = validate_address_string(
+            &address_string(Prefix::Testnet, Version::ScriptHash),
+            Prefix::Testnet,
+        );
+        assert!(response.is_valid);
+        assert!(response.is_network_match);
+        assert_eq!(response.prefix, "kaspatest");
+        assert_eq!(response.network_prefix, "kaspatest");
+        assert_eq!(response.version, AddressVersion::ScriptHash as i32);
+    }
+
+    #[test]
+    fn address_for_another_network_is_valid_but_does_not_match() {
+        let (response, address) = validate_address_string(
+            &address_string(Prefix::Testnet, Version::PubKey),
+            Prefix::Mainnet,
+        );
+        assert!(address.is_some());
+        assert!(response.is_valid);
+        assert!(!response.is_network_match);
+        assert_eq!(response.prefix, "kaspatest");
+        assert_eq!(response.network_prefix, "kaspa");
+    }
+
+    #[test]
+    fn address_with_a_malformed_checksum_is_invalid() {
+        let mut input = address_string(Prefix::Mainnet, Version::PubKey);
+        let last = input.pop().unwrap();
+        input.push(if last == 'q' { 'p' } else { 'q' });
+
+        let (response, address) = validate_address_string(&input, Prefix::Mainnet);
+        assert!(address.is_none());
+        assert!(!response.is_valid);
+        assert!(!response.invalid_reason.is_empty());
+        assert!(!response.is_network_match);
+        assert!(response.prefix.is_empty());
+        assert_eq!(response.network_prefix, "kaspa");
+    }
+}
@@ -0,0 +1,43 @@
+use crate::service::common::{address_schnorr_public_key, parse_address};
+use crate::service::kaswallet_service::KasWalletService;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use kaspa_addresses::Address;
+use kaspa_wallet_core::message::{PersonalMessage, verify_message};
+use proto::kaswallet_proto::{VerifyMessageRequest, VerifyMessageResponse};
+
+/// Schnorr signature length, in bytes.
+const SIGNATURE_LENGTH: usize = 64;
+
+impl KasWalletService {
+    pub(crate) async fn verify_message(
+        &self,
+        request: VerifyMessageRequest,
+    ) -> WalletResult<VerifyMessageResponse> {
+        let address = parse_address(&request.address)?;
+        Ok(VerifyMessageResponse {
+            is_valid: verify_personal_message(&request.message, &request.signature, &address)?,
+        })
+    }
+}
+
+/// Whether `signature_hex` is a valid signature of `message` by the key of `address`.
+pub(crate) fn verify_personal_message(
+    message: &str,
+    signature_hex: &str,
+    address: &Address,
+) -> WalletResult<bool> {
+    let public_key = address_schnorr_public_key(address)?;
+    let signature = hex::decode(signature_hex.trim())
+        .ok()
+        .filter(|signature| signature.len() == SIGNATURE_LENGTH)
+        .ok_or_else(|| {
+            WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("signature must be {} hex characters", SIGNATURE_LENGTH * 2),
+                location: ErrorLocation::capture(),
+            })
+        })?;
+
+    let message = PersonalMessage(message);
+    Ok(verify_message(&message, &signature, &public_key).is_ok())
+}
@@ -0,0 +1,139 @@
+use crate::args::parse_subnetwork_id_arg;
+use common::error_location::ErrorLocation;
+use common::errors::{UserInputError, WalletError, WalletResult};
+use kaspa_consensus_core::subnets::SubnetworkId;
+use std::fmt;
+use tracing::warn;
+
+/// The lanes a daemon builds, signs and relays transactions for: the `--subnetwork-id` default
+/// plus any `--allowed-subnetwork-id`.
+///
+/// A native default with no extra lanes preserves the generic kaspa wallet behavior and lets any
+/// wire-supplied transaction through; every other configuration gates by membership.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedSubnetworks {
+    // The default comes first.
+    allowed: Vec<SubnetworkId>,
+}
+
+impl AllowedSubnetworks {
+    pub fn new(default_subnetwork_id: SubnetworkId, additional: &[SubnetworkId]) -> Self {
+        let mut allowed = vec![default_subnetwork_id];
+        for subnetwork_id in additional {
+            if !allowed.contains(subnetwork_id) {
+                allowed.push(*subnetwork_id);
+            }
+        }
+        Self { allowed }
+    }
+
+    /// The lane used when a request does not choose one.
+    pub fn default_subnetwork_id(&self) -> SubnetworkId {
+        self.allowed[0]
+    }
+
+    pub fn is_restricted(&self) -> bool {
+        !(self.allowed.len() == 1 && self.allowed[0].is_native())
+    }
+
+    /// Reject a transaction whose subnetwork id is not one of the daemon's lanes.
+    pub fn ensure_allowed(&self, tx_subnetwork_id: &SubnetworkId) -> WalletResult<()> {
+        if !self.is_restricted() || self.allowed.contains(tx_subnetwork_id) {
+            return Ok(());
+        }
+        // Surface lane-mismatch attempts: silent rejections leave on-call with
+        // no signal that a probe or misroute happened. Emitted at warn so
+        // log aggregators flag it without paging.
+        warn!(
+            allowed = %self,
+            attempted = %tx_subnetwork_id,
+            "rejecting tx with a subnetwork id the daemon is not configured for"
+        );
+        Err(WalletError::from(UserInputError::InvalidArgument {
+            reason: format!(
+                "transaction subnetwork_id {tx_subnetwork_id} does not match daemon's \
+                 configured subnetwork_id(s) {self}",
+            ),
+            location: ErrorLocation::capture(),
+        }))
+    }
+
+    /// The lane requested by a `TransactionDescription`, given as the 4-byte namespace in the
+    /// same form as `--subnetwork-id`, or the default when unset.
+    pub fn resolve(&self, requested: Option<&str>) -> WalletResult<SubnetworkId> {
+        let Some(requested) = requested else {
+            return Ok(self.default_subnetwork_id());
+        };
+        let subnetwork_id = parse_subnetwork_id_arg(requested).map_err(|reason| {
+            WalletError::from(UserInputError::InvalidArgument {
+                reason: format!("invalid transaction_description.subnetwork_id: {reason}"),
+                location: ErrorLocation::capture(),
+            })
+        })?;
+        // A request may only pick a listed lane, even on an unrestricted native daemon.
+        if !self.allowed.contains(&subnetwork_id) {
+            return Err(WalletError::from(UserInputError::InvalidArgument {
+                reason: format!(
+                    "subnetwork_id {subnetwork_id} is not allowed; the daemon allows {self}"
+                ),
+                location: ErrorLocation::capture(),
+            }));
+        }
+        Ok(subnetwork_id)
+    }
+}
+
+impl fmt::Display for AllowedSubnetworks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, subnetwork_id) in self.allowed.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{subnetwork_id}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+
+    fn igra_lane() -> SubnetworkId {
+        parse_subnetwork_id_arg("97b10000").unwrap()
+    }
+
+    fn other_lane() -> SubnetworkId {
+        parse_subnetwork_id_arg("aabb0000").unwrap()
+    }
+
+    #[test]
+    fn native_with_extra_lanes_gates_wire_transactions() {
+        let subnetworks = AllowedSubnetworks::new(SUBNETWORK_ID_NATIVE, &[igra_lane()]);
+        assert!(subnetworks.is_restricted());
+        subnetworks.ensure_allowed(&SUBNETWORK_ID_NATIVE).unwrap();
+        subnetworks.ensure_allowed(&igra_lane()).unwrap();
+        assert!(subnetworks.ensure_allowed(&other_lane()).is_err());
+    }
+
+    #[test]
+    fn resolve_defaults_and_accepts_listed_lanes() {
+        let subnetworks = AllowedSubnetworks::new(SUBNETWORK_ID_NATIVE, &[igra_lane()]);
+        assert_eq!(subnetworks.resolve(None).unwrap(), SUBNETWORK_ID_NATIVE);
+        assert_eq!(subnetworks.resolve(Some("97b10000")).unwrap(), igra_lane());
+        assert_eq!(
+            subnetworks.resolve(Some("00000000")).unwrap(),
+            SUBNETWORK_ID_NATIVE
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_unlisted_and_malformed_lanes() {
+        let subnetworks = AllowedSubnetworks::new(SUBNETWORK_ID_NATIVE, &[]);
+        let err = subnetworks.resolve(Some("97b10000")).unwrap_err();
+        assert_eq!(err.kind_name(), "InvalidArgument", "got: {err}");
+        let err = subnetworks.resolve(Some("zz")).unwrap_err();
+        assert_eq!(err.kind_name(), "InvalidArgument", "got: {err}");
+    }
+}
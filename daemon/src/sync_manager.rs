@@ -6,7 +6,7 @@ use common::keys::Keys;
 use kaspa_addresses::Address;
 use kaspa_grpc_client::GrpcClient;
 use kaspa_wallet_core::rpc::RpcApi;
-use std::cmp::max;
+use std::cmp::{max, min};
 use std::sync::Arc;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::atomic::{AtomicBool, AtomicU32};
@@ -109,6 +109,18 @@ impl SyncManager {
         // we update the utxo set
         let mut utxo_manager = self.utxo_manager.lock().await;
 
+        // Fetched before the mempool snapshot so the expiry window of tracked mempool
+        // transactions is never measured against a score newer than the snapshot.
+        let virtual_daa_score = self
+            .kaspa_client
+            .get_block_dag_info()
+            .await
+            .map_err(|e| RpcError::Transport {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            })?
+            .virtual_daa_score;
+
         debug!("Getting mempool entries for addresses: {:?}...", addresses);
         let addresses_count = addresses.len();
         let mempool_entries_by_addresses = self
@@ -151,7 +163,11 @@ impl SyncManager {
         debug!("Got {} utxo entries", get_utxo_by_addresses_response.len());
 
         utxo_manager
-            .update_utxo_set(get_utxo_by_addresses_response, mempool_entries_by_addresses)
+            .update_utxo_set(
+                get_utxo_by_addresses_response,
+                mempool_entries_by_addresses,
+                virtual_daa_score,
+            )
             .await?;
 
         // Surface a per-sync summary of what we now hold, split by
@@ -228,6 +244,18 @@ impl SyncManager {
         Ok(())
     }
 
+    /// Extend discovery over addresses just issued past the range scanned so far, up to
+    /// `last_issued_index`, so the wallet stays synced without waiting for the sync loop to reach
+    /// them.
+    pub async fn collect_issued_addresses(&self, last_issued_index: u32) -> WalletResult<()> {
+        let start = self.next_sync_start_index.load(Relaxed);
+        for (batch_start, batch_end) in issued_address_batches(start, last_issued_index) {
+            self.collect_addresses(batch_start, batch_end).await?;
+            self.next_sync_start_index.fetch_max(batch_end, Relaxed);
+        }
+        Ok(())
+    }
+
     async fn collect_addresses(&self, start: u32, end: u32) -> WalletResult<()> {
         debug!("Collecting addresses from {} to {}", start, end);
 
@@ -306,3 +334,46 @@ impl SyncManager {
         }
     }
 }
+
+/// The `[start, end)` index ranges to collect so that discovery covers a gap limit past
+/// `last_issued_index`, starting from `next_sync_start_index`. Empty if that is already covered.
+fn issued_address_batches(next_sync_start_index: u32, last_issued_index: u32) -> Vec<(u32, u32)> {
+    let end = last_issued_index.saturating_add(NUM_INDEXES_TO_QUERY_FOR_FAR_ADDRESSES);
+    let mut batches = vec![];
+    let mut index = next_sync_start_index;
+    while index < end {
+        let batch_end = min(
+            index.saturating_add(NUM_INDEXES_TO_QUERY_FOR_RECENT_ADDRESSES),
+            end,
+        );
+        batches.push((index, batch_end));
+        index = batch_end;
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_within_the_scanned_range_need_no_collection() {
+        assert!(issued_address_batches(1_000, 10).is_empty());
+        assert!(issued_address_batches(1_000, 900).is_empty());
+    }
+
+    #[test]
+    fn a_batch_past_the_scanned_range_is_collected_up_to_its_gap_limit() {
+        assert_eq!(issued_address_batches(1_000, 901), vec![(1_000, 1_001)]);
+        assert_eq!(
+            issued_address_batches(1_000, 2_500),
+            vec![(1_000, 2_000), (2_000, 2_600)]
+        );
+    }
+
+    #[test]
+    fn batches_near_the_last_index_do_not_overflow() {
+        let batches = issued_address_batches(u32::MAX - 50, u32::MAX - 10);
+        assert_eq!(batches, vec![(u32::MAX - 50, u32::MAX)]);
+    }
+}
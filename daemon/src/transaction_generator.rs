@@ -1,12 +1,16 @@
 use crate::address_manager::AddressManager;
+use crate::coin_selection::{CoinSelection, CoinSelectionStrategy, SelectionTarget};
+use crate::external_keys::ExternalUtxo;
+use crate::subnetworks::AllowedSubnetworks;
 use crate::utxo_manager::UtxoManager;
 use common::error_location::ErrorLocation;
 use common::errors::{TransactionError, UserInputError as UserInputErr, WalletError, WalletResult};
 use common::keys::Keys;
 use common::model::{
-    WalletAddress, WalletOutpoint, WalletPayment, WalletSignableTransaction, WalletUtxo,
-    WalletUtxoEntry,
+    Keychain, PaymentDestination, WalletAddress, WalletOutpoint, WalletPayment,
+    WalletSignableTransaction, WalletUtxo, WalletUtxoEntry,
 };
+use common::proto_convert::script_public_key_from_proto;
 use itertools::Itertools;
 use kaspa_addresses::{Address, Version};
 use kaspa_bip32::DerivationPath;
@@ -16,20 +20,23 @@ use kaspa_consensus_core::constants::{
     UNACCEPTED_DAA_SCORE,
 };
 use kaspa_consensus_core::mass::{
-    GRAMS_PER_COMPUTE_BUDGET_UNIT, MassCofactors, NonContextualMasses,
-    transaction_estimated_serialized_size,
+    GRAMS_PER_COMPUTE_BUDGET_UNIT, MassCalculator as ConsensusMassCalculator, MassCofactors,
+    NonContextualMasses, transaction_estimated_serialized_size,
 };
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_consensus_core::tx::{
-    ComputeCommit, SignableTransaction, Transaction, TransactionInput, TransactionOutpoint,
-    TransactionOutput, UtxoEntry,
+    ComputeCommit, ScriptPublicKey, SignableTransaction, Transaction, TransactionInput,
+    TransactionOutpoint, TransactionOutput, UtxoEntry,
 };
 use kaspa_grpc_client::GrpcClient;
 use kaspa_rpc_core::api::rpc::RpcApi;
-use kaspa_txscript::pay_to_address_script;
+use kaspa_rpc_core::{RpcFeeEstimate, RpcFeerateBucket};
 use kaspa_wallet_core::prelude::AddressPrefix;
 use kaspa_wallet_core::tx::{MAXIMUM_STANDARD_TRANSACTION_MASS, MassCalculator, SIGNATURE_SIZE};
-use proto::kaswallet_proto::{FeePolicy, Outpoint, TransactionDescription, fee_policy};
+use proto::kaswallet_proto::{
+    AppliedFee, FeePolicy, FeePriority, Outpoint, ScriptPublicKey as ProtoScriptPublicKey,
+    TransactionDescription, fee_policy,
+};
 use std::cmp::min;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -39,13 +46,11 @@ use tracing::{debug, info};
 // The current minimal fee rate according to mempool standards
 const MIN_FEE_RATE: f64 = 1.0;
 
-// The minimal change amount to target in order to avoid large storage mass (see KIP9 for more details).
-// By having at least 10KAS in the change output we make sure that the storage mass charged for change is
-// at most 1000 gram. Generally, if the payment is above 10KAS as well, the resulting storage mass will be
-// in the order of magnitude of compute mass and will not incur additional charges.
-// Additionally, every transaction with send value > ~0.1 KAS should succeed (at most ~99K storage mass for payment
-// output, thus overall lower than standard mass upper bound which is 100K gram)
-const MIN_CHANGE_TARGET: u64 = SOMPI_PER_KASPA * 10;
+// Fee cap applied when the fee policy does not set one
+const DEFAULT_MAX_FEE: u64 = SOMPI_PER_KASPA;
+
+// Rebuilds allowed for a fee bump's fee to cover the storage mass of its reduced change
+const FEE_BUMP_RESIZE_ROUNDS: usize = 4;
 
 /// Pick the consensus transaction version for a given subnetwork.
 ///
@@ -108,14 +113,307 @@ fn estimate_transient_mass(tx: &Transaction, minimum_signatures: u16) -> u64 {
     signed_serialized_size * TRANSIENT_BYTE_TO_MASS_FACTOR
 }
 
+/// Returns `(total_ins, total_outs)` of a signable transaction. Inputs without a populated entry
+/// count as zero.
+fn total_input_and_output_value(signable_transaction: &SignableTransaction) -> (u64, u64) {
+    let total_ins: u64 = signable_transaction
+        .entries
+        .iter()
+        .map(|entry| match entry {
+            None => 0,
+            Some(entry) => entry.amount,
+        })
+        .sum();
+
+    let total_outs: u64 = signable_transaction
+        .tx
+        .outputs
+        .iter()
+        .map(|output| output.value)
+        .sum();
+
+    (total_ins, total_outs)
+}
+
+/// Fee of a signable transaction whose input entries are populated: inputs minus outputs, in
+/// sompi.
+pub(crate) fn transaction_fee(signable_transaction: &SignableTransaction) -> u64 {
+    let (total_ins, total_outs) = total_input_and_output_value(signable_transaction);
+    total_ins.saturating_sub(total_outs)
+}
+
+/// The smallest of `candidates` (sorted by amount) to merge into one so that `target_utxo_count`
+/// remain, or `None` if there are already no more than that.
+fn consolidation_inputs(
+    candidates: &[WalletUtxo],
+    target_utxo_count: usize,
+) -> Option<&[WalletUtxo]> {
+    if candidates.len() <= target_utxo_count {
+        return None;
+    }
+    // Merging the smallest `n - target + 1` UTXOs into one leaves exactly `target`.
+    Some(&candidates[..candidates.len() - target_utxo_count + 1])
+}
+
+/// The single output of merging `total_value` sompi of inputs while paying `fee`.
+fn merged_amount(total_value: u64, fee: u64) -> WalletResult<u64> {
+    if fee >= total_value {
+        return Err(WalletError::from(TransactionError::InsufficientFunds {
+            required_sompi: fee,
+            available_sompi: total_value,
+            location: ErrorLocation::capture(),
+        }));
+    }
+    Ok(total_value - fee)
+}
+
+/// How many split transactions `input_count` inputs of `mass_per_input` need when each has
+/// `mass_for_inputs` of room, and how many inputs each takes: `(split_count, inputs_per_split)`.
+fn split_counts(
+    input_count: u64,
+    mass_per_input: u64,
+    mass_for_inputs: u64,
+) -> WalletResult<(usize, usize)> {
+    let inputs_per_split_count = mass_for_inputs / mass_per_input.max(1);
+    if inputs_per_split_count == 0 {
+        return Err(WalletError::from(TransactionError::MassExceeded {
+            mass: mass_per_input,
+            limit: mass_for_inputs,
+            location: ErrorLocation::capture(),
+        }));
+    }
+    let split_count = input_count.div_ceil(inputs_per_split_count);
+    Ok((split_count as usize, inputs_per_split_count as usize))
+}
+
+/// Reject a requested fee rate below the mempool's minimum.
+fn ensure_minimum_fee_rate(fee_rate: f64) -> WalletResult<()> {
+    if fee_rate < MIN_FEE_RATE {
+        return Err(WalletError::from(TransactionError::FeeRateTooLow {
+            provided_fee_rate: fee_rate,
+            required_fee_rate: MIN_FEE_RATE,
+            location: ErrorLocation::capture(),
+        }));
+    }
+    Ok(())
+}
+
+/// Fee a child must pay to lift the parent+child package to `target_fee_rate`, after what the
+/// parent already pays. Never below the child's own minimum relay fee.
+fn child_pays_for_parent_fee(
+    parent_fee: u64,
+    parent_mass: u64,
+    child_mass: u64,
+    target_fee_rate: f64,
+) -> u64 {
+    let package_fee =
+        (parent_mass.saturating_add(child_mass) as f64 * target_fee_rate).ceil() as u64;
+    let child_minimum_fee = (child_mass as f64 * MIN_FEE_RATE).ceil() as u64;
+    package_fee
+        .saturating_sub(parent_fee)
+        .max(child_minimum_fee)
+}
+
+/// Fee for a selection of `input_count` inputs at `fee_per_input` each, capped at `max_fee` as a
+/// whole rather than per input.
+fn selection_fee(fee_per_input: u64, input_count: usize, max_fee: u64) -> u64 {
+    min(fee_per_input.saturating_mul(input_count as u64), max_fee)
+}
+
+/// What a selection worth `total_value` spends and what the recipient receives, as
+/// `(total_spend, total_received)`, for a payment of `amount` paying `fee`. A send-all spends the
+/// whole selection and ignores `amount`.
+fn payment_totals(
+    amount: u64,
+    total_value: u64,
+    fee: u64,
+    is_send_all: bool,
+    subtract_fee_from_amount: bool,
+) -> WalletResult<(u64, u64)> {
+    let (total_spend, total_received) = if is_send_all {
+        if total_value <= fee {
+            return Err(WalletError::from(TransactionError::InsufficientFunds {
+                required_sompi: fee.saturating_add(1),
+                available_sompi: total_value,
+                location: ErrorLocation::capture(),
+            }));
+        }
+        (total_value, total_value - fee)
+    } else if subtract_fee_from_amount {
+        if amount <= fee {
+            return Err(WalletError::from(TransactionError::InsufficientFunds {
+                required_sompi: fee,
+                available_sompi: amount,
+                location: ErrorLocation::capture(),
+            }));
+        }
+        (amount, amount - fee)
+    } else {
+        (amount.saturating_add(fee), amount)
+    };
+
+    if total_value < total_spend {
+        return Err(WalletError::from(TransactionError::InsufficientFunds {
+            required_sompi: total_spend,
+            available_sompi: total_value,
+            location: ErrorLocation::capture(),
+        }));
+    }
+    Ok((total_spend, total_received))
+}
+
+/// Fee rate and fee cap resolved from a `FeePolicy`, with kaspad's expected time to acceptance at
+/// that rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeLimits {
+    pub fee_rate: f64,
+    pub max_fee: u64,
+    pub estimated_seconds: f64,
+}
+
+impl From<FeeLimits> for AppliedFee {
+    fn from(value: FeeLimits) -> Self {
+        AppliedFee {
+            fee_rate: value.fee_rate,
+            estimated_seconds: value.estimated_seconds,
+        }
+    }
+}
+
+/// Resolve a `FeePolicy` against kaspad's current fee estimate. Free function (no generator
+/// state) so it is unit-testable.
+fn fee_limits_from_policy(
+    fee_estimate: &RpcFeeEstimate,
+    fee_policy: Option<fee_policy::FeePolicy>,
+) -> WalletResult<FeeLimits> {
+    let with_estimated_seconds = |fee_rate: f64, max_fee: u64| FeeLimits {
+        fee_rate,
+        max_fee,
+        estimated_seconds: estimated_seconds_for_fee_rate(fee_estimate, fee_rate),
+    };
+    let from_bucket = |bucket: &RpcFeerateBucket| FeeLimits {
+        fee_rate: bucket.feerate,
+        max_fee: DEFAULT_MAX_FEE,
+        estimated_seconds: bucket.estimated_seconds,
+    };
+    match fee_policy {
+        Some(fee_policy::FeePolicy::MaxFeeRate(requested_max_fee_rate)) => {
+            ensure_minimum_fee_rate(requested_max_fee_rate)?;
+            let fee_rate = f64::min(fee_estimate.priority_bucket.feerate, requested_max_fee_rate);
+            Ok(with_estimated_seconds(fee_rate, u64::MAX))
+        }
+        Some(fee_policy::FeePolicy::ExactFeeRate(requested_exact_fee_rate)) => {
+            ensure_minimum_fee_rate(requested_exact_fee_rate)?;
+            Ok(with_estimated_seconds(requested_exact_fee_rate, u64::MAX))
+        }
+        Some(fee_policy::FeePolicy::MaxFee(requested_max_fee)) => Ok(with_estimated_seconds(
+            fee_estimate.priority_bucket.feerate,
+            requested_max_fee,
+        )),
+        Some(fee_policy::FeePolicy::Priority(priority)) => {
+            let priority = FeePriority::try_from(priority)
+                .ok()
+                .filter(|priority| *priority != FeePriority::Unspecified)
+                .ok_or_else(|| {
+                    WalletError::from(UserInputErr::InvalidArgument {
+                        reason: format!(
+                            "fee_policy.priority must be low, normal or high, got {priority}"
+                        ),
+                        location: ErrorLocation::capture(),
+                    })
+                })?;
+            Ok(from_bucket(fee_bucket_for_priority(fee_estimate, priority)))
+        }
+        Some(fee_policy::FeePolicy::TargetSeconds(target_seconds)) => {
+            if target_seconds == 0 {
+                return Err(WalletError::from(UserInputErr::InvalidArgument {
+                    reason: "fee_policy.target_seconds must be positive".to_string(),
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            Ok(from_bucket(fee_bucket_for_target_seconds(
+                fee_estimate,
+                target_seconds,
+            )))
+        }
+        None => Ok(from_bucket(&fee_estimate.priority_bucket)),
+    }
+}
+
+/// The estimate's buckets, fastest (and most expensive) first.
+fn fee_buckets(fee_estimate: &RpcFeeEstimate) -> impl Iterator<Item = &RpcFeerateBucket> {
+    std::iter::once(&fee_estimate.priority_bucket)
+        .chain(&fee_estimate.normal_buckets)
+        .chain(&fee_estimate.low_buckets)
+}
+
+pub(crate) fn fee_bucket_for_priority(
+    fee_estimate: &RpcFeeEstimate,
+    priority: FeePriority,
+) -> &RpcFeerateBucket {
+    let normal = fee_estimate.normal_buckets.first();
+    match priority {
+        FeePriority::Low => fee_estimate.low_buckets.first().or(normal),
+        FeePriority::Normal => normal,
+        FeePriority::High | FeePriority::Unspecified => None,
+    }
+    .unwrap_or(&fee_estimate.priority_bucket)
+}
+
+/// The cheapest bucket kaspad expects to be accepted within `target_seconds`, or the priority
+/// bucket if none is fast enough.
+fn fee_bucket_for_target_seconds(
+    fee_estimate: &RpcFeeEstimate,
+    target_seconds: u32,
+) -> &RpcFeerateBucket {
+    fee_buckets(fee_estimate)
+        .filter(|bucket| bucket.estimated_seconds <= target_seconds as f64)
+        .last()
+        .unwrap_or(&fee_estimate.priority_bucket)
+}
+
+/// Expected time to acceptance at `fee_rate`: that of the fastest bucket the rate pays for, or
+/// of the slowest bucket if it pays for none.
+fn estimated_seconds_for_fee_rate(fee_estimate: &RpcFeeEstimate, fee_rate: f64) -> f64 {
+    let mut slowest = &fee_estimate.priority_bucket;
+    for bucket in fee_buckets(fee_estimate) {
+        if bucket.feerate <= fee_rate {
+            return bucket.estimated_seconds;
+        }
+        slowest = bucket;
+    }
+    slowest.estimated_seconds
+}
+
+/// Result of [`TransactionGenerator::max_sendable`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxSendable {
+    pub amount: u64,
+    /// Total fee of all transactions, including merge transactions.
+    pub fee: u64,
+    pub transaction_count: usize,
+    pub fee_limits: FeeLimits,
+}
+
+/// Result of [`TransactionGenerator::create_dust_sweep_transactions`].
+#[derive(Debug, Clone)]
+pub struct DustSweep {
+    /// Empty unless `is_economic`.
+    pub transactions: Vec<WalletSignableTransaction>,
+    pub dust_value: u64,
+    /// Total fee of `transactions`, or the estimated fee when nothing was built.
+    pub fee: u64,
+    pub is_economic: bool,
+    pub fee_limits: FeeLimits,
+}
+
 pub struct TransactionGenerator {
     kaspa_client: Arc<GrpcClient>,
     keys: Arc<Keys>,
     address_manager: Arc<Mutex<AddressManager>>,
     mass_calculator: Arc<MassCalculator>,
     address_prefix: AddressPrefix,
-    subnetwork_id: SubnetworkId,
-    tx_version: u16,
+    allowed_subnetworks: AllowedSubnetworks,
     compute_budget_per_input: u16,
     /// `keys.minimum_signatures` narrowed to `u8` once at construction so the
     /// v0 input builder never silently truncates a wider value.
@@ -131,6 +429,13 @@ pub struct TransactionGenerator {
     /// transient_block_limit). Used to normalize transient mass to the compute scale exactly as the
     /// node's standardness relay-fee floor does — the node reads `mempool_mass_cofactors.raw_post()`.
     mass_cofactors: MassCofactors,
+
+    /// Consensus-side calculator for the contextual (KIP-9 storage) mass, which needs the input
+    /// entries and so is only available on fully built transactions.
+    consensus_mass_calculator: ConsensusMassCalculator,
+
+    /// Used when a request leaves `coin_selection_strategy` unspecified.
+    default_coin_selection_strategy: CoinSelectionStrategy,
 }
 
 impl TransactionGenerator {
@@ -140,8 +445,9 @@ impl TransactionGenerator {
         address_manager: Arc<Mutex<AddressManager>>,
         mass_calculator: Arc<MassCalculator>,
         address_prefix: AddressPrefix,
-        subnetwork_id: SubnetworkId,
+        allowed_subnetworks: AllowedSubnetworks,
         consensus_params: &Params,
+        default_coin_selection_strategy: CoinSelectionStrategy,
     ) -> WalletResult<Self> {
         if keys.minimum_signatures == 0 {
             return Err(WalletError::from(UserInputErr::InvalidArgument {
@@ -162,6 +468,7 @@ impl TransactionGenerator {
         // public batch helper and request mass for a single input.
         let signature_mass_per_input =
             mass_calculator.calc_signature_compute_mass_for_inputs(1, keys.minimum_signatures);
+        let subnetwork_id = allowed_subnetworks.default_subnetwork_id();
         let tx_version = select_tx_version(&subnetwork_id);
         let compute_budget_per_input = compute_budget_for_signature(
             consensus_params.mass_per_sig_op,
@@ -172,6 +479,7 @@ impl TransactionGenerator {
         // wait for the first tx.
         debug!(
             subnetwork_id = %subnetwork_id,
+            allowed_subnetwork_ids = %allowed_subnetworks,
             tx_version,
             compute_budget_per_input,
             mass_per_sig_op = consensus_params.mass_per_sig_op,
@@ -184,13 +492,16 @@ impl TransactionGenerator {
             address_manager,
             mass_calculator,
             address_prefix,
-            subnetwork_id,
-            tx_version,
+            allowed_subnetworks,
             compute_budget_per_input,
             minimum_signatures_u8,
             mass_per_sig_op: consensus_params.mass_per_sig_op,
             signature_mass_per_input,
             mass_cofactors: consensus_params.mempool_block_mass_cofactors().raw_post(),
+            consensus_mass_calculator: ConsensusMassCalculator::new_with_consensus_params(
+                consensus_params,
+            ),
+            default_coin_selection_strategy,
         })
     }
 
@@ -198,7 +509,62 @@ impl TransactionGenerator {
         &mut self,
         utxo_manager: &MutexGuard<'_, UtxoManager>,
         transaction_description: TransactionDescription,
-    ) -> WalletResult<Vec<WalletSignableTransaction>> {
+    ) -> WalletResult<(Vec<WalletSignableTransaction>, FeeLimits)> {
+        self.build_unsigned_transactions(utxo_manager, transaction_description, false)
+            .await
+    }
+
+    /// The most `transaction_description` can send: runs the send-all path, including any split
+    /// into merge transactions, without deriving a change address. `amount`, `is_send_all` and
+    /// `subtract_fee_from_amount` are ignored.
+    pub async fn max_sendable(
+        &mut self,
+        utxo_manager: &MutexGuard<'_, UtxoManager>,
+        transaction_description: TransactionDescription,
+    ) -> WalletResult<MaxSendable> {
+        let transaction_description = TransactionDescription {
+            amount: 0,
+            is_send_all: true,
+            subtract_fee_from_amount: false,
+            ..transaction_description
+        };
+        let (transactions, fee_limits) = self
+            .build_unsigned_transactions(utxo_manager, transaction_description, true)
+            .await?;
+
+        let fee = transactions
+            .iter()
+            .map(|transaction| transaction_fee(transaction.transaction.inner()))
+            .sum();
+        // A send-all has no change output: the final transaction pays the recipient only.
+        let amount = transactions
+            .last()
+            .map(|transaction| {
+                transaction
+                    .transaction
+                    .inner()
+                    .tx
+                    .outputs
+                    .iter()
+                    .map(|output| output.value)
+                    .sum()
+            })
+            .unwrap_or_default();
+        Ok(MaxSendable {
+            amount,
+            fee,
+            transaction_count: transactions.len(),
+            fee_limits,
+        })
+    }
+
+    /// With `dry_run` the change address is peeked rather than derived, so nothing is persisted.
+    async fn build_unsigned_transactions(
+        &mut self,
+        utxo_manager: &MutexGuard<'_, UtxoManager>,
+        transaction_description: TransactionDescription,
+        dry_run: bool,
+    ) -> WalletResult<(Vec<WalletSignableTransaction>, FeeLimits)> {
         let validate_address = |address_string: String, _name: &str| -> WalletResult<Address> {
             match Address::try_from(address_string.clone()) {
                 Ok(address) => Ok(address),
@@ -210,13 +576,29 @@ impl TransactionGenerator {
             }
         };
 
-        let to_address = validate_address(transaction_description.to_address, "to")?;
+        let subnetwork_id = self
+            .allowed_subnetworks
+            .resolve(transaction_description.subnetwork_id.as_deref())?;
+
+        let destination = self.payment_destination(
+            transaction_description.to_address,
+            transaction_description.to_script_public_key,
+            &transaction_description.to_redeem_script,
+            validate_address,
+        )?;
         let address_set: HashMap<String, WalletAddress>;
         {
             let address_manager = self.address_manager.lock().await;
             address_set = address_manager.address_set().await;
         }
 
+        if transaction_description.is_send_all && transaction_description.subtract_fee_from_amount {
+            return Err(WalletError::from(UserInputErr::InvalidArgument {
+                reason: "Cannot specify both is_send_all and subtract_fee_from_amount".to_string(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
         if !transaction_description.from_addresses.is_empty()
             && !transaction_description.utxos.is_empty()
         {
@@ -262,16 +644,26 @@ impl TransactionGenerator {
             preselected_utxos
         };
 
-        let (fee_rate, max_fee) = self
+        let fee_limits = self
             .calculate_fee_limits(transaction_description.fee_policy)
             .await?;
+        let FeeLimits {
+            fee_rate, max_fee, ..
+        } = fee_limits;
+        let coin_selection_strategy =
+            CoinSelectionStrategy::from_proto(transaction_description.coin_selection_strategy)?
+                .unwrap_or(self.default_coin_selection_strategy);
 
         let change_address: Address;
         let change_wallet_address: WalletAddress;
         {
             let address_manager = self.address_manager.lock().await;
             (change_address, change_wallet_address) = // TODO: check if I really need both.
-                address_manager.change_address(transaction_description.use_existing_change_address, &from_addresses).await?;
+                if dry_run {
+                    address_manager.peek_change_address(&from_addresses).await?
+                } else {
+                    address_manager.change_address(transaction_description.use_existing_change_address, &from_addresses).await?
+                };
         }
 
         let selected_utxos: Vec<WalletUtxo>;
@@ -283,10 +675,14 @@ impl TransactionGenerator {
                 &preselected_utxos,
                 transaction_description.amount,
                 transaction_description.is_send_all,
+                transaction_description.subtract_fee_from_amount,
                 fee_rate,
                 max_fee,
                 &from_addresses,
+                &destination.script_public_key,
                 &transaction_description.payload,
+                coin_selection_strategy,
+                subnetwork_id,
             )
             .await?;
 
@@ -312,39 +708,639 @@ impl TransactionGenerator {
                 .join(", ")
         );
 
-        let mut payments = vec![WalletPayment::new(
-            to_address.clone(),
-            amount_sent_to_recipient,
-        )];
-        if change_sompi > 0 {
-            payments.push(WalletPayment::new(change_address.clone(), change_sompi));
+        let mut payments = vec![WalletPayment::to_destination(
+            destination.clone(),
+            amount_sent_to_recipient,
+        )];
+        if change_sompi > 0 {
+            payments.push(WalletPayment::new(change_address.clone(), change_sompi));
+        }
+        let unsigned_transaction = self
+            .generate_unsigned_transaction(
+                payments,
+                &selected_utxos,
+                transaction_description.payload.into(),
+                subnetwork_id,
+            )
+            .await?;
+
+        let unsigned_transactions = self
+            .maybe_auto_compound_transaction(
+                utxo_manager,
+                unsigned_transaction,
+                &selected_utxos,
+                from_addresses,
+                &destination,
+                transaction_description.amount,
+                transaction_description.is_send_all,
+                transaction_description.subtract_fee_from_amount,
+                &transaction_description.utxos,
+                &change_address,
+                &change_wallet_address,
+                fee_rate,
+                max_fee,
+            )
+            .await?;
+
+        Ok((unsigned_transactions, fee_limits))
+    }
+
+    /// Resolve the recipient of a `TransactionDescription`. Exactly one of `to_address`,
+    /// `to_script_public_key` and `to_redeem_script` may be set.
+    fn payment_destination(
+        &self,
+        to_address: String,
+        to_script_public_key: Option<ProtoScriptPublicKey>,
+        to_redeem_script: &[u8],
+        validate_address: impl Fn(String, &str) -> WalletResult<Address>,
+    ) -> WalletResult<PaymentDestination> {
+        let destination_count = [
+            !to_address.is_empty(),
+            to_script_public_key.is_some(),
+            !to_redeem_script.is_empty(),
+        ]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count();
+        if destination_count > 1 {
+            return Err(WalletError::from(UserInputErr::InvalidArgument {
+                reason:
+                    "Only one of to_address, to_script_public_key and to_redeem_script may be set"
+                        .to_string(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        if let Some(to_script_public_key) = to_script_public_key {
+            let script_public_key = script_public_key_from_proto(to_script_public_key)?;
+            if script_public_key.script().is_empty() {
+                return Err(WalletError::from(UserInputErr::InvalidArgument {
+                    reason: "to_script_public_key must not be empty".to_string(),
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            return Ok(PaymentDestination::from_script_public_key(
+                script_public_key,
+                self.address_prefix,
+            ));
+        }
+        if !to_redeem_script.is_empty() {
+            return Ok(PaymentDestination::from_redeem_script(
+                to_redeem_script,
+                self.address_prefix,
+            ));
+        }
+        let to_address = validate_address(to_address, "to")?;
+        Ok(PaymentDestination::from_address(to_address))
+    }
+
+    /// Rebuild a tracked wallet transaction from the same inputs with a higher fee, taken from its
+    /// change output, so it can be submitted as a replace-by-fee replacement.
+    pub async fn create_fee_bump_transaction(
+        &self,
+        original_wallet_transaction: &WalletSignableTransaction,
+        fee_policy: Option<FeePolicy>,
+    ) -> WalletResult<WalletSignableTransaction> {
+        let original_transaction = original_wallet_transaction.transaction.inner();
+        // The replacement stays on the original's lane.
+        let subnetwork_id = original_transaction.tx.subnetwork_id;
+        self.allowed_subnetworks.ensure_allowed(&subnetwork_id)?;
+
+        let change_output_index = self
+            .change_output_index(original_wallet_transaction)
+            .await
+            .ok_or_else(|| {
+                WalletError::from(TransactionError::BuildFailed {
+                    reason: "transaction has no change output to take a higher fee from"
+                        .to_string(),
+                    location: ErrorLocation::capture(),
+                })
+            })?;
+
+        let FeeLimits {
+            fee_rate, max_fee, ..
+        } = self.calculate_fee_limits(fee_policy).await?;
+        let original_fee = transaction_fee(original_transaction);
+
+        let mut selected_utxos = vec![];
+        for (i, input) in original_transaction.tx.inputs.iter().enumerate() {
+            let (Some(entry), Some(address)) = (
+                original_transaction.entries[i].clone(),
+                original_wallet_transaction.address_by_input_index.get(i),
+            ) else {
+                return Err(WalletError::from(TransactionError::BuildFailed {
+                    reason: format!("input {i} is missing its UTXO entry or wallet address"),
+                    location: ErrorLocation::capture(),
+                }));
+            };
+            selected_utxos.push(WalletUtxo::new(
+                input.previous_outpoint.into(),
+                entry.into(),
+                address.clone(),
+            ));
+        }
+        let payments_with_change_reduced_by = |fee_increase: u64| -> Vec<WalletPayment> {
+            original_transaction
+                .tx
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(i, output)| {
+                    let amount = if i == change_output_index {
+                        output.value - fee_increase
+                    } else {
+                        output.value
+                    };
+                    let destination = PaymentDestination {
+                        script_public_key: output.script_public_key.clone(),
+                        address: original_wallet_transaction.address_by_output_index[i].clone(),
+                    };
+                    WalletPayment::to_destination(destination, amount)
+                })
+                .collect()
+        };
+
+        let fee_for_mass = |mass: u64| min(((mass as f64) * fee_rate).ceil() as u64, max_fee);
+        let change_value = original_transaction.tx.outputs[change_output_index].value;
+
+        // The original is signed, so size the fee on an unsigned rebuild - the mass helpers
+        // account for signatures themselves.
+        let mut replacement = self
+            .generate_unsigned_transaction(
+                payments_with_change_reduced_by(0),
+                &selected_utxos,
+                original_transaction.tx.payload.clone(),
+                subnetwork_id,
+            )
+            .await?;
+        let mut new_fee = fee_for_mass(self.full_fee_mass(replacement.transaction.inner()));
+        if new_fee <= original_fee {
+            return Err(WalletError::from(TransactionError::FeeTooLow {
+                provided_sompi: new_fee,
+                required_sompi: original_fee + 1,
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        // Taking the fee from the change lowers it, which can raise the storage mass, so re-size
+        // on each rebuild until the fee covers the replacement's own mass.
+        for _ in 0..FEE_BUMP_RESIZE_ROUNDS {
+            let fee_increase = new_fee - original_fee;
+            if change_value <= fee_increase {
+                return Err(WalletError::from(TransactionError::InsufficientFunds {
+                    required_sompi: fee_increase,
+                    available_sompi: change_value,
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            replacement = self
+                .generate_unsigned_transaction(
+                    payments_with_change_reduced_by(fee_increase),
+                    &selected_utxos,
+                    original_transaction.tx.payload.clone(),
+                    subnetwork_id,
+                )
+                .await?;
+            let required_fee = fee_for_mass(self.full_fee_mass(replacement.transaction.inner()));
+            if required_fee <= new_fee {
+                debug!(
+                    "Bumping fee of {} from {} to {} sompi",
+                    original_transaction.id(),
+                    original_fee,
+                    new_fee
+                );
+                self.check_transaction_fee_rate(&replacement, max_fee)?;
+                return Ok(replacement);
+            }
+            new_fee = required_fee;
+        }
+        Err(WalletError::from(TransactionError::BuildFailed {
+            reason: format!(
+                "replacement fee did not settle after {FEE_BUMP_RESIZE_ROUNDS} rounds; the change output is too small for its storage mass"
+            ),
+            location: ErrorLocation::capture(),
+        }))
+    }
+
+    /// Build a child-pays-for-parent transaction spending `parent_outputs` (unconfirmed outputs of
+    /// an incoming transaction) back to a fresh change address, with a fee high enough to lift the
+    /// parent+child package to `target_fee_rate`.
+    pub async fn create_child_pays_for_parent_transaction(
+        &self,
+        parent_outputs: &Vec<WalletUtxo>,
+        parent_fee: u64,
+        parent_mass: u64,
+        target_fee_rate: f64,
+    ) -> WalletResult<WalletSignableTransaction> {
+        ensure_minimum_fee_rate(target_fee_rate)?;
+        let subnetwork_id = self.allowed_subnetworks.default_subnetwork_id();
+
+        let (change_address, _) = {
+            let address_manager = self.address_manager.lock().await;
+            address_manager.change_address(false, &[]).await?
+        };
+        let total_value: u64 = parent_outputs
+            .iter()
+            .map(|utxo| utxo.utxo_entry.amount)
+            .sum();
+
+        let mock_transaction = self
+            .generate_unsigned_transaction(
+                vec![WalletPayment::new(change_address.clone(), total_value)],
+                parent_outputs,
+                vec![],
+                subnetwork_id,
+            )
+            .await?;
+        let child_mass = self.non_contextual_fee_mass(
+            &mock_transaction.transaction.inner().tx,
+            self.keys.minimum_signatures,
+        );
+
+        let child_fee =
+            child_pays_for_parent_fee(parent_fee, parent_mass, child_mass, target_fee_rate);
+        if child_fee >= total_value {
+            return Err(WalletError::from(TransactionError::InsufficientFunds {
+                required_sompi: child_fee,
+                available_sompi: total_value,
+                location: ErrorLocation::capture(),
+            }));
+        }
+        debug!(
+            "Child pays for parent: parent_fee={}, parent_mass={}, child_mass={}, child_fee={}",
+            parent_fee, parent_mass, child_mass, child_fee
+        );
+
+        let child_transaction = self
+            .generate_unsigned_transaction(
+                vec![WalletPayment::new(change_address, total_value - child_fee)],
+                parent_outputs,
+                vec![],
+                subnetwork_id,
+            )
+            .await?;
+        self.check_transaction_fee_rate(&child_transaction, u64::MAX)?;
+        Ok(child_transaction)
+    }
+
+    /// Build the sweep-to-self transactions merging the smallest spendable UTXOs matching the
+    /// filters into a single change output, so that `target_utxo_count` matching UTXOs remain.
+    /// Inputs beyond the standard mass limit are split and merged like an auto-compounded
+    /// payment. Returns no transactions if there is nothing to consolidate.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_consolidation_transactions(
+        &self,
+        utxo_manager: &MutexGuard<'_, UtxoManager>,
+        from_addresses: Vec<String>,
+        min_input_value: u64,
+        max_input_value: Option<u64>,
+        target_utxo_count: u32,
+        fee_policy: Option<FeePolicy>,
+    ) -> WalletResult<Vec<WalletSignableTransaction>> {
+        if target_utxo_count == 0 {
+            return Err(WalletError::from(UserInputErr::InvalidArgument {
+                reason: "target_utxo_count must be at least 1".to_string(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+        if max_input_value.is_some_and(|max_input_value| max_input_value < min_input_value) {
+            return Err(WalletError::from(UserInputErr::InvalidArgument {
+                reason: "max_input_value must not be lower than min_input_value".to_string(),
+                location: ErrorLocation::capture(),
+            }));
+        }
+
+        let address_set = {
+            let address_manager = self.address_manager.lock().await;
+            address_manager.address_set().await
+        };
+        let mut filter_addresses = vec![];
+        for address_string in from_addresses {
+            let wallet_address = address_set.get(&address_string).ok_or_else(|| {
+                WalletError::from(UserInputErr::InvalidAddress {
+                    input: address_string.clone(),
+                    reason: "From address is not in address set".to_string(),
+                    location: ErrorLocation::capture(),
+                })
+            })?;
+            filter_addresses.push(wallet_address);
+        }
+
+        let dag_info = self.kaspa_client.get_block_dag_info().await.map_err(|e| {
+            common::errors::RpcError::Transport {
+                reason: e.to_string(),
+                location: ErrorLocation::capture(),
+            }
+        })?;
+        let candidates: Vec<WalletUtxo> = utxo_manager
+            .utxos_sorted_by_amount()
+            .into_iter()
+            .filter(|utxo| !utxo_manager.is_utxo_unspendable(utxo, dag_info.virtual_daa_score))
+            .filter(|utxo| !utxo_manager.is_utxo_held_back(&utxo.outpoint))
+            .filter(|utxo| filter_addresses.is_empty() || filter_addresses.contains(&&utxo.address))
+            .filter(|utxo| {
+                utxo.utxo_entry.amount >= min_input_value
+                    && max_input_value
+                        .is_none_or(|max_input_value| utxo.utxo_entry.amount <= max_input_value)
+            })
+            .collect();
+
+        let Some(selected_utxos) = consolidation_inputs(&candidates, target_utxo_count as usize)
+        else {
+            debug!(
+                "Nothing to consolidate: {} matching UTXOs, target is {}",
+                candidates.len(),
+                target_utxo_count
+            );
+            return Ok(vec![]);
+        };
+        let selected_utxos = selected_utxos.to_vec();
+
+        let FeeLimits {
+            fee_rate, max_fee, ..
+        } = self.calculate_fee_limits(fee_policy).await?;
+        self.merge_into_change(
+            utxo_manager,
+            selected_utxos,
+            fee_rate,
+            max_fee,
+            false,
+            self.allowed_subnetworks.default_subnetwork_id(),
+        )
+        .await
+    }
+
+    /// Sweep `dust_utxos` into a change address if the fee to do so is lower than their value.
+    /// With `dry_run` the change address is peeked rather than derived, so nothing is persisted.
+    pub async fn create_dust_sweep_transactions(
+        &self,
+        utxo_manager: &MutexGuard<'_, UtxoManager>,
+        dust_utxos: Vec<WalletUtxo>,
+        fee_policy: Option<FeePolicy>,
+        dry_run: bool,
+    ) -> WalletResult<DustSweep> {
+        let subnetwork_id = self.allowed_subnetworks.default_subnetwork_id();
+        let fee_limits = self.calculate_fee_limits(fee_policy).await?;
+        let dust_value: u64 = dust_utxos.iter().map(|utxo| utxo.utxo_entry.amount).sum();
+        let estimated_fee = if dust_utxos.is_empty() {
+            0
+        } else {
+            self.estimate_fee(
+                &dust_utxos,
+                fee_limits.fee_rate,
+                fee_limits.max_fee,
+                dust_value,
+                None,
+                &[],
+                subnetwork_id,
+            )
+            .await?
+        };
+        if estimated_fee >= dust_value {
+            debug!(
+                dust_utxo_count = dust_utxos.len(),
+                dust_value, estimated_fee, "Sweeping dust is not economic at the current fee rate"
+            );
+            return Ok(DustSweep {
+                transactions: vec![],
+                dust_value,
+                fee: estimated_fee,
+                is_economic: false,
+                fee_limits,
+            });
+        }
+
+        let transactions = self
+            .merge_into_change(
+                utxo_manager,
+                dust_utxos,
+                fee_limits.fee_rate,
+                fee_limits.max_fee,
+                dry_run,
+                subnetwork_id,
+            )
+            .await?;
+        let fee = transactions
+            .iter()
+            .map(|transaction| transaction_fee(transaction.transaction.inner()))
+            .sum();
+        Ok(DustSweep {
+            transactions,
+            dust_value,
+            fee,
+            is_economic: true,
+            fee_limits,
+        })
+    }
+
+    /// Spend `selected_utxos` to a single change output, split and merged like an auto-compounded
+    /// payment when beyond the standard mass limit. With `dry_run` the change address is peeked
+    /// rather than derived.
+    async fn merge_into_change(
+        &self,
+        utxo_manager: &MutexGuard<'_, UtxoManager>,
+        selected_utxos: Vec<WalletUtxo>,
+        fee_rate: f64,
+        max_fee: u64,
+        dry_run: bool,
+        subnetwork_id: SubnetworkId,
+    ) -> WalletResult<Vec<WalletSignableTransaction>> {
+        let (change_address, change_wallet_address) = {
+            let address_manager = self.address_manager.lock().await;
+            if dry_run {
+                address_manager.peek_change_address(&[]).await?
+            } else {
+                address_manager.change_address(false, &[]).await?
+            }
+        };
+        let destination = PaymentDestination::from_address(change_address.clone());
+
+        let total_value: u64 = selected_utxos
+            .iter()
+            .map(|utxo| utxo.utxo_entry.amount)
+            .sum();
+        let fee = self
+            .estimate_fee(
+                &selected_utxos,
+                fee_rate,
+                max_fee,
+                total_value,
+                Some(&destination.script_public_key),
+                &[],
+                subnetwork_id,
+            )
+            .await?;
+        let amount = merged_amount(total_value, fee)?;
+        info!(
+            input_count = selected_utxos.len(),
+            total_value, fee, "consolidating UTXOs"
+        );
+
+        let sweep_transaction = self
+            .generate_unsigned_transaction(
+                vec![WalletPayment::new(change_address.clone(), amount)],
+                &selected_utxos,
+                vec![],
+                subnetwork_id,
+            )
+            .await?;
+
+        let selected_outpoints: Vec<Outpoint> = selected_utxos
+            .iter()
+            .map(|utxo| utxo.outpoint.clone().into())
+            .collect();
+        self.maybe_auto_compound_transaction(
+            utxo_manager,
+            sweep_transaction,
+            &selected_utxos,
+            vec![],
+            &destination,
+            amount,
+            true,
+            false,
+            &selected_outpoints,
+            &change_address,
+            &change_wallet_address,
+            fee_rate,
+            max_fee,
+        )
+        .await
+    }
+
+    /// Sweep `utxos` of a single-signature key held outside the wallet to `destination`, in as
+    /// many transactions as the standard mass limit requires. The transactions carry no
+    /// derivation paths: the caller signs them with the external key.
+    pub async fn create_external_sweep_transactions(
+        &self,
+        utxos: &[ExternalUtxo],
+        destination: Address,
+        fee_policy: Option<FeePolicy>,
+    ) -> WalletResult<(Vec<WalletSignableTransaction>, FeeLimits)> {
+        let subnetwork_id = self.allowed_subnetworks.default_subnetwork_id();
+        let fee_limits = self.calculate_fee_limits(fee_policy).await?;
+        let destination = PaymentDestination::from_address(destination);
+
+        let mut transactions = vec![];
+        let mut remaining = utxos;
+        while !remaining.is_empty() {
+            let mut input_count = remaining.len();
+            let mut mass =
+                self.external_sweep_mass(&remaining[..input_count], &destination, subnetwork_id)?;
+            while mass > MAXIMUM_STANDARD_TRANSACTION_MASS && input_count > 1 {
+                // Mass grows about linearly with the input count, so shrink proportionally.
+                let proportional =
+                    (input_count as u64 * MAXIMUM_STANDARD_TRANSACTION_MASS / mass) as usize;
+                input_count = proportional.clamp(1, input_count - 1);
+                mass = self.external_sweep_mass(
+                    &remaining[..input_count],
+                    &destination,
+                    subnetwork_id,
+                )?;
+            }
+            let (batch, rest) = remaining.split_at(input_count);
+
+            let total_value: u64 = batch.iter().map(|utxo| utxo.utxo_entry.amount).sum();
+            let fee = min(
+                ((mass as f64) * fee_limits.fee_rate).ceil() as u64,
+                fee_limits.max_fee,
+            );
+            if fee >= total_value {
+                return Err(WalletError::from(TransactionError::InsufficientFunds {
+                    required_sompi: fee,
+                    available_sompi: total_value,
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            info!(input_count, total_value, fee, "sweeping external UTXOs");
+            transactions.push(self.external_sweep_transaction(
+                batch,
+                &destination,
+                total_value - fee,
+                subnetwork_id,
+            )?);
+            remaining = rest;
+        }
+        Ok((transactions, fee_limits))
+    }
+
+    /// Fee mass of sweeping `utxos` to `destination`, signed by a single key.
+    fn external_sweep_mass(
+        &self,
+        utxos: &[ExternalUtxo],
+        destination: &PaymentDestination,
+        subnetwork_id: SubnetworkId,
+    ) -> WalletResult<u64> {
+        let total_value = utxos.iter().map(|utxo| utxo.utxo_entry.amount).sum();
+        let mock_transaction =
+            self.external_sweep_transaction(utxos, destination, total_value, subnetwork_id)?;
+        Ok(self.non_contextual_fee_mass(&mock_transaction.transaction.inner().tx, 1))
+    }
+
+    /// A transaction spending `utxos` of an external single-signature key to one output. Unlike
+    /// `generate_unsigned_transaction`, inputs commit to one signature whatever the wallet's own
+    /// `minimum_signatures`, and no derivation paths or input addresses are recorded.
+    fn external_sweep_transaction(
+        &self,
+        utxos: &[ExternalUtxo],
+        destination: &PaymentDestination,
+        amount: u64,
+        subnetwork_id: SubnetworkId,
+    ) -> WalletResult<WalletSignableTransaction> {
+        let tx_version = select_tx_version(&subnetwork_id);
+        let compute_budget_per_input = compute_budget_for_signature(self.mass_per_sig_op, 1)?;
+        let mut inputs = vec![];
+        let mut utxo_entries = vec![];
+        for utxo in utxos {
+            let previous_outpoint =
+                TransactionOutpoint::new(utxo.outpoint.transaction_id, utxo.outpoint.index);
+            let input = if ComputeCommit::version_expects_compute_budget_field(tx_version) {
+                TransactionInput::new_with_compute_budget(
+                    previous_outpoint,
+                    vec![],
+                    0,
+                    compute_budget_per_input,
+                )
+            } else {
+                TransactionInput::new(previous_outpoint, vec![], 0, 1)
+            };
+            inputs.push(input);
+            utxo_entries.push(UtxoEntry::from(utxo.utxo_entry.clone()));
         }
-        let unsigned_transaction = self
-            .generate_unsigned_transaction(
-                payments,
-                &selected_utxos,
-                transaction_description.payload.into(),
-            )
-            .await?;
+        let outputs = vec![TransactionOutput::new(
+            amount,
+            destination.script_public_key.clone(),
+        )];
 
-        let unsigned_transactions = self
-            .maybe_auto_compound_transaction(
-                utxo_manager,
-                unsigned_transaction,
-                &selected_utxos,
-                from_addresses,
-                &to_address,
-                transaction_description.amount,
-                transaction_description.is_send_all,
-                &transaction_description.utxos,
-                &change_address,
-                &change_wallet_address,
-                fee_rate,
-                max_fee,
-            )
-            .await?;
+        let transaction =
+            Transaction::new(tx_version, inputs, outputs, 0, subnetwork_id, 0, vec![]);
+        let mut signable_transaction = SignableTransaction::with_entries(transaction, utxo_entries);
+        signable_transaction.calculated_non_contextual_masses =
+            Some(self.non_contextual_masses(&signable_transaction.tx, 1));
+        Ok(WalletSignableTransaction::new_from_unsigned(
+            signable_transaction,
+            vec![],
+            vec![],
+            vec![destination.address.clone()],
+        ))
+    }
 
-        Ok(unsigned_transactions)
+    /// Index of the output paying to one of the wallet's internal (change) addresses, if any.
+    async fn change_output_index(&self, transaction: &WalletSignableTransaction) -> Option<usize> {
+        let address_manager = self.address_manager.lock().await;
+        for (i, address) in transaction.address_by_output_index.iter().enumerate().rev() {
+            let Some(address) = address else {
+                continue;
+            };
+            let wallet_address = address_manager
+                .wallet_address_from_string(&address.to_string())
+                .await;
+            if wallet_address
+                .is_some_and(|wallet_address| wallet_address.keychain == Keychain::Internal)
+            {
+                return Some(i);
+            }
+        }
+        None
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -354,9 +1350,10 @@ impl TransactionGenerator {
         original_wallet_transaction: WalletSignableTransaction,
         original_selected_utxos: &Vec<WalletUtxo>,
         from_addresses: Vec<&WalletAddress>,
-        to_address: &Address,
+        destination: &PaymentDestination,
         amount: u64,
         is_send_all: bool,
+        subtract_fee_from_amount: bool,
         preselected_utxo_outpoints: &Vec<Outpoint>,
         change_address: &Address,
         change_wallet_address: &WalletAddress,
@@ -366,6 +1363,8 @@ impl TransactionGenerator {
         self.check_transaction_fee_rate(&original_wallet_transaction, max_fee)?;
 
         let original_consensus_transaction = original_wallet_transaction.transaction.inner();
+        // Split and merge transactions stay on the lane of the transaction they compound.
+        let subnetwork_id = original_consensus_transaction.tx.subnetwork_id;
 
         let transaction_mass = self.compute_mass_for_unsigned_consensus_transaction(
             &original_consensus_transaction.tx,
@@ -385,6 +1384,7 @@ impl TransactionGenerator {
                 change_address,
                 fee_rate,
                 max_fee,
+                subnetwork_id,
             )
             .await?;
 
@@ -402,6 +1402,7 @@ impl TransactionGenerator {
                     end_index,
                     fee_rate,
                     max_fee,
+                    subnetwork_id,
                 )
                 .await?;
 
@@ -421,26 +1422,38 @@ impl TransactionGenerator {
                 &original_consensus_transaction.tx,
                 original_selected_utxos,
                 &from_addresses,
-                to_address,
+                destination,
                 amount,
                 is_send_all,
+                subtract_fee_from_amount,
                 preselected_utxo_outpoints,
                 change_address,
                 change_wallet_address,
                 fee_rate,
                 max_fee,
+                subnetwork_id,
             )
             .await?;
 
+        // When the recipient pays the fee, the merge transaction already sends the reduced amount,
+        // so a further compounding round deducts its own fees from that rather than from the
+        // originally requested amount.
+        let merge_amount = if subtract_fee_from_amount {
+            merge_transaction.transaction.inner().tx.outputs[0].value
+        } else {
+            amount
+        };
+
         // Recursion will be 2-3 iterations deep even in the rarest cases, so considered safe...
         let split_merge_transaction = Box::pin(self.maybe_auto_compound_transaction(
             utxo_manager,
             merge_transaction,
             original_selected_utxos,
             from_addresses,
-            to_address,
-            amount,
+            destination,
+            merge_amount,
             is_send_all,
+            subtract_fee_from_amount,
             preselected_utxo_outpoints,
             change_address,
             change_wallet_address,
@@ -464,14 +1477,16 @@ impl TransactionGenerator {
         original_consensus_transaction: &Transaction,
         original_selected_utxos: &[WalletUtxo],
         from_addresses: &[&WalletAddress],
-        to_address: &Address,
+        destination: &PaymentDestination,
         amount: u64,
         is_send_all: bool,
+        subtract_fee_from_amount: bool,
         preselected_utxo_outpoints: &[Outpoint],
         change_address: &Address,
         change_wallet_address: &WalletAddress,
         fee_rate: f64,
         max_fee: u64,
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<WalletSignableTransaction> {
         let num_outputs = original_consensus_transaction.outputs.len();
         if ![1, 2].contains(&num_outputs) {
@@ -527,7 +1542,9 @@ impl TransactionGenerator {
                 fee_rate,
                 max_fee,
                 amount,
+                Some(&destination.script_public_key),
                 &original_consensus_transaction.payload,
+                subnetwork_id,
             )
             .await?;
         debug!("merge_transaction_fee: {}", merge_transaction_fee);
@@ -535,7 +1552,26 @@ impl TransactionGenerator {
         let mut available_value = total_value - merge_transaction_fee;
         debug!("available_value: {}", available_value);
 
-        let mut sent_value = if !is_send_all {
+        let mut sent_value = if subtract_fee_from_amount {
+            // The recipient pays for the split transactions as well as for the merge transaction.
+            let split_transactions_fee: u64 = split_transactions
+                .iter()
+                .map(|split_transaction| {
+                    let (total_ins, total_outs) =
+                        total_input_and_output_value(split_transaction.transaction.inner());
+                    total_ins - total_outs
+                })
+                .sum();
+            let total_fee = split_transactions_fee + merge_transaction_fee;
+            if amount <= total_fee {
+                return Err(WalletError::from(TransactionError::InsufficientFunds {
+                    required_sompi: total_fee,
+                    available_sompi: amount,
+                    location: ErrorLocation::capture(),
+                }));
+            }
+            amount - total_fee
+        } else if !is_send_all {
             amount
         } else {
             let total_value_from_split_transactions: u64 = utxos_from_split_transactions
@@ -596,16 +1632,16 @@ impl TransactionGenerator {
         let utxos_for_merge_transactions =
             [utxos_from_split_transactions, additional_utxos].concat();
 
-        let mut payments = vec![WalletPayment {
-            address: to_address.clone(),
-            amount: sent_value,
-        }];
+        let mut payments = vec![WalletPayment::to_destination(
+            destination.clone(),
+            sent_value,
+        )];
 
         if available_value > sent_value {
-            payments.push(WalletPayment {
-                address: change_address.clone(),
-                amount: available_value - sent_value,
-            });
+            payments.push(WalletPayment::new(
+                change_address.clone(),
+                available_value - sent_value,
+            ));
         }
         debug!(
             "Creating merge transaction with {} payments",
@@ -616,6 +1652,7 @@ impl TransactionGenerator {
             payments,
             &utxos_for_merge_transactions,
             original_consensus_transaction.payload.clone(),
+            subnetwork_id,
         )
         .await
     }
@@ -651,6 +1688,7 @@ impl TransactionGenerator {
         for utxo in utxos_sorted_by_amount {
             if already_selected_utxos.contains(&utxo)
                 || utxo_manager.is_utxo_unspendable(&utxo, dag_info.virtual_daa_score)
+                || utxo_manager.is_utxo_held_back(&utxo.outpoint)
             {
                 continue;
             }
@@ -677,6 +1715,7 @@ impl TransactionGenerator {
     }
 
     // Returns: (split_count, input_per_split_count)
+    #[allow(clippy::too_many_arguments)]
     async fn split_and_input_per_split_counts(
         &self,
         original_wallet_transaction: &WalletSignableTransaction,
@@ -685,6 +1724,7 @@ impl TransactionGenerator {
         change_address: &Address,
         fee_rate: f64,
         max_fee: u64,
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<(usize, usize)> {
         // Create a dummy transaction which is a clone of the original transaction, but without inputs,
         // to calculate how much mass do all the inputs have
@@ -715,6 +1755,7 @@ impl TransactionGenerator {
                 0,
                 fee_rate,
                 max_fee,
+                subnetwork_id,
             )
             .await?;
 
@@ -727,13 +1768,11 @@ impl TransactionGenerator {
         let mass_for_inputs_in_split_transaction = MAXIMUM_STANDARD_TRANSACTION_MASS
             - mass_for_everything_except_inputs_in_split_transaction;
 
-        let inputs_per_split_count = mass_for_inputs_in_split_transaction / mass_per_input;
-        let mut split_count = input_count / inputs_per_split_count;
-        if input_count % inputs_per_split_count > 0 {
-            split_count += 1;
-        }
-
-        Ok((split_count as usize, inputs_per_split_count as usize))
+        split_counts(
+            input_count,
+            mass_per_input,
+            mass_for_inputs_in_split_transaction,
+        )
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -746,6 +1785,7 @@ impl TransactionGenerator {
         end_index: usize,
         fee_rate: f64,
         max_fee: u64,
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<WalletSignableTransaction> {
         let mut selected_utxos = vec![];
         let mut total_sompi = 0;
@@ -768,16 +1808,21 @@ impl TransactionGenerator {
         if !selected_utxos.is_empty() {
             // selected utxos is empty when creating a dummy transaction for mass calculation
             let fee = self
-                .estimate_fee(&selected_utxos, fee_rate, max_fee, total_sompi, &[])
+                .estimate_fee(
+                    &selected_utxos,
+                    fee_rate,
+                    max_fee,
+                    total_sompi,
+                    None,
+                    &[],
+                    subnetwork_id,
+                )
                 .await?;
             total_sompi -= fee;
         }
 
-        let payment = WalletPayment {
-            address: change_address.clone(),
-            amount: total_sompi,
-        };
-        self.generate_unsigned_transaction(vec![payment], &selected_utxos, vec![])
+        let payment = WalletPayment::new(change_address.clone(), total_sompi);
+        self.generate_unsigned_transaction(vec![payment], &selected_utxos, vec![], subnetwork_id)
             .await
     }
 
@@ -787,21 +1832,7 @@ impl TransactionGenerator {
         max_fee: u64,
     ) -> WalletResult<()> {
         let signable_transaction = transaction.transaction.inner();
-        let total_ins: u64 = signable_transaction
-            .entries
-            .iter()
-            .map(|entry| match entry {
-                None => 0,
-                Some(entry) => entry.amount,
-            })
-            .sum();
-
-        let total_outs: u64 = signable_transaction
-            .tx
-            .outputs
-            .iter()
-            .map(|output| output.value)
-            .sum();
+        let (total_ins, total_outs) = total_input_and_output_value(signable_transaction);
 
         if total_ins < total_outs {
             return Err(WalletError::from(TransactionError::InsufficientFunds {
@@ -833,7 +1864,9 @@ impl TransactionGenerator {
         payments: Vec<WalletPayment>,
         selected_utxos: &Vec<WalletUtxo>,
         payload: Vec<u8>,
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<WalletSignableTransaction> {
+        let tx_version = select_tx_version(&subnetwork_id);
         let mut sorted_extended_public_keys = self.keys.public_keys.clone();
         sorted_extended_public_keys.sort();
 
@@ -866,8 +1899,7 @@ impl TransactionGenerator {
                 // we only commit M — pre-existing wallet behaviour the v1 path
                 // intentionally mirrors so the migration adds zero new
                 // regression surface. Fix is out of scope here.
-                let input = if ComputeCommit::version_expects_compute_budget_field(self.tx_version)
-                {
+                let input = if ComputeCommit::version_expects_compute_budget_field(tx_version) {
                     TransactionInput::new_with_compute_budget(
                         previous_outpoint,
                         vec![],
@@ -892,23 +1924,16 @@ impl TransactionGenerator {
         let mut outputs = vec![];
         let mut addresses_by_output_index = vec![];
         for payment in payments {
-            let script_public_key = pay_to_address_script(&payment.address);
-            let output = TransactionOutput::new(payment.amount, script_public_key);
+            let output =
+                TransactionOutput::new(payment.amount, payment.destination.script_public_key);
             outputs.push(output);
-            addresses_by_output_index.push(payment.address.clone());
+            addresses_by_output_index.push(payment.destination.address);
         }
 
         let input_count = inputs.len();
         let output_count = outputs.len();
-        let transaction = Transaction::new(
-            self.tx_version,
-            inputs,
-            outputs,
-            0,
-            self.subnetwork_id,
-            0,
-            payload,
-        );
+        let transaction =
+            Transaction::new(tx_version, inputs, outputs, 0, subnetwork_id, 0, payload);
         // Capture id before `transaction` is moved into `SignableTransaction`.
         // One info! per built tx — bounded, much lower volume than per-input.
         let tx_id = transaction.id();
@@ -928,8 +1953,8 @@ impl TransactionGenerator {
         );
         info!(
             tx_id = %tx_id,
-            subnetwork_id = %self.subnetwork_id,
-            tx_version = self.tx_version,
+            subnetwork_id = %subnetwork_id,
+            tx_version,
             compute_budget_per_input = self.compute_budget_per_input,
             input_count,
             output_count,
@@ -940,66 +1965,17 @@ impl TransactionGenerator {
     }
 
     // Returns: (fee_rate, max_fee)
-    async fn default_fee_rate(&self) -> WalletResult<(f64, u64)> {
+    async fn calculate_fee_limits(&self, fee_policy: Option<FeePolicy>) -> WalletResult<FeeLimits> {
         let fee_estimate = self.kaspa_client.get_fee_estimate().await.map_err(|e| {
             common::errors::RpcError::Transport {
                 reason: e.to_string(),
                 location: ErrorLocation::capture(),
             }
         })?;
-        Ok((fee_estimate.priority_bucket.feerate, SOMPI_PER_KASPA)) // Default to a bound of max 1 KAS as fee
-    }
-
-    async fn calculate_fee_limits(
-        &self,
-        fee_policy: Option<FeePolicy>,
-    ) -> WalletResult<(f64, u64)> {
-        // returns (fee_rate, max_fee)
-        match fee_policy {
-            Some(fee_policy) => match fee_policy.fee_policy {
-                Some(fee_policy::FeePolicy::MaxFeeRate(requested_max_fee_rate)) => {
-                    if requested_max_fee_rate < MIN_FEE_RATE {
-                        return Err(WalletError::from(TransactionError::FeeTooLow {
-                            provided_sompi: requested_max_fee_rate as u64,
-                            required_sompi: MIN_FEE_RATE as u64,
-                            location: ErrorLocation::capture(),
-                        }));
-                    }
-
-                    let fee_estimate = self.kaspa_client.get_fee_estimate().await.map_err(|e| {
-                        common::errors::RpcError::Transport {
-                            reason: e.to_string(),
-                            location: ErrorLocation::capture(),
-                        }
-                    })?;
-                    let fee_rate =
-                        f64::min(fee_estimate.priority_bucket.feerate, requested_max_fee_rate);
-                    Ok((fee_rate, u64::MAX))
-                }
-                Some(fee_policy::FeePolicy::ExactFeeRate(requested_exact_fee_rate)) => {
-                    if requested_exact_fee_rate < MIN_FEE_RATE {
-                        return Err(WalletError::from(TransactionError::FeeTooLow {
-                            provided_sompi: requested_exact_fee_rate as u64,
-                            required_sompi: MIN_FEE_RATE as u64,
-                            location: ErrorLocation::capture(),
-                        }));
-                    }
-
-                    Ok((requested_exact_fee_rate, u64::MAX))
-                }
-                Some(fee_policy::FeePolicy::MaxFee(requested_max_fee)) => {
-                    let fee_estimate = self.kaspa_client.get_fee_estimate().await.map_err(|e| {
-                        common::errors::RpcError::Transport {
-                            reason: e.to_string(),
-                            location: ErrorLocation::capture(),
-                        }
-                    })?;
-                    Ok((fee_estimate.priority_bucket.feerate, requested_max_fee))
-                }
-                None => self.default_fee_rate().await,
-            },
-            None => self.default_fee_rate().await,
-        }
+        fee_limits_from_policy(
+            &fee_estimate,
+            fee_policy.and_then(|policy| policy.fee_policy),
+        )
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1009,22 +1985,24 @@ impl TransactionGenerator {
         preselected_utxos: &HashMap<WalletOutpoint, WalletUtxo>,
         amount: u64,
         is_send_all: bool,
+        subtract_fee_from_amount: bool,
         fee_rate: f64,
         max_fee: u64,
         from_addresses: &[&WalletAddress],
+        recipient_script_public_key: &ScriptPublicKey,
         payload: &[u8],
+        coin_selection_strategy: CoinSelectionStrategy,
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<(Vec<WalletUtxo>, u64, u64)> {
         debug!(
-            "Selecting UTXOs for payment: from_address:{}, amount: {}, is_send_all: {}, fee_rate: {}, max_fee: {}",
+            "Selecting UTXOs for payment: from_address:{}, amount: {}, is_send_all: {}, subtract_fee_from_amount: {}, fee_rate: {}, max_fee: {}",
             from_addresses.len(),
             amount,
             is_send_all,
+            subtract_fee_from_amount,
             fee_rate,
             max_fee
         );
-        let mut total_value = 0;
-        let mut selected_utxos = vec![];
-
         let dag_info = self.kaspa_client.get_block_dag_info().await.map_err(|e| {
             common::errors::RpcError::Transport {
                 reason: e.to_string(),
@@ -1032,113 +2010,105 @@ impl TransactionGenerator {
             }
         })?;
 
-        let mut fee = 0;
-        let mut fee_per_utxo = None;
-        let mut iteration = async |transaction_generator: &mut TransactionGenerator,
-                                   utxo_manager: &MutexGuard<UtxoManager>,
-                                   utxo: &WalletUtxo|
-               -> WalletResult<bool> {
-            if !from_addresses.is_empty() && !from_addresses.contains(&&utxo.address) {
-                return Ok(true);
-            }
-            if utxo_manager.is_utxo_unspendable(utxo, dag_info.virtual_daa_score) {
-                return Ok(true);
-            }
-
-            selected_utxos.push(utxo.clone());
-            total_value += utxo.utxo_entry.amount;
-            let estimated_recipient_value = if is_send_all { total_value } else { amount };
-            if fee_per_utxo.is_none() {
-                fee_per_utxo = Some(
-                    transaction_generator
-                        .estimate_fee(
-                            &selected_utxos,
-                            fee_rate,
-                            max_fee,
-                            estimated_recipient_value,
-                            payload,
-                        )
-                        .await?,
-                );
-            }
-            fee += fee_per_utxo.unwrap();
-
-            let total_spend = amount + fee;
-            // Two break cases (if not send all):
-            // 		1. total_value == totalSpend, so there's no change needed -> number of outputs = 1, so a single input is sufficient
-            // 		2. total_value > totalSpend, so there will be change and 2 outputs, therefore in order to not struggle with --
-            //		   2.1 go-nodes dust patch we try and find at least 2 inputs (even though the next one is not necessary in terms of spend value)
-            // 		   2.2 KIP9 we try and make sure that the change amount is not too small
-            if is_send_all {
-                return Ok(true);
-            }
-            if total_value == total_spend {
-                return Ok(false);
-            }
-            if total_value >= total_spend + MIN_CHANGE_TARGET && selected_utxos.len() > 1 {
-                return Ok(false);
-            }
-            Ok(true)
-        };
-        let owned_utxos = utxo_manager.utxos_sorted_by_amount();
-        let available_utxos: Vec<_> = if !preselected_utxos.is_empty() {
-            preselected_utxos.values().collect()
+        let mut candidates: Vec<WalletUtxo> = if !preselected_utxos.is_empty() {
+            preselected_utxos.values().cloned().collect()
         } else {
-            owned_utxos.iter().collect()
+            utxo_manager.utxos_sorted_by_amount()
         };
-        for utxo in available_utxos {
-            let should_continue = iteration(self, utxo_manager, utxo).await?;
-            if !should_continue {
-                break;
+        candidates.retain(|utxo| {
+            (from_addresses.is_empty() || from_addresses.contains(&&utxo.address))
+                && !utxo_manager.is_utxo_unspendable(utxo, dag_info.virtual_daa_score)
+                // Frozen or reserved UTXOs are only spent when the caller preselects them explicitly.
+                && (!preselected_utxos.is_empty() || !utxo_manager.is_utxo_held_back(&utxo.outpoint))
+        });
+        candidates.sort_by_key(|utxo| utxo.utxo_entry.amount);
+
+        let fee_per_input = match candidates.first() {
+            Some(first) => {
+                let estimated_recipient_value = if is_send_all {
+                    first.utxo_entry.amount
+                } else {
+                    amount
+                };
+                self.estimate_fee(
+                    std::slice::from_ref(first),
+                    fee_rate,
+                    max_fee,
+                    estimated_recipient_value,
+                    Some(recipient_script_public_key),
+                    payload,
+                    subnetwork_id,
+                )
+                .await?
             }
-        }
+            None => 0,
+        };
 
-        let total_spend: u64;
-        let total_received: u64;
-        if is_send_all {
-            total_spend = total_value;
-            total_received = total_value - fee;
+        let selection = if is_send_all {
+            CoinSelection {
+                utxos: candidates,
+                changeless: true,
+            }
         } else {
-            total_spend = amount + fee;
-            total_received = amount;
-        }
-
-        if total_value < total_spend {
-            return Err(WalletError::from(TransactionError::InsufficientFunds {
-                required_sompi: total_spend,
-                available_sompi: total_value,
-                location: ErrorLocation::capture(),
-            }));
-        }
-        if is_send_all && total_value == 0 {
-            return Err(WalletError::from(TransactionError::InsufficientFunds {
-                required_sompi: 0,
-                available_sompi: 0,
-                location: ErrorLocation::capture(),
-            }));
-        }
+            let target = SelectionTarget {
+                amount,
+                subtract_fee_from_amount,
+                fee_per_input,
+                max_fee,
+            };
+            coin_selection_strategy
+                .selector()
+                .select(&candidates, &target)
+        };
+        let total_value = selection.total_value();
+        let selected_utxos = selection.utxos;
+        let fee = selection_fee(fee_per_input, selected_utxos.len(), max_fee);
+        let (total_spend, total_received) = payment_totals(
+            amount,
+            total_value,
+            fee,
+            is_send_all,
+            subtract_fee_from_amount,
+        )?;
 
         debug!(
-            "Selected {} UTXOS with total_received: {}, total_value: {}, total_spend: {}",
+            "Selected {} UTXOS with total_received: {}, total_value: {}, total_spend: {}, changeless: {}",
             selected_utxos.len(),
             total_received,
             total_value,
-            total_spend
+            total_spend,
+            selection.changeless
         );
 
-        Ok((selected_utxos, total_received, total_value - total_spend))
+        // A changeless selection leaves at most one input's fee of excess, which goes to the fee
+        // rather than to a dust change output.
+        let change = if selection.changeless {
+            0
+        } else {
+            total_value - total_spend
+        };
+        Ok((selected_utxos, total_received, change))
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn estimate_fee(
         &self,
         selected_utxos: &Vec<WalletUtxo>,
         fee_rate: f64,
         max_fee: u64,
         estimated_recipient_value: u64,
+        recipient_script_public_key: Option<&ScriptPublicKey>,
         payload: &[u8],
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<u64> {
         let estimated_mass = self
-            .estimate_mass(selected_utxos, estimated_recipient_value, payload)
+            .estimate_mass(
+                selected_utxos,
+                estimated_recipient_value,
+                recipient_script_public_key,
+                payload,
+                subnetwork_id,
+            )
             .await?;
         let calculated_fee = ((estimated_mass as f64) * (fee_rate)).ceil() as u64;
         let fee = min(calculated_fee, max_fee);
@@ -1199,15 +2169,45 @@ impl TransactionGenerator {
             .normalized_max(&self.mass_cofactors)
     }
 
+    /// Fee mass of a transaction whose input entries are populated: the non-contextual fee mass,
+    /// or the KIP-9 storage mass when that is higher. A storage mass that overflows counts as
+    /// `u64::MAX`, so the fee falls to the caller's cap.
+    fn full_fee_mass(&self, signable_transaction: &SignableTransaction) -> u64 {
+        let non_contextual_fee_mass =
+            self.non_contextual_fee_mass(&signable_transaction.tx, self.keys.minimum_signatures);
+        let storage_mass = self
+            .consensus_mass_calculator
+            .calc_contextual_masses(&signable_transaction.as_verifiable())
+            .map_or(u64::MAX, |contextual_masses| contextual_masses.storage_mass);
+        non_contextual_fee_mass.max(storage_mass)
+    }
+
     pub async fn estimate_mass(
         &self,
         selected_utxos: &Vec<WalletUtxo>,
         estimated_recipient_value: u64,
+        recipient_script_public_key: Option<&ScriptPublicKey>,
         payload: &[u8],
+        subnetwork_id: SubnetworkId,
     ) -> WalletResult<u64> {
         let fake_public_key = &[0u8; 33];
         // We assume the worst case where the recipient address is ECDSA. In this case the scriptPubKey will be the longest.
         let fake_address = Address::new(self.address_prefix, Version::PubKeyECDSA, fake_public_key);
+        let fake_destination = PaymentDestination::from_address(fake_address);
+        // Raw script outputs are not bounded by the ECDSA worst case, so size the recipient on its own
+        // script when it is longer.
+        let recipient_destination = match recipient_script_public_key {
+            Some(script_public_key)
+                if script_public_key.script().len()
+                    > fake_destination.script_public_key.script().len() =>
+            {
+                PaymentDestination {
+                    script_public_key: script_public_key.clone(),
+                    address: None,
+                }
+            }
+            _ => fake_destination.clone(),
+        };
 
         let mut total_value = 0;
         for utxo in selected_utxos {
@@ -1217,23 +2217,25 @@ impl TransactionGenerator {
         // This is an approximation for the distribution of value between the recipient output and the change output.
         let mock_payments = if total_value > estimated_recipient_value {
             vec![
-                WalletPayment {
-                    address: fake_address.clone(),
-                    amount: estimated_recipient_value,
-                },
-                WalletPayment {
-                    address: fake_address,
-                    amount: total_value - estimated_recipient_value,
-                },
+                WalletPayment::to_destination(recipient_destination, estimated_recipient_value),
+                WalletPayment::to_destination(
+                    fake_destination,
+                    total_value - estimated_recipient_value,
+                ),
             ]
         } else {
-            vec![WalletPayment {
-                address: fake_address,
-                amount: total_value,
-            }]
+            vec![WalletPayment::to_destination(
+                recipient_destination,
+                total_value,
+            )]
         };
         let mock_transaction = self
-            .generate_unsigned_transaction(mock_payments, selected_utxos, payload.to_owned())
+            .generate_unsigned_transaction(
+                mock_payments,
+                selected_utxos,
+                payload.to_owned(),
+                subnetwork_id,
+            )
             .await?;
 
         let mass = self.non_contextual_fee_mass(
@@ -1288,6 +2290,223 @@ mod tests {
         assert_ne!(TX_VERSION, TX_VERSION_TOCCATA);
     }
 
+    fn test_fee_estimate() -> RpcFeeEstimate {
+        let bucket = |feerate: f64, estimated_seconds: f64| RpcFeerateBucket {
+            feerate,
+            estimated_seconds,
+        };
+        RpcFeeEstimate {
+            priority_bucket: bucket(10.0, 5.0),
+            normal_buckets: vec![bucket(5.0, 10.0), bucket(4.0, 20.0)],
+            low_buckets: vec![bucket(2.0, 60.0), bucket(1.0, 3600.0)],
+        }
+    }
+
+    #[test]
+    fn fee_priority_picks_the_matching_bucket() {
+        let fee_estimate = test_fee_estimate();
+        let limits = |priority: FeePriority| {
+            fee_limits_from_policy(
+                &fee_estimate,
+                Some(fee_policy::FeePolicy::Priority(priority.into())),
+            )
+            .unwrap()
+        };
+        assert_eq!(limits(FeePriority::High).fee_rate, 10.0);
+        assert_eq!(limits(FeePriority::Normal).fee_rate, 5.0);
+        let low = limits(FeePriority::Low);
+        assert_eq!(low.fee_rate, 2.0);
+        assert_eq!(low.estimated_seconds, 60.0);
+        assert_eq!(low.max_fee, DEFAULT_MAX_FEE);
+    }
+
+    fn utxos_with_amounts(amounts: impl IntoIterator<Item = u64>) -> Vec<WalletUtxo> {
+        amounts
+            .into_iter()
+            .enumerate()
+            .map(|(index, amount)| WalletUtxo {
+                outpoint: WalletOutpoint::new(
+                    kaspa_hashes::Hash::from_bytes([1u8; 32]),
+                    index as u32,
+                ),
+                utxo_entry: WalletUtxoEntry {
+                    amount,
+                    script_public_key: ScriptPublicKey::new(0, vec![].into()),
+                    block_daa_score: 0,
+                    is_coinbase: false,
+                    is_unconfirmed: false,
+                },
+                address: WalletAddress::new(0, 0, Keychain::External),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn consolidation_merges_the_smallest_utxos_down_to_the_target() {
+        let candidates = utxos_with_amounts([100, 200, 300, 400, 500]);
+        let inputs = consolidation_inputs(&candidates, 2).unwrap();
+        // Four merge into one change output, leaving the largest and the change: two UTXOs.
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|utxo| utxo.utxo_entry.amount)
+                .collect::<Vec<_>>(),
+            vec![100, 200, 300, 400]
+        );
+        assert_eq!(merged_amount(1_000, 30).unwrap(), 970);
+        assert!(merged_amount(1_000, 1_000).is_err());
+
+        assert!(consolidation_inputs(&candidates, 5).is_none());
+        assert!(consolidation_inputs(&candidates, 6).is_none());
+    }
+
+    #[test]
+    fn consolidation_splits_only_beyond_the_per_transaction_input_limit() {
+        // Room for 10 inputs of 1_000 mass per transaction.
+        let (mass_per_input, mass_for_inputs) = (1_000, 10_500);
+
+        // At or below the limit a single transaction takes every input.
+        assert_eq!(
+            split_counts(4, mass_per_input, mass_for_inputs).unwrap(),
+            (1, 10)
+        );
+        assert_eq!(
+            split_counts(10, mass_per_input, mass_for_inputs).unwrap(),
+            (1, 10)
+        );
+        // Above it the inputs are split, and the split outputs merged in one more transaction.
+        assert_eq!(
+            split_counts(11, mass_per_input, mass_for_inputs).unwrap(),
+            (2, 10)
+        );
+        assert_eq!(
+            split_counts(25, mass_per_input, mass_for_inputs).unwrap(),
+            (3, 10)
+        );
+        // An input too heavy for a split transaction on its own.
+        assert!(split_counts(2, 20_000, mass_for_inputs).is_err());
+    }
+
+    #[test]
+    fn child_fee_lifts_the_package_to_the_target_fee_rate() {
+        let (parent_mass, child_mass, target_fee_rate) = (2_000, 1_000, 5.0);
+        for parent_fee in [0, 2_000, 6_000] {
+            let child_fee =
+                child_pays_for_parent_fee(parent_fee, parent_mass, child_mass, target_fee_rate);
+            let package_fee_rate =
+                (parent_fee + child_fee) as f64 / (parent_mass + child_mass) as f64;
+            assert_eq!(package_fee_rate, target_fee_rate);
+        }
+        // Whatever the parent already pays comes off the child's fee.
+        assert_eq!(child_pays_for_parent_fee(0, 2_000, 1_000, 5.0), 15_000);
+        assert_eq!(child_pays_for_parent_fee(6_000, 2_000, 1_000, 5.0), 9_000);
+    }
+
+    #[test]
+    fn child_fee_never_drops_below_the_childs_minimum_relay_fee() {
+        // The parent alone already beats the target.
+        assert_eq!(child_pays_for_parent_fee(50_000, 2_000, 1_000, 5.0), 1_000);
+    }
+
+    #[test]
+    fn fee_rates_below_the_minimum_are_rejected_as_fee_rates() {
+        let err = ensure_minimum_fee_rate(0.5).unwrap_err();
+        assert!(err.to_string().contains("FeeRateTooLow"), "got: {err}");
+        ensure_minimum_fee_rate(MIN_FEE_RATE).unwrap();
+    }
+
+    #[test]
+    fn max_sendable_amount_is_spent_exactly_without_change() {
+        let total_value = 30_000;
+        let input_count = 3;
+        // Uncapped, and a fee-policy `max_fee` below the summed per-input fees.
+        for (max_fee, expected_fee) in [(DEFAULT_MAX_FEE, 3_000), (1_500, 1_500)] {
+            let fee = selection_fee(1_000, input_count, max_fee);
+            assert_eq!(fee, expected_fee);
+
+            let (send_all_spend, max_amount) =
+                payment_totals(0, total_value, fee, true, false).unwrap();
+            assert_eq!(send_all_spend, total_value);
+            assert_eq!(max_amount, total_value - expected_fee);
+
+            // Sending that amount from the same selection spends all of it, leaving no change.
+            let (total_spend, total_received) =
+                payment_totals(max_amount, total_value, fee, false, false).unwrap();
+            assert_eq!(total_spend, total_value);
+            assert_eq!(total_received, max_amount);
+
+            // One sompi more does not fit.
+            assert!(payment_totals(max_amount + 1, total_value, fee, false, false).is_err());
+        }
+    }
+
+    #[test]
+    fn send_all_of_less_than_the_fee_is_insufficient() {
+        assert!(payment_totals(0, 1_000, 1_000, true, false).is_err());
+        assert!(payment_totals(0, 0, 0, true, false).is_err());
+    }
+
+    #[test]
+    fn fee_priority_falls_back_to_the_priority_bucket_without_normal_buckets() {
+        let fee_estimate = RpcFeeEstimate {
+            normal_buckets: vec![],
+            low_buckets: vec![],
+            ..test_fee_estimate()
+        };
+        assert_eq!(
+            fee_bucket_for_priority(&fee_estimate, FeePriority::Normal).feerate,
+            10.0
+        );
+        assert_eq!(
+            fee_bucket_for_priority(&fee_estimate, FeePriority::Low).feerate,
+            10.0
+        );
+    }
+
+    #[test]
+    fn fee_priority_unspecified_is_rejected() {
+        let err = fee_limits_from_policy(
+            &test_fee_estimate(),
+            Some(fee_policy::FeePolicy::Priority(
+                FeePriority::Unspecified.into(),
+            )),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind_name(), "InvalidArgument", "got: {err}");
+    }
+
+    #[test]
+    fn fee_target_seconds_picks_the_cheapest_bucket_in_time() {
+        let fee_estimate = test_fee_estimate();
+        let limits = |target_seconds: u32| {
+            fee_limits_from_policy(
+                &fee_estimate,
+                Some(fee_policy::FeePolicy::TargetSeconds(target_seconds)),
+            )
+            .unwrap()
+        };
+        assert_eq!(limits(30).fee_rate, 4.0);
+        assert_eq!(limits(60).fee_rate, 2.0);
+        // Nothing is expected that fast: fall back to the priority rate.
+        assert_eq!(limits(1).fee_rate, 10.0);
+        assert!(
+            fee_limits_from_policy(&fee_estimate, Some(fee_policy::FeePolicy::TargetSeconds(0)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn exact_fee_rate_reports_the_time_of_the_bucket_it_pays_for() {
+        let fee_estimate = test_fee_estimate();
+        let limits = fee_limits_from_policy(
+            &fee_estimate,
+            Some(fee_policy::FeePolicy::ExactFeeRate(4.5)),
+        )
+        .unwrap();
+        assert_eq!(limits.fee_rate, 4.5);
+        assert_eq!(limits.estimated_seconds, 20.0);
+    }
+
     const TEST_MASS_PER_SIG_OP: u64 = 1000;
 
     #[test]
@@ -1524,6 +2743,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn total_input_and_output_value_sums_entries_and_outputs() {
+        use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+        let spk = ScriptPublicKey::from_vec(0, vec![0x51]);
+        let outpoint = TransactionOutpoint::new(kaspa_hashes::Hash::from_bytes([7u8; 32]), 0);
+        let tx = Transaction::new(
+            0,
+            vec![
+                TransactionInput::new(outpoint, vec![], 0, 1),
+                TransactionInput::new(
+                    TransactionOutpoint::new(outpoint.transaction_id, 1),
+                    vec![],
+                    0,
+                    1,
+                ),
+            ],
+            vec![
+                TransactionOutput::new(700, spk.clone()),
+                TransactionOutput::new(250, spk.clone()),
+            ],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entry = |amount| UtxoEntry {
+            amount,
+            script_public_key: spk.clone(),
+            block_daa_score: 0,
+            is_coinbase: false,
+            covenant_id: None,
+        };
+        let signable = SignableTransaction::with_entries(tx, vec![entry(600), entry(400)]);
+
+        // The split/merge path derives each split transaction's fee from this pair when the
+        // recipient pays the fee.
+        assert_eq!(total_input_and_output_value(&signable), (1000, 950));
+    }
+
     #[test]
     fn compute_budget_for_overflow_returns_error() {
         // A pathological minimum_signatures that overflows u16 must be
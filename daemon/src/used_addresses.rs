@@ -0,0 +1,127 @@
+use common::error_location::ErrorLocation;
+use common::errors::{StorageError, WalletError, WalletResult};
+use common::json_store::{read_json_file, write_json_file};
+use common::model::{Keychain, WalletAddress};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const USED_ADDRESSES_FILE_KIND: &str = "used_addresses.json";
+
+#[derive(Serialize, Deserialize)]
+struct UsedAddressJson {
+    keychain: u32,
+    index: u32,
+    cosigner_index: u16,
+}
+
+/// Addresses kaspad has reported a balance for at some point, persisted next to the keys file
+/// so an address stays used after it is spent from and the daemon restarts.
+#[derive(Debug)]
+pub struct UsedAddressStore {
+    file_path: Option<String>,
+    used: HashSet<WalletAddress>,
+}
+
+impl UsedAddressStore {
+    pub fn load(file_path: &str) -> WalletResult<Self> {
+        let entries: Vec<UsedAddressJson> =
+            read_json_file(file_path, USED_ADDRESSES_FILE_KIND)?.unwrap_or_default();
+        let mut used = HashSet::with_capacity(entries.len());
+        for entry in entries {
+            let keychain = match entry.keychain {
+                0 => Keychain::External,
+                1 => Keychain::Internal,
+                other => {
+                    return Err(WalletError::from(StorageError::Deserialize {
+                        kind: USED_ADDRESSES_FILE_KIND,
+                        reason: format!("invalid keychain {other}"),
+                        location: ErrorLocation::capture(),
+                    }));
+                }
+            };
+            used.insert(WalletAddress::new(
+                entry.index,
+                entry.cosigner_index,
+                keychain,
+            ));
+        }
+        Ok(Self {
+            file_path: Some(file_path.to_string()),
+            used,
+        })
+    }
+
+    /// A store that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            file_path: None,
+            used: HashSet::new(),
+        }
+    }
+
+    pub fn is_used(&self, wallet_address: &WalletAddress) -> bool {
+        self.used.contains(wallet_address)
+    }
+
+    pub fn used_addresses(&self) -> HashSet<WalletAddress> {
+        self.used.clone()
+    }
+
+    /// Mark `wallet_addresses` as used, writing the store only if any of them is new.
+    pub fn mark_used(
+        &mut self,
+        wallet_addresses: impl IntoIterator<Item = WalletAddress>,
+    ) -> WalletResult<()> {
+        let mut is_changed = false;
+        for wallet_address in wallet_addresses {
+            is_changed |= self.used.insert(wallet_address);
+        }
+        if is_changed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> WalletResult<()> {
+        let Some(file_path) = &self.file_path else {
+            return Ok(());
+        };
+        let mut entries: Vec<UsedAddressJson> = self
+            .used
+            .iter()
+            .map(|wallet_address| UsedAddressJson {
+                keychain: wallet_address.keychain.clone() as u32,
+                index: wallet_address.index,
+                cosigner_index: wallet_address.cosigner_index,
+            })
+            .collect();
+        entries.sort_by_key(|entry| (entry.keychain, entry.index, entry.cosigner_index));
+        write_json_file(file_path, USED_ADDRESSES_FILE_KIND, &entries)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn used_addresses_persist_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("used_addresses.json");
+        let path = path.to_str().unwrap();
+
+        let mut store = UsedAddressStore::load(path).unwrap();
+        store
+            .mark_used([
+                WalletAddress::new(3, 0, Keychain::External),
+                WalletAddress::new(0, 0, Keychain::Internal),
+            ])
+            .unwrap();
+
+        let reloaded = UsedAddressStore::load(path).unwrap();
+        assert!(reloaded.is_used(&WalletAddress::new(3, 0, Keychain::External)));
+        assert!(reloaded.is_used(&WalletAddress::new(0, 0, Keychain::Internal)));
+        assert!(!reloaded.is_used(&WalletAddress::new(3, 0, Keychain::Internal)));
+    }
+}
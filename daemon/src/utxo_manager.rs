@@ -1,32 +1,71 @@
 use crate::address_manager::{AddressManager, AddressSet};
+use crate::frozen_utxos::{FrozenUtxo, FrozenUtxoStore};
+use crate::utxo_reservations::UtxoReservations;
 use common::errors::WalletResult;
 use common::model::{
-    WalletOutpoint, WalletSignableTransaction, WalletUtxo, WalletUtxoEntry,
-    is_spendable_rpc_utxo_entry,
+    CovenantBoundUtxo, WalletOutpoint, WalletSignableTransaction, WalletUtxo, WalletUtxoEntry,
 };
 use itertools::Itertools;
 use kaspa_consensus_core::config::params::Params;
+use kaspa_consensus_core::tx::TransactionId;
 use kaspa_rpc_core::{RpcMempoolEntryByAddress, RpcUtxosByAddressesEntry};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::warn;
 
+/// A wallet-generated transaction that was submitted to kaspad but is not yet accepted.
+#[derive(Debug, Clone)]
+pub struct TrackedMempoolTransaction {
+    pub transaction: WalletSignableTransaction,
+    /// Virtual DAA score known to the wallet when the transaction was submitted.
+    pub submitted_daa_score: u64,
+    /// Times the rebroadcast manager resubmitted the transaction after kaspad dropped it.
+    pub rebroadcast_attempts: u32,
+    /// Error of the most recent rebroadcast, cleared by a successful one.
+    pub last_rebroadcast_error: Option<String>,
+    /// Virtual DAA score at the first sync that found the transaction missing from kaspad's
+    /// mempool. Cleared whenever a sync finds kaspad holding it again.
+    pub missing_since_daa_score: Option<u64>,
+}
+
+impl TrackedMempoolTransaction {
+    pub fn id(&self) -> TransactionId {
+        self.transaction.transaction.inner().id()
+    }
+}
+
 pub struct UtxoManager {
     address_manager: Arc<Mutex<AddressManager>>,
     coinbase_maturity: u64, // Is different in testnet
+    // Tracked transactions missing from kaspad's mempool for this many DAA score units are
+    // dropped and their inputs restored. 0 disables expiry.
+    mempool_transaction_expiry_daa_window: u64,
+    virtual_daa_score: u64,
 
     utxos_sorted_by_amount: Vec<WalletUtxo>,
     utxos_by_outpoint: HashMap<WalletOutpoint, WalletUtxo>,
+    // Kept apart from the UTXO set so they never reach coin selection.
+    covenant_bound_utxos: Vec<CovenantBoundUtxo>,
     // Since the sync stage of address collection only picks up on addresses that have accepted
     // (non-mempool) balance, we might miss some mempool outputs that are not yet accepted.
     // To mitigate this we maintain a list of mempool transactions generated by this wallet
     // that should be accepted soon, but are not yet accepted by consensus.
-    mempool_transactions: Vec<WalletSignableTransaction>,
+    mempool_transactions: Vec<TrackedMempoolTransaction>,
+    // Outpoints excluded from automatic coin selection until unfrozen.
+    frozen_utxos: FrozenUtxoStore,
+    // Outpoints leased to unsigned transactions returned by CreateUnsignedTransactions.
+    utxo_reservations: UtxoReservations,
 }
 
 impl UtxoManager {
-    pub fn new(address_manager: Arc<Mutex<AddressManager>>, consensus_params: Params) -> Self {
+    pub fn new(
+        address_manager: Arc<Mutex<AddressManager>>,
+        consensus_params: Params,
+        mempool_transaction_expiry_daa_window: u64,
+        frozen_utxos: FrozenUtxoStore,
+        utxo_reservations: UtxoReservations,
+    ) -> Self {
         // Upstream collapsed `coinbase_maturity` from a DAA-score-keyed
         // Forkable into a flat `u64` on the Toccata branch.
         let coinbase_maturity = consensus_params.coinbase_maturity();
@@ -34,9 +73,14 @@ impl UtxoManager {
         Self {
             address_manager,
             coinbase_maturity,
+            mempool_transaction_expiry_daa_window,
+            virtual_daa_score: 0,
             utxos_sorted_by_amount: Vec::new(),
             utxos_by_outpoint: HashMap::new(),
+            covenant_bound_utxos: Vec::new(),
             mempool_transactions: Vec::new(),
+            frozen_utxos,
+            utxo_reservations,
         }
     }
 
@@ -48,21 +92,230 @@ impl UtxoManager {
         self.utxos_by_outpoint.clone()
     }
 
+    pub fn coinbase_maturity(&self) -> u64 {
+        self.coinbase_maturity
+    }
+
+    pub fn covenant_bound_utxos(&self) -> Vec<CovenantBoundUtxo> {
+        self.covenant_bound_utxos.clone()
+    }
+
+    pub fn is_utxo_frozen(&self, outpoint: &WalletOutpoint) -> bool {
+        self.frozen_utxos.is_frozen(outpoint)
+    }
+
+    /// Whether automatic coin selection must skip the outpoint: it is frozen or leased to an
+    /// unsigned transaction.
+    pub fn is_utxo_held_back(&self, outpoint: &WalletOutpoint) -> bool {
+        self.frozen_utxos.is_frozen(outpoint) || self.utxo_reservations.is_reserved(outpoint)
+    }
+
+    pub fn is_utxo_reserved(&self, outpoint: &WalletOutpoint) -> bool {
+        self.utxo_reservations.is_reserved(outpoint)
+    }
+
+    /// Lease the inputs of `transactions` to them until they are broadcast, released or the
+    /// reservation TTL passes.
+    pub fn reserve_transaction_inputs(&mut self, transactions: &[WalletSignableTransaction]) {
+        for transaction in transactions {
+            let tx = &transaction.transaction.inner().tx;
+            let outpoints: Vec<WalletOutpoint> = tx
+                .inputs
+                .iter()
+                .map(|input| input.previous_outpoint.into())
+                .collect();
+            self.utxo_reservations.reserve(tx.id(), &outpoints);
+        }
+    }
+
+    pub fn release_reservation(&mut self, transaction_id: &TransactionId) -> Vec<WalletOutpoint> {
+        self.utxo_reservations.release(transaction_id)
+    }
+
+    pub fn frozen_utxo(&self, outpoint: &WalletOutpoint) -> Option<&FrozenUtxo> {
+        self.frozen_utxos.get(outpoint)
+    }
+
+    pub fn frozen_utxos(&self) -> Vec<(WalletOutpoint, FrozenUtxo)> {
+        self.frozen_utxos.list()
+    }
+
+    pub fn freeze_utxos(&mut self, outpoints: &[WalletOutpoint], reason: &str) -> WalletResult<()> {
+        self.frozen_utxos.freeze(outpoints, reason)
+    }
+
+    pub fn unfreeze_utxos(
+        &mut self,
+        outpoints: &[WalletOutpoint],
+    ) -> WalletResult<Vec<WalletOutpoint>> {
+        self.frozen_utxos.unfreeze(outpoints)
+    }
+
     pub async fn add_mempool_transaction(&mut self, transaction: &WalletSignableTransaction) {
-        self.mempool_transactions.push(transaction.clone());
-        self.apply_mempool_transaction(transaction).await;
+        // Broadcasting consumes the lease taken when the transaction was created.
+        self.utxo_reservations
+            .release(&transaction.transaction.inner().id());
+        let tracked_transaction = TrackedMempoolTransaction {
+            transaction: transaction.clone(),
+            submitted_daa_score: self.virtual_daa_score,
+            rebroadcast_attempts: 0,
+            last_rebroadcast_error: None,
+            missing_since_daa_score: None,
+        };
+        self.track_mempool_transaction(tracked_transaction).await;
+    }
+
+    async fn track_mempool_transaction(&mut self, tracked_transaction: TrackedMempoolTransaction) {
+        self.apply_mempool_transaction(&tracked_transaction.transaction)
+            .await;
+        self.mempool_transactions.push(tracked_transaction);
+    }
+
+    pub fn mempool_transactions(&self) -> Vec<TrackedMempoolTransaction> {
+        self.mempool_transactions.clone()
+    }
+
+    pub fn record_rebroadcast_attempt(
+        &mut self,
+        transaction_id: &TransactionId,
+        error: Option<String>,
+    ) {
+        if let Some(tracked_transaction) = self
+            .mempool_transactions
+            .iter_mut()
+            .find(|tracked_transaction| tracked_transaction.id() == *transaction_id)
+        {
+            tracked_transaction.rebroadcast_attempts += 1;
+            tracked_transaction.last_rebroadcast_error = error;
+        }
+    }
+
+    pub fn mempool_transaction(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> Option<WalletSignableTransaction> {
+        self.mempool_transactions
+            .iter()
+            .find(|tracked_transaction| tracked_transaction.id() == *transaction_id)
+            .map(|tracked_transaction| tracked_transaction.transaction.clone())
+    }
+
+    /// True if another tracked mempool transaction spends one of the outputs of
+    /// `transaction_id`. Replacing or dropping such a parent would orphan the child.
+    pub fn has_mempool_descendants(&self, transaction_id: &TransactionId) -> bool {
+        self.mempool_transactions.iter().any(|tracked_transaction| {
+            tracked_transaction
+                .transaction
+                .transaction
+                .inner()
+                .tx
+                .inputs
+                .iter()
+                .any(|input| input.previous_outpoint.transaction_id == *transaction_id)
+        })
+    }
+
+    /// Swap a tracked mempool transaction for its replacement (RBF). Both spend the same inputs,
+    /// so the replaced transaction's effects are undone before the replacement is applied.
+    pub async fn replace_mempool_transaction(
+        &mut self,
+        replaced_transaction_id: &TransactionId,
+        replacement: &WalletSignableTransaction,
+    ) {
+        self.remove_mempool_transaction(replaced_transaction_id);
+        self.add_mempool_transaction(replacement).await;
+    }
+
+    /// Stop tracking `transaction_id` and every tracked transaction that (transitively) spends
+    /// its outputs, restoring their inputs. Returns the ids of the dropped transactions,
+    /// descendants first; empty if `transaction_id` is not tracked.
+    pub fn abandon_mempool_transaction(
+        &mut self,
+        transaction_id: &TransactionId,
+    ) -> Vec<TransactionId> {
+        if self.mempool_transaction(transaction_id).is_none() {
+            return vec![];
+        }
+
+        let mut abandoned = vec![];
+        let children: Vec<TransactionId> = self
+            .mempool_transactions
+            .iter()
+            .filter(|tracked_transaction| {
+                tracked_transaction
+                    .transaction
+                    .transaction
+                    .inner()
+                    .tx
+                    .inputs
+                    .iter()
+                    .any(|input| input.previous_outpoint.transaction_id == *transaction_id)
+            })
+            .map(TrackedMempoolTransaction::id)
+            .collect();
+        for child in children {
+            abandoned.extend(self.abandon_mempool_transaction(&child));
+        }
+
+        self.remove_mempool_transaction(transaction_id);
+        abandoned.push(*transaction_id);
+        abandoned
+    }
+
+    /// Stop tracking a mempool transaction and undo `apply_mempool_transaction`: its outputs
+    /// leave the UTXO set and its inputs are restored.
+    fn remove_mempool_transaction(
+        &mut self,
+        transaction_id: &TransactionId,
+    ) -> Option<WalletSignableTransaction> {
+        let position = self
+            .mempool_transactions
+            .iter()
+            .position(|tracked_transaction| tracked_transaction.id() == *transaction_id)?;
+        let transaction = self.mempool_transactions.remove(position).transaction;
+        let signable_transaction = transaction.transaction.inner();
+
+        for index in 0..signable_transaction.tx.outputs.len() {
+            let outpoint = WalletOutpoint::new(*transaction_id, index as u32);
+            if self.contains_utxo(&outpoint) {
+                self.remove_utxo(&outpoint);
+            }
+        }
+
+        for (i, input) in signable_transaction.tx.inputs.iter().enumerate() {
+            let outpoint: WalletOutpoint = input.previous_outpoint.into();
+            let (Some(entry), Some(address)) = (
+                signable_transaction.entries[i].clone(),
+                transaction.address_by_input_index.get(i),
+            ) else {
+                continue;
+            };
+            if !self.contains_utxo(&outpoint) {
+                let utxo = WalletUtxo::new(outpoint.clone(), entry.into(), address.clone());
+                self.insert_utxo(outpoint, utxo);
+            }
+        }
+
+        Some(transaction)
     }
 
     async fn apply_mempool_transaction(&mut self, transaction: &WalletSignableTransaction) {
         let tx = &transaction.transaction.inner().tx;
 
         for input in &tx.inputs {
-            let outpoint = input.previous_outpoint;
-            self.remove_utxo(&outpoint.into());
+            // Inputs of a sweep from an external key were never in the wallet's UTXO set.
+            let outpoint = input.previous_outpoint.into();
+            if self.contains_utxo(&outpoint) {
+                self.remove_utxo(&outpoint);
+            }
         }
 
         for (i, output) in tx.outputs.iter().enumerate() {
-            let address_string = transaction.address_by_output_index[i].to_string();
+            let Some(address) = &transaction.address_by_output_index[i] else {
+                // Raw script output with no address form - never ours.
+                continue;
+            };
+            let address_string = address.to_string();
             let wallet_address = {
                 let address_manager = self.address_manager.lock().await;
                 address_manager
@@ -78,6 +331,10 @@ impl UtxoManager {
                 transaction_id: tx.id(),
                 index: i as u32,
             };
+            // The sync already added the output from kaspad's mempool view, as unconfirmed.
+            if self.contains_utxo(&outpoint) {
+                self.remove_utxo(&outpoint);
+            }
             // `is_unconfirmed: false` even though the parent tx has not
             // confirmed yet: this path runs only for `SubmitSource::Internal`
             // submissions whose payload we built, signed, and just shipped
@@ -131,12 +388,21 @@ impl UtxoManager {
         &mut self,
         rpc_utxo_entries: Vec<RpcUtxosByAddressesEntry>,
         rpc_mempool_utxo_entries: Vec<RpcMempoolEntryByAddress>,
+        virtual_daa_score: u64,
     ) -> WalletResult<()> {
+        self.virtual_daa_score = virtual_daa_score;
         let mut wallet_utxos: Vec<WalletUtxo> = vec![];
+        let mut covenant_bound_utxos: Vec<CovenantBoundUtxo> = vec![];
 
         let mut exclude: HashSet<WalletOutpoint> = HashSet::new();
+        // Every wallet-originated transaction kaspad still holds spends at least one wallet
+        // address, so it shows up as a `sending` entry.
+        let mut in_mempool: HashSet<TransactionId> = HashSet::new();
         for rpc_mempool_entries_by_address in &rpc_mempool_utxo_entries {
             for sending_rpc_mempool_entry in &rpc_mempool_entries_by_address.sending {
+                if let Some(verbose_data) = &sending_rpc_mempool_entry.transaction.verbose_data {
+                    in_mempool.insert(verbose_data.transaction_id);
+                }
                 for input in &sending_rpc_mempool_entry.transaction.inputs {
                     exclude.insert(input.previous_outpoint.into());
                 }
@@ -155,32 +421,40 @@ impl UtxoManager {
                 continue;
             }
 
+            let address = address_set
+                .get(&rpc_utxo_entry.address.as_ref().unwrap().to_string())
+                .unwrap();
+
             // Covenant-bound UTXOs cannot be spent by this wallet — the
-            // signer does not satisfy the covenant. Skip them at the
+            // signer does not satisfy the covenant. Divert them at the
             // sync boundary (the only path where `RpcUtxoEntry` enters
-            // the wallet) so they never reach UTXO selection.
-            if !is_spendable_rpc_utxo_entry(&rpc_utxo_entry.utxo_entry) {
-                warn!(
-                    outpoint = ?wallet_outpoint,
-                    covenant_id = ?rpc_utxo_entry.utxo_entry.covenant_id,
-                    "skipping covenant-bound UTXO; this wallet cannot spend covenants"
+            // the wallet) so they never reach UTXO selection, but keep
+            // them so balances still account for them.
+            if let Some(covenant_id) = rpc_utxo_entry.utxo_entry.covenant_id {
+                let rpc_entry = &rpc_utxo_entry.utxo_entry;
+                let utxo_entry = WalletUtxoEntry::new(
+                    rpc_entry.amount,
+                    rpc_entry.script_public_key.clone(),
+                    rpc_entry.block_daa_score,
+                    rpc_entry.is_coinbase,
+                    false,
                 );
+                covenant_bound_utxos.push(CovenantBoundUtxo {
+                    utxo: WalletUtxo::new(wallet_outpoint, utxo_entry, address.clone()),
+                    covenant_id,
+                });
                 continue;
             }
 
-            // Safe to `try_into` after the filter above: covenant-bound
-            // entries are the only failure mode and we've already skipped
+            // Safe to `try_into` after the branch above: covenant-bound
+            // entries are the only failure mode and we've already diverted
             // them. Use `expect` so any future variant addition trips
             // loudly during development.
             let wallet_utxo_entry: WalletUtxoEntry = rpc_utxo_entry
                 .utxo_entry
                 .clone()
                 .try_into()
-                .expect("covenant-bound entry already filtered above");
-
-            let address = address_set
-                .get(&rpc_utxo_entry.address.as_ref().unwrap().to_string())
-                .unwrap();
+                .expect("covenant-bound entry already diverted above");
 
             let wallet_utxo = WalletUtxo::new(wallet_outpoint, wallet_utxo_entry, address.clone());
 
@@ -241,23 +515,60 @@ impl UtxoManager {
 
         self.update_utxos_sorted_by_amount(wallet_utxos.clone());
         self.update_utxos_by_outpoint(wallet_utxos);
+        self.update_covenant_bound_utxos(covenant_bound_utxos);
 
-        self.apply_mempool_transactions_after_update().await;
+        self.apply_mempool_transactions_after_update(&in_mempool)
+            .await;
         Ok(())
     }
 
-    async fn apply_mempool_transactions_after_update(&mut self) {
+    async fn apply_mempool_transactions_after_update(
+        &mut self,
+        in_mempool: &HashSet<TransactionId>,
+    ) {
         let previous_mempool_transactions = std::mem::take(&mut self.mempool_transactions);
         self.mempool_transactions = vec![];
-        'outer: for transaction in &previous_mempool_transactions {
-            for input in transaction.transaction.inner().tx.inputs.iter() {
+        'outer: for mut tracked_transaction in previous_mempool_transactions {
+            // The sync excluded the inputs of everything kaspad holds, so a held transaction
+            // must be kept before its inputs are looked at.
+            if in_mempool.contains(&tracked_transaction.id()) {
+                tracked_transaction.missing_since_daa_score = None;
+                self.track_mempool_transaction(tracked_transaction).await;
+                continue;
+            }
+            for input in tracked_transaction
+                .transaction
+                .transaction
+                .inner()
+                .tx
+                .inputs
+                .iter()
+            {
                 let outpoint = input.previous_outpoint;
                 if !self.contains_utxo(&outpoint.into()) {
                     // this means this transaction was either accepted or double-spent
                     continue 'outer;
                 }
             }
-            self.add_mempool_transaction(transaction).await;
+            // Inputs are all still unspent, yet kaspad no longer has the transaction: it was
+            // rejected or evicted. Give it a grace window before releasing the inputs.
+            let missing_since_daa_score = *tracked_transaction
+                .missing_since_daa_score
+                .get_or_insert(self.virtual_daa_score);
+            if is_mempool_transaction_expired(
+                self.mempool_transaction_expiry_daa_window,
+                missing_since_daa_score,
+                self.virtual_daa_score,
+            ) {
+                warn!(
+                    tx_id = %tracked_transaction.id(),
+                    missing_since_daa_score,
+                    virtual_daa_score = self.virtual_daa_score,
+                    "tracked transaction is no longer in kaspad's mempool; expiring it"
+                );
+                continue;
+            }
+            self.track_mempool_transaction(tracked_transaction).await;
         }
     }
 
@@ -266,6 +577,20 @@ impl UtxoManager {
         self.utxos_sorted_by_amount = wallet_utxos;
     }
 
+    fn update_covenant_bound_utxos(&mut self, covenant_bound_utxos: Vec<CovenantBoundUtxo>) {
+        // Warn once per outpoint rather than on every sync cycle.
+        for covenant_bound_utxo in
+            newly_covenant_bound_utxos(&self.covenant_bound_utxos, &covenant_bound_utxos)
+        {
+            warn!(
+                outpoint = ?covenant_bound_utxo.utxo.outpoint,
+                covenant_id = %covenant_bound_utxo.covenant_id,
+                "keeping covenant-bound UTXO out of coin selection; this wallet cannot spend covenants"
+            );
+        }
+        self.covenant_bound_utxos = covenant_bound_utxos;
+    }
+
     fn update_utxos_by_outpoint(&mut self, wallet_utxos: Vec<WalletUtxo>) {
         self.utxos_by_outpoint.clear();
         for wallet_utxo in wallet_utxos {
@@ -307,10 +632,50 @@ pub(crate) fn is_utxo_unspendable_with(
     utxo.utxo_entry.block_daa_score + coinbase_maturity > virtual_daa_score
 }
 
+/// True if a tracked transaction kaspad no longer holds has been missing for long enough to
+/// release its inputs. A `window` of 0 disables expiry.
+pub(crate) fn is_mempool_transaction_expired(
+    window: u64,
+    missing_since_daa_score: u64,
+    virtual_daa_score: u64,
+) -> bool {
+    if window == 0 {
+        return false;
+    }
+    virtual_daa_score >= missing_since_daa_score.saturating_add(window)
+}
+
+/// Covenant-bound UTXOs in `current` that were not already known in `previous`.
+pub(crate) fn newly_covenant_bound_utxos<'a>(
+    previous: &[CovenantBoundUtxo],
+    current: &'a [CovenantBoundUtxo],
+) -> Vec<&'a CovenantBoundUtxo> {
+    let previous_outpoints: HashSet<&WalletOutpoint> = previous
+        .iter()
+        .map(|covenant_bound_utxo| &covenant_bound_utxo.utxo.outpoint)
+        .collect();
+    current
+        .iter()
+        .filter(|covenant_bound_utxo| {
+            !previous_outpoints.contains(&covenant_bound_utxo.utxo.outpoint)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::used_addresses::UsedAddressStore;
+    use common::keys::Keys;
     use common::model::{Keychain, WalletAddress};
+    use kaspa_addresses::{Address, Prefix, Version};
+    use kaspa_consensus_core::config::params::DEVNET_PARAMS;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{
+        ScriptPublicKey, SignableTransaction, Transaction, TransactionInput, TransactionOutpoint,
+        TransactionOutput, UtxoEntry,
+    };
+    use std::time::Duration;
 
     const COINBASE_MATURITY: u64 = 100;
 
@@ -328,6 +693,91 @@ mod tests {
         }
     }
 
+    fn make_utxo_manager(mempool_transaction_expiry_daa_window: u64) -> UtxoManager {
+        let keys = Keys::new(
+            String::new(),
+            1,
+            vec![],
+            kaspa_bip32::Prefix::KPUB,
+            vec![],
+            0,
+            0,
+            1,
+            0,
+        );
+        let address_manager = AddressManager::new(
+            Arc::new(keys),
+            Prefix::Devnet,
+            UsedAddressStore::in_memory(),
+        );
+        UtxoManager::new(
+            Arc::new(Mutex::new(address_manager)),
+            DEVNET_PARAMS.clone(),
+            mempool_transaction_expiry_daa_window,
+            FrozenUtxoStore::in_memory(),
+            UtxoReservations::new(Duration::from_secs(60)),
+        )
+    }
+
+    /// A transaction spending `spent_outpoint` to an address outside the wallet. Like an external
+    /// sweep, it records no input addresses or derivation paths.
+    fn make_transaction_spending(spent_outpoint: &WalletOutpoint) -> WalletSignableTransaction {
+        let script_public_key = ScriptPublicKey::new(0, vec![].into());
+        let transaction = Transaction::new(
+            0,
+            vec![TransactionInput::new(
+                TransactionOutpoint::new(spent_outpoint.transaction_id, spent_outpoint.index),
+                vec![],
+                0,
+                1,
+            )],
+            vec![TransactionOutput::new(9_000, script_public_key.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entries = vec![UtxoEntry {
+            amount: 10_000,
+            script_public_key,
+            block_daa_score: 42,
+            is_coinbase: false,
+            covenant_id: None,
+        }];
+        let destination = Address::new(Prefix::Devnet, Version::PubKey, &[3u8; 32]);
+        WalletSignableTransaction::new_from_unsigned(
+            SignableTransaction::with_entries(transaction, entries),
+            vec![],
+            vec![],
+            vec![Some(destination)],
+        )
+    }
+
+    #[tokio::test]
+    async fn sweeping_utxos_not_in_the_wallet_is_tracked_without_touching_the_utxo_set() {
+        let mut utxo_manager = make_utxo_manager(0);
+        let wallet_utxo = make_utxo(false, false, 42);
+        utxo_manager.insert_utxo(wallet_utxo.outpoint.clone(), wallet_utxo.clone());
+
+        let external_outpoint = WalletOutpoint::new(kaspa_hashes::Hash::from_bytes([9u8; 32]), 0);
+        let sweep = make_transaction_spending(&external_outpoint);
+        let sweep_id = sweep.transaction.inner().id();
+        utxo_manager.add_mempool_transaction(&sweep).await;
+
+        assert!(utxo_manager.mempool_transaction(&sweep_id).is_some());
+        assert_eq!(
+            utxo_manager.utxos_sorted_by_amount(),
+            vec![wallet_utxo.clone()]
+        );
+
+        // Abandoning it must not restore the external input into the wallet either.
+        assert_eq!(
+            utxo_manager.abandon_mempool_transaction(&sweep_id),
+            vec![sweep_id]
+        );
+        assert_eq!(utxo_manager.utxos_sorted_by_amount(), vec![wallet_utxo]);
+    }
+
     #[test]
     fn is_utxo_unspendable_rejects_unconfirmed() {
         // virtual_daa_score is irrelevant: the unconfirmed branch
@@ -366,4 +816,132 @@ mod tests {
         let utxo = make_utxo(true, true, 50);
         assert!(is_utxo_unspendable_with(COINBASE_MATURITY, &utxo, 999_999));
     }
+
+    /// Stand-in for `update_utxo_set`: kaspad reports `utxos`, which leave out the inputs of the
+    /// transactions in `in_mempool`, then the tracked transactions are re-applied.
+    async fn sync(
+        utxo_manager: &mut UtxoManager,
+        utxos: Vec<WalletUtxo>,
+        in_mempool: &[TransactionId],
+        virtual_daa_score: u64,
+    ) {
+        utxo_manager.virtual_daa_score = virtual_daa_score;
+        utxo_manager.update_utxos_sorted_by_amount(utxos.clone());
+        utxo_manager.update_utxos_by_outpoint(utxos);
+        utxo_manager
+            .apply_mempool_transactions_after_update(&in_mempool.iter().copied().collect())
+            .await;
+    }
+
+    fn tracked(utxo_manager: &UtxoManager, id: TransactionId) -> Option<TrackedMempoolTransaction> {
+        utxo_manager
+            .mempool_transactions()
+            .into_iter()
+            .find(|tracked_transaction| tracked_transaction.id() == id)
+    }
+
+    #[tokio::test]
+    async fn transactions_kaspad_holds_stay_tracked_across_syncs() {
+        let mut utxo_manager = make_utxo_manager(100);
+        let wallet_utxo = make_utxo(false, false, 42);
+        utxo_manager.insert_utxo(wallet_utxo.outpoint.clone(), wallet_utxo.clone());
+        let spend = make_transaction_spending(&wallet_utxo.outpoint);
+        let id = spend.transaction.inner().id();
+        utxo_manager.add_mempool_transaction(&spend).await;
+
+        for virtual_daa_score in [1_000, 5_000] {
+            sync(&mut utxo_manager, vec![], &[id], virtual_daa_score).await;
+            let tracked_transaction = tracked(&utxo_manager, id).unwrap();
+            assert_eq!(tracked_transaction.missing_since_daa_score, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn transactions_kaspad_holds_can_still_be_fee_bumped() {
+        let mut utxo_manager = make_utxo_manager(100);
+        let wallet_utxo = make_utxo(false, false, 42);
+        utxo_manager.insert_utxo(wallet_utxo.outpoint.clone(), wallet_utxo.clone());
+        let spend = make_transaction_spending(&wallet_utxo.outpoint);
+        let id = spend.transaction.inner().id();
+        utxo_manager.add_mempool_transaction(&spend).await;
+
+        // The sync excludes the input kaspad's mempool entry spends, and reports the transaction
+        // as held. BumpFee needs it tracked, with no wallet children, to replace it.
+        sync(&mut utxo_manager, vec![], &[id], 1_000).await;
+        let bumpable = utxo_manager.mempool_transaction(&id).unwrap();
+        assert_eq!(bumpable.transaction.inner().id(), id);
+        assert!(!utxo_manager.has_mempool_descendants(&id));
+    }
+
+    #[tokio::test]
+    async fn transactions_evicted_after_being_seen_expire_a_window_after_going_missing() {
+        let mut utxo_manager = make_utxo_manager(100);
+        let wallet_utxo = make_utxo(false, false, 42);
+        utxo_manager.insert_utxo(wallet_utxo.outpoint.clone(), wallet_utxo.clone());
+        let spend = make_transaction_spending(&wallet_utxo.outpoint);
+        let id = spend.transaction.inner().id();
+        utxo_manager.add_mempool_transaction(&spend).await;
+        sync(&mut utxo_manager, vec![], &[id], 1_000).await;
+
+        // kaspad evicted it, so its input is unspent again. It stays tracked for rebroadcast,
+        // and its input stays spent in the wallet's view.
+        sync(&mut utxo_manager, vec![wallet_utxo.clone()], &[], 2_000).await;
+        let tracked_transaction = tracked(&utxo_manager, id).unwrap();
+        assert_eq!(tracked_transaction.missing_since_daa_score, Some(2_000));
+        assert!(utxo_manager.utxos_sorted_by_amount().is_empty());
+
+        sync(&mut utxo_manager, vec![wallet_utxo.clone()], &[], 2_099).await;
+        assert!(tracked(&utxo_manager, id).is_some());
+
+        sync(&mut utxo_manager, vec![wallet_utxo.clone()], &[], 2_100).await;
+        assert!(tracked(&utxo_manager, id).is_none());
+        assert_eq!(utxo_manager.utxos_sorted_by_amount(), vec![wallet_utxo]);
+    }
+
+    #[tokio::test]
+    async fn transactions_back_in_kaspads_mempool_restart_the_expiry_window() {
+        let mut utxo_manager = make_utxo_manager(100);
+        let wallet_utxo = make_utxo(false, false, 42);
+        utxo_manager.insert_utxo(wallet_utxo.outpoint.clone(), wallet_utxo.clone());
+        let spend = make_transaction_spending(&wallet_utxo.outpoint);
+        let id = spend.transaction.inner().id();
+        utxo_manager.add_mempool_transaction(&spend).await;
+
+        sync(&mut utxo_manager, vec![wallet_utxo.clone()], &[], 1_000).await;
+        sync(&mut utxo_manager, vec![], &[id], 1_050).await;
+        sync(&mut utxo_manager, vec![wallet_utxo.clone()], &[], 1_120).await;
+        let tracked_transaction = tracked(&utxo_manager, id).unwrap();
+        assert_eq!(tracked_transaction.missing_since_daa_score, Some(1_120));
+    }
+
+    #[test]
+    fn is_mempool_transaction_expired_waits_for_window() {
+        assert!(!is_mempool_transaction_expired(100, 500, 599));
+        assert!(is_mempool_transaction_expired(100, 500, 600));
+    }
+
+    #[test]
+    fn is_mempool_transaction_expired_zero_window_disables_expiry() {
+        assert!(!is_mempool_transaction_expired(0, 0, u64::MAX));
+    }
+
+    #[test]
+    fn newly_covenant_bound_utxos_reports_each_outpoint_once() {
+        let covenant_bound = |index: u32| CovenantBoundUtxo {
+            utxo: WalletUtxo {
+                outpoint: WalletOutpoint::new(kaspa_hashes::Hash::from_bytes([1u8; 32]), index),
+                ..make_utxo(false, false, 42)
+            },
+            covenant_id: kaspa_hashes::Hash::from_bytes([2u8; 32]),
+        };
+        let first_sync = vec![covenant_bound(0)];
+        assert_eq!(newly_covenant_bound_utxos(&[], &first_sync).len(), 1);
+
+        let second_sync = vec![covenant_bound(0), covenant_bound(1)];
+        let new = newly_covenant_bound_utxos(&first_sync, &second_sync);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].utxo.outpoint.index, 1);
+
+        assert!(newly_covenant_bound_utxos(&second_sync, &second_sync).is_empty());
+    }
 }
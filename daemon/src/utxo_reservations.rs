@@ -0,0 +1,136 @@
+use common::model::WalletOutpoint;
+use kaspa_consensus_core::tx::TransactionId;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+struct UtxoReservation {
+    transaction_id: TransactionId,
+    expires_at: Instant,
+}
+
+/// Short-lived leases on outpoints selected for an unsigned transaction, so concurrent
+/// create-sign-broadcast flows do not pick the same inputs. Kept in memory only: after a restart
+/// nothing has been broadcast under the old leases yet, and callers simply rebuild.
+#[derive(Debug)]
+pub struct UtxoReservations {
+    // Zero disables reservations.
+    ttl: Duration,
+    by_outpoint: HashMap<WalletOutpoint, UtxoReservation>,
+}
+
+impl UtxoReservations {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            by_outpoint: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Lease `outpoints` to `transaction_id`, replacing any lease they had.
+    pub fn reserve(&mut self, transaction_id: TransactionId, outpoints: &[WalletOutpoint]) {
+        self.reserve_at(transaction_id, outpoints, Instant::now());
+    }
+
+    fn reserve_at(
+        &mut self,
+        transaction_id: TransactionId,
+        outpoints: &[WalletOutpoint],
+        now: Instant,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.prune_expired(now);
+        let expires_at = now + self.ttl;
+        for outpoint in outpoints {
+            self.by_outpoint.insert(
+                outpoint.clone(),
+                UtxoReservation {
+                    transaction_id,
+                    expires_at,
+                },
+            );
+        }
+    }
+
+    pub fn is_reserved(&self, outpoint: &WalletOutpoint) -> bool {
+        self.is_reserved_at(outpoint, Instant::now())
+    }
+
+    fn is_reserved_at(&self, outpoint: &WalletOutpoint, now: Instant) -> bool {
+        self.by_outpoint
+            .get(outpoint)
+            .is_some_and(|reservation| reservation.expires_at > now)
+    }
+
+    /// Drop the leases held by `transaction_id`, returning the outpoints they covered.
+    pub fn release(&mut self, transaction_id: &TransactionId) -> Vec<WalletOutpoint> {
+        let released: Vec<WalletOutpoint> = self
+            .by_outpoint
+            .iter()
+            .filter(|(_, reservation)| reservation.transaction_id == *transaction_id)
+            .map(|(outpoint, _)| outpoint.clone())
+            .collect();
+        for outpoint in &released {
+            self.by_outpoint.remove(outpoint);
+        }
+        released
+    }
+
+    fn prune_expired(&mut self, now: Instant) {
+        self.by_outpoint
+            .retain(|_, reservation| reservation.expires_at > now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outpoint(byte: u8) -> WalletOutpoint {
+        WalletOutpoint::new(kaspa_hashes::Hash::from_bytes([byte; 32]), 0)
+    }
+
+    fn transaction_id(byte: u8) -> TransactionId {
+        kaspa_hashes::Hash::from_bytes([byte; 32])
+    }
+
+    #[test]
+    fn reservation_expires_after_ttl() {
+        let mut reservations = UtxoReservations::new(Duration::from_secs(60));
+        let now = Instant::now();
+        reservations.reserve_at(transaction_id(10), &[outpoint(1)], now);
+
+        assert!(reservations.is_reserved_at(&outpoint(1), now + Duration::from_secs(59)));
+        assert!(!reservations.is_reserved_at(&outpoint(1), now + Duration::from_secs(60)));
+        assert!(!reservations.is_reserved_at(&outpoint(2), now));
+    }
+
+    #[test]
+    fn release_drops_only_the_transactions_leases() {
+        let mut reservations = UtxoReservations::new(Duration::from_secs(60));
+        let now = Instant::now();
+        reservations.reserve_at(transaction_id(10), &[outpoint(1), outpoint(2)], now);
+        reservations.reserve_at(transaction_id(11), &[outpoint(3)], now);
+
+        let mut released = reservations.release(&transaction_id(10));
+        released.sort_by_key(|outpoint| outpoint.transaction_id);
+        assert_eq!(released, vec![outpoint(1), outpoint(2)]);
+        assert!(!reservations.is_reserved_at(&outpoint(1), now));
+        assert!(reservations.is_reserved_at(&outpoint(3), now));
+    }
+
+    #[test]
+    fn zero_ttl_disables_reservations() {
+        let mut reservations = UtxoReservations::new(Duration::ZERO);
+        let now = Instant::now();
+        reservations.reserve_at(transaction_id(10), &[outpoint(1)], now);
+
+        assert!(!reservations.is_reserved_at(&outpoint(1), now));
+    }
+}
@@ -48,8 +48,8 @@ pub async fn test_send_with_explicit_native_subnetwork_id_uses_tx_version_zero()
 // TODO: extend with an auto-compound case (split / merge paths) once we have a
 // fixture that can stage enough UTXOs to exceed MAXIMUM_STANDARD_TRANSACTION_MASS.
 // Both `create_split_transaction` and `merge_transaction` recurse through
-// `generate_unsigned_transaction`, which carries `self.tx_version` and
-// `self.subnetwork_id`, so the field propagates by construction — this test
+// `generate_unsigned_transaction` on the lane of the transaction they
+// compound, so the field propagates by construction — this test
 // would lock that in against future refactors.
 //
 // TODO: once IgraLabs/rusty-kaspa rebases onto the upstream Toccata branch,
@@ -326,6 +326,10 @@ async fn sanity_test(client: &mut KaswalletClient) -> WalletResult<()> {
         address: to_address.clone(),
         available: 0,
         pending: 0,
+        frozen: 0,
+        covenant_bound: 0,
+        dust: 0,
+        label: None,
     };
 
     let to_address_balance_response = balance_println